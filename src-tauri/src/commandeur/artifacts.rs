@@ -0,0 +1,131 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::commandeur::models::{CommandeurOperation, CommandeurProcessArtifact};
+use crate::commandeur::process::ProcessOutput;
+
+const FOLDERS_DIR: &str = "folders";
+const STDOUT_FILE: &str = "stdout.txt";
+const STDERR_FILE: &str = "stderr.txt";
+const RESULT_FILE: &str = "result.json";
+
+/// Répertoire des sorties d'une opération pour un dossier:
+/// `<run>/folders/<dossier>/<séquence>-<opération>`.
+fn artifact_dir(run_dir: &Path, folder: &str, sequence: usize, operation_id: &str) -> PathBuf {
+    let safe_id: String = operation_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    run_dir
+        .join(FOLDERS_DIR)
+        .join(folder)
+        .join(format!("{sequence:03}-{safe_id}"))
+}
+
+pub fn persist_process_output(
+    run_dir: &Path,
+    folder: &str,
+    sequence: usize,
+    operation: &CommandeurOperation,
+    command: &str,
+    output: &ProcessOutput,
+    timed_out: bool,
+) -> Result<CommandeurProcessArtifact> {
+    let dir = artifact_dir(run_dir, folder, sequence, operation.id());
+    fs::create_dir_all(&dir)?;
+    let stdout_path = dir.join(STDOUT_FILE);
+    let stderr_path = dir.join(STDERR_FILE);
+    let result_path = dir.join(RESULT_FILE);
+    fs::write(&stdout_path, &output.stdout)?;
+    fs::write(&stderr_path, &output.stderr)?;
+
+    let artifact = CommandeurProcessArtifact {
+        operation_id: operation.id().to_string(),
+        operation_label: operation.label().to_string(),
+        folder: folder.to_string(),
+        command: command.to_string(),
+        exit_code: output.status.and_then(|status| status.code()),
+        duration_ms: output.duration.as_millis() as u64,
+        timed_out,
+        stdout_path: stdout_path.to_string_lossy().to_string(),
+        stderr_path: stderr_path.to_string_lossy().to_string(),
+        result_path: result_path.to_string_lossy().to_string(),
+    };
+    fs::write(&result_path, serde_json::to_vec_pretty(&artifact)?)?;
+    Ok(artifact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commandeur::models::{OperationDetails, OperationMeta, ShellKind};
+    use std::time::Duration;
+
+    #[test]
+    fn artifacts_are_written_per_folder_and_operation() {
+        let run_dir = tempfile::tempdir().unwrap();
+        let operation = CommandeurOperation {
+            meta: OperationMeta {
+                id: "op/1".into(),
+                label: "Tests".into(),
+                comment: None,
+                enabled: true,
+                continue_on_error: false,
+                matching: None,
+            },
+            details: OperationDetails::Exec {
+                command: "mvn".into(),
+                args: vec!["test".into()],
+                shell: ShellKind::Default,
+                cwd: None,
+                env: None,
+                timeout_seconds: None,
+                capture: None,
+            },
+        };
+        let output = ProcessOutput {
+            status: None,
+            stdout: b"BUILD SUCCESS\n".to_vec(),
+            stderr: b"warning\n".to_vec(),
+            duration: Duration::from_millis(1500),
+        };
+
+        let artifact = persist_process_output(
+            run_dir.path(),
+            "Alice",
+            2,
+            &operation,
+            "mvn test",
+            &output,
+            true,
+        )
+        .unwrap();
+
+        let expected_dir = run_dir
+            .path()
+            .join("folders")
+            .join("Alice")
+            .join("002-op_1");
+        assert_eq!(
+            PathBuf::from(&artifact.stdout_path),
+            expected_dir.join("stdout.txt")
+        );
+        assert_eq!(
+            fs::read_to_string(&artifact.stdout_path).unwrap(),
+            "BUILD SUCCESS\n"
+        );
+        assert_eq!(artifact.duration_ms, 1500);
+        let result: serde_json::Value =
+            serde_json::from_slice(&fs::read(&artifact.result_path).unwrap()).unwrap();
+        assert_eq!(result["timedOut"], true);
+        assert_eq!(result["exitCode"], serde_json::Value::Null);
+    }
+}
@@ -1,13 +1,15 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Result};
-use regex::Regex;
-use walkdir::WalkDir;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 
+use crate::commandeur::globs::{path_to_forward_string, Disk, FolderView, GlobOptions};
 use crate::commandeur::models::{
-    ConditionOperator, ConditionScope, ConditionSelector, ConditionTest,
+    ConditionOperator, ConditionQuantifier, ConditionRunner, ConditionScope, ConditionSelector,
+    ConditionTest, ShellKind,
 };
-use crate::commandeur::workspace::resolve_in_folder;
+use crate::commandeur::utils::build_regex;
 
 #[derive(Debug, Clone)]
 pub struct NormalizedConditionTest {
@@ -16,16 +18,75 @@ pub struct NormalizedConditionTest {
     pub scope: Option<ConditionScope>,
     pub pattern: Option<String>,
     pub value: Option<String>,
+    pub variable: Option<String>,
+    pub flags: Option<String>,
+    pub quantifier: Option<ConditionQuantifier>,
+    pub command: Option<ConditionCommand>,
+    pub ignore_case: bool,
     pub negate: bool,
 }
 
+/// Commande lancée dans le dossier pour évaluer une condition `command-exit-code`.
+#[derive(Debug, Clone)]
+pub struct ConditionCommand {
+    pub runner: ConditionRunner,
+    pub command: String,
+    pub shell: ShellKind,
+    pub timeout_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConditionCommandOutput {
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+}
+
+/// Exécute les commandes des conditions. Seule l'exécution réelle en fournit une qui lance
+/// vraiment la commande; la validation et la simulation utilisent `commands_unavailable`.
+pub type ConditionCommandRunner<'r> =
+    dyn FnMut(&ConditionCommand) -> Result<ConditionCommandOutput> + 'r;
+
+pub fn commands_unavailable(command: &ConditionCommand) -> Result<ConditionCommandOutput> {
+    Err(anyhow!(
+        "La commande de condition \"{}\" n'est lancée qu'à l'exécution",
+        command.command
+    ))
+}
+
+/// Arbre de conditions normalisé: les nœuds `all`, `any` et `not` combinent des tests simples.
+#[derive(Debug, Clone)]
+pub enum NormalizedCondition {
+    Test(NormalizedConditionTest),
+    All(Vec<NormalizedCondition>),
+    Any(Vec<NormalizedCondition>),
+    Not(Box<NormalizedCondition>),
+}
+
 #[derive(Debug, Clone)]
 pub struct ConditionEvaluation {
     pub result: bool,
     pub summary: String,
 }
 
-pub fn normalize_condition(test: &ConditionTest) -> NormalizedConditionTest {
+/// Normalise l'arbre de conditions. Un nœud combiné avec `negate` est enveloppé dans une négation.
+pub fn normalize_condition(test: &ConditionTest) -> NormalizedCondition {
+    let node = if let Some(children) = &test.all {
+        NormalizedCondition::All(children.iter().map(normalize_condition).collect())
+    } else if let Some(children) = &test.any {
+        NormalizedCondition::Any(children.iter().map(normalize_condition).collect())
+    } else if let Some(child) = &test.not {
+        NormalizedCondition::Not(Box::new(normalize_condition(child)))
+    } else {
+        return NormalizedCondition::Test(normalize_condition_test(test));
+    };
+    if test.negate {
+        NormalizedCondition::Not(Box::new(node))
+    } else {
+        node
+    }
+}
+
+fn normalize_condition_test(test: &ConditionTest) -> NormalizedConditionTest {
     let selector = test.selector.unwrap_or(ConditionSelector::FileSearch);
 
     let default_operator = default_operator_for_selector(selector);
@@ -56,20 +117,44 @@ pub fn normalize_condition(test: &ConditionTest) -> NormalizedConditionTest {
                 .map(|s| s.to_string())
                 .unwrap_or_else(String::new),
         ),
-        ConditionSelector::FileCount => Some(
+        ConditionSelector::FileCount
+        | ConditionSelector::TotalSize
+        | ConditionSelector::LineCount => Some(
             test.value
                 .as_ref()
                 .map(|s| s.trim().to_string())
                 .filter(|s| !s.is_empty())
                 .unwrap_or_else(|| "0".to_string()),
         ),
+        ConditionSelector::CapturedValue
+        | ConditionSelector::FileContent
+        | ConditionSelector::CommandExitCode => Some(test.value.clone().unwrap_or_default()),
         _ => normalize_optional_string(&test.value),
     };
 
     let scope = match selector {
-        ConditionSelector::FileSearch | ConditionSelector::FileCount => {
-            Some(test.scope.unwrap_or(ConditionScope::CurrentFolder))
-        }
+        ConditionSelector::FileSearch
+        | ConditionSelector::FileCount
+        | ConditionSelector::FileContent
+        | ConditionSelector::TotalSize
+        | ConditionSelector::NewestModified
+        | ConditionSelector::OldestModified
+        | ConditionSelector::LineCount => Some(test.scope.unwrap_or(ConditionScope::CurrentFolder)),
+        _ => None,
+    };
+
+    let quantifier = match selector {
+        ConditionSelector::FileContent => Some(test.quantifier.unwrap_or(ConditionQuantifier::Any)),
+        _ => None,
+    };
+
+    let command = match selector {
+        ConditionSelector::CommandExitCode => Some(ConditionCommand {
+            runner: test.runner.unwrap_or(ConditionRunner::Command),
+            command: test.command.clone().unwrap_or_default().trim().to_string(),
+            shell: test.shell.clone().unwrap_or(ShellKind::Default),
+            timeout_seconds: test.timeout_seconds.filter(|seconds| *seconds > 0),
+        }),
         _ => None,
     };
 
@@ -79,21 +164,309 @@ pub fn normalize_condition(test: &ConditionTest) -> NormalizedConditionTest {
         scope,
         pattern,
         value,
+        variable: normalize_optional_string(&test.variable),
+        flags: normalize_optional_string(&test.flags),
+        quantifier,
+        command,
+        ignore_case: test.ignore_case,
         negate: test.negate,
     }
 }
 
+/// Nom de la valeur capturée dont dépend la condition, le cas échéant. Ces conditions ne
+/// peuvent être évaluées qu'à l'exécution, une fois la commande lancée.
+pub fn captured_value_dependency(test: &ConditionTest) -> Option<String> {
+    find_leaf(
+        &normalize_condition(test),
+        &|normalized| match normalized.selector {
+            ConditionSelector::CapturedValue => {
+                Some(normalized.variable.clone().unwrap_or_default())
+            }
+            _ => None,
+        },
+    )
+}
+
+/// Commande lancée par la condition, le cas échéant. Ces conditions ont des effets de bord et
+/// ne sont évaluées qu'à l'exécution.
+pub fn command_dependency(test: &ConditionTest) -> Option<String> {
+    find_leaf(&normalize_condition(test), &|normalized| {
+        normalized
+            .command
+            .as_ref()
+            .map(|command| command.command.clone())
+    })
+}
+
+fn find_leaf(
+    node: &NormalizedCondition,
+    select: &dyn Fn(&NormalizedConditionTest) -> Option<String>,
+) -> Option<String> {
+    match node {
+        NormalizedCondition::Test(normalized) => select(normalized),
+        NormalizedCondition::All(children) | NormalizedCondition::Any(children) => {
+            children.iter().find_map(|child| find_leaf(child, select))
+        }
+        NormalizedCondition::Not(child) => find_leaf(child, select),
+    }
+}
+
 pub fn evaluate_condition_for_folder(
     base_path: &Path,
     folder: &str,
     test: &ConditionTest,
+    captured: &HashMap<String, String>,
+    run_command: &mut ConditionCommandRunner,
+) -> Result<ConditionEvaluation> {
+    evaluate_condition_in(&Disk, base_path, folder, test, captured, run_command)
+}
+
+/// Évalue la condition sur le dossier tel que `view` le présente (dossier simulé d'une
+/// simulation).
+pub fn evaluate_condition_in(
+    view: &dyn FolderView,
+    base_path: &Path,
+    folder: &str,
+    test: &ConditionTest,
+    captured: &HashMap<String, String>,
+    run_command: &mut ConditionCommandRunner,
 ) -> Result<ConditionEvaluation> {
-    let normalized = normalize_condition(test);
-    match normalized.selector {
-        ConditionSelector::CurrentFolderName => evaluate_current_folder(folder, &normalized),
-        ConditionSelector::FileSearch => evaluate_file_search(base_path, &normalized),
-        ConditionSelector::FileCount => evaluate_file_count(base_path, &normalized),
+    evaluate_node(
+        view,
+        base_path,
+        folder,
+        &normalize_condition(test),
+        captured,
+        run_command,
+    )
+}
+
+/// Évalue l'arbre avec court-circuit. Le résumé d'un nœud combiné indique la condition
+/// qui a décidé du résultat, ou toutes les conditions lorsqu'elles ont toutes compté.
+fn evaluate_node(
+    view: &dyn FolderView,
+    base_path: &Path,
+    folder: &str,
+    node: &NormalizedCondition,
+    captured: &HashMap<String, String>,
+    run_command: &mut ConditionCommandRunner,
+) -> Result<ConditionEvaluation> {
+    match node {
+        NormalizedCondition::Test(normalized) => match normalized.selector {
+            ConditionSelector::CurrentFolderName => evaluate_current_folder(folder, normalized),
+            ConditionSelector::FileSearch => evaluate_file_search(view, base_path, normalized),
+            ConditionSelector::FileCount => evaluate_file_count(view, base_path, normalized),
+            ConditionSelector::CapturedValue => evaluate_captured_value(captured, normalized),
+            ConditionSelector::FileContent => evaluate_file_content(view, base_path, normalized),
+            ConditionSelector::TotalSize
+            | ConditionSelector::NewestModified
+            | ConditionSelector::OldestModified
+            | ConditionSelector::LineCount => evaluate_file_metric(view, base_path, normalized),
+            ConditionSelector::CommandExitCode => evaluate_command(normalized, run_command),
+        },
+        NormalizedCondition::All(children) => evaluate_group(
+            view,
+            base_path,
+            folder,
+            children,
+            captured,
+            run_command,
+            false,
+        ),
+        NormalizedCondition::Any(children) => evaluate_group(
+            view,
+            base_path,
+            folder,
+            children,
+            captured,
+            run_command,
+            true,
+        ),
+        NormalizedCondition::Not(child) => {
+            let inner = evaluate_node(view, base_path, folder, child, captured, run_command)?;
+            let result = !inner.result;
+            Ok(ConditionEvaluation {
+                result,
+                summary: format!("NON [{}] => {}", inner.summary, truth_label(result)),
+            })
+        }
+    }
+}
+
+/// `decisive` vaut `true` pour un OU (la première condition vraie décide) et `false` pour
+/// un ET (la première condition fausse décide).
+fn evaluate_group(
+    view: &dyn FolderView,
+    base_path: &Path,
+    folder: &str,
+    children: &[NormalizedCondition],
+    captured: &HashMap<String, String>,
+    run_command: &mut ConditionCommandRunner,
+    decisive: bool,
+) -> Result<ConditionEvaluation> {
+    let label = if decisive {
+        "Au moins une condition (OU)"
+    } else {
+        "Toutes les conditions (ET)"
+    };
+    let total = children.len();
+    let mut summaries = Vec::with_capacity(total);
+    for (index, child) in children.iter().enumerate() {
+        let evaluation = evaluate_node(view, base_path, folder, child, captured, run_command)?;
+        if evaluation.result == decisive {
+            return Ok(ConditionEvaluation {
+                result: decisive,
+                summary: format!(
+                    "{label} => {}, décidé par la condition {}/{}: {}",
+                    truth_label(decisive),
+                    index + 1,
+                    total,
+                    evaluation.summary
+                ),
+            });
+        }
+        summaries.push(format!("[{}]", evaluation.summary));
     }
+    let result = !decisive;
+    let summary = if summaries.is_empty() {
+        format!("{label} => {} (aucune condition)", truth_label(result))
+    } else {
+        format!(
+            "{label} => {}: {}",
+            truth_label(result),
+            summaries.join(" ; ")
+        )
+    };
+    Ok(ConditionEvaluation { result, summary })
+}
+
+/// Les opérateurs de comparaison portent sur le code de sortie (0 par défaut), les opérateurs
+/// textuels sur la sortie standard.
+fn evaluate_command(
+    normalized: &NormalizedConditionTest,
+    run_command: &mut ConditionCommandRunner,
+) -> Result<ConditionEvaluation> {
+    let command = normalized
+        .command
+        .as_ref()
+        .filter(|command| !command.command.is_empty())
+        .ok_or_else(|| anyhow!("Commande de condition manquante"))?;
+    let value = normalized.value.clone().unwrap_or_default();
+    let output = run_command(command)?;
+    let exit_code = output.exit_code;
+
+    let (raw_result, compared) = match normalized.operator {
+        ConditionOperator::Equals
+        | ConditionOperator::GreaterThan
+        | ConditionOperator::LessThan => {
+            let expected_str = if value.trim().is_empty() {
+                "0"
+            } else {
+                value.trim()
+            };
+            let expected: i32 = expected_str
+                .parse()
+                .map_err(|_| anyhow!("Code de sortie invalide: {}", expected_str))?;
+            let result = exit_code.map_or(false, |code| match normalized.operator {
+                ConditionOperator::GreaterThan => code > expected,
+                ConditionOperator::LessThan => code < expected,
+                _ => code == expected,
+            });
+            (
+                result,
+                format!(
+                    "code de sortie {} comparé {} {}",
+                    exit_code.map_or_else(|| "absent".to_string(), |code| code.to_string()),
+                    operator_symbol(normalized.operator),
+                    expected
+                ),
+            )
+        }
+        operator => {
+            let result = match operator {
+                ConditionOperator::Regex => {
+                    build_regex(&value, normalized.flags.as_deref())?.is_match(&output.stdout)
+                }
+                ConditionOperator::NotContains => !output.stdout.contains(&value),
+                _ => output.stdout.contains(&value),
+            };
+            (
+                result,
+                format!(
+                    "sortie standard {} \"{}\"",
+                    operator_phrase_for_content(operator),
+                    value
+                ),
+            )
+        }
+    };
+
+    let final_result = apply_negate(raw_result, normalized.negate);
+    let summary = format!(
+        "Commande \"{}\" -> {}, résultat {}{}",
+        command.command,
+        compared,
+        truth_label(final_result),
+        negate_suffix(normalized.negate),
+    );
+
+    Ok(ConditionEvaluation {
+        result: final_result,
+        summary,
+    })
+}
+
+fn evaluate_captured_value(
+    captured: &HashMap<String, String>,
+    normalized: &NormalizedConditionTest,
+) -> Result<ConditionEvaluation> {
+    let name = normalized
+        .variable
+        .clone()
+        .ok_or_else(|| anyhow!("Nom de la valeur capturée manquant"))?;
+    let captured_value = captured.get(&name);
+    let expected = normalized.value.clone().unwrap_or_default();
+
+    let raw_result = match (normalized.operator, captured_value) {
+        (ConditionOperator::Exists, value) => value.is_some(),
+        (ConditionOperator::NotExists, value) => value.is_none(),
+        (_, None) => return Err(anyhow!("Aucune valeur capturée sous le nom {name}")),
+        (ConditionOperator::Equals, Some(value)) => value.trim() == expected.trim(),
+        (ConditionOperator::Contains, Some(value)) => value.contains(&expected),
+        (ConditionOperator::NotContains, Some(value)) => !value.contains(&expected),
+        (ConditionOperator::Regex, Some(value)) => {
+            build_regex(&expected, normalized.flags.as_deref())?.is_match(value)
+        }
+        (ConditionOperator::GreaterThan, Some(value)) => {
+            parse_number(value)? > parse_number(&expected)?
+        }
+        (ConditionOperator::LessThan, Some(value)) => {
+            parse_number(value)? < parse_number(&expected)?
+        }
+    };
+
+    let final_result = apply_negate(raw_result, normalized.negate);
+    let summary = format!(
+        "Valeur capturée {} ({}) {} \"{}\" => {}{}",
+        name,
+        captured_value.map(String::as_str).unwrap_or("absente"),
+        operator_phrase_for_captured(normalized.operator),
+        expected,
+        truth_label(final_result),
+        negate_suffix(normalized.negate),
+    );
+
+    Ok(ConditionEvaluation {
+        result: final_result,
+        summary,
+    })
+}
+
+fn parse_number(value: &str) -> Result<f64> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("Valeur numérique attendue: {}", value.trim()))
 }
 
 fn evaluate_current_folder(
@@ -109,7 +482,7 @@ fn evaluate_current_folder(
             if value.trim().is_empty() {
                 false
             } else {
-                let regex = build_regex(&value)?;
+                let regex = build_regex(&value, normalized.flags.as_deref())?;
                 regex.is_match(folder)
             }
         }
@@ -133,6 +506,7 @@ fn evaluate_current_folder(
 }
 
 fn evaluate_file_search(
+    view: &dyn FolderView,
     base_path: &Path,
     normalized: &NormalizedConditionTest,
 ) -> Result<ConditionEvaluation> {
@@ -141,7 +515,7 @@ fn evaluate_file_search(
         .clone()
         .ok_or_else(|| anyhow!("Motif de recherche manquant"))?;
     let scope = normalized.scope.unwrap_or(ConditionScope::CurrentFolder);
-    let matches = collect_matches(base_path, &pattern, scope)?;
+    let matches = collect_matches(view, base_path, &pattern, scope, normalized.ignore_case)?;
     let raw_result = match normalized.operator {
         ConditionOperator::Exists => !matches.is_empty(),
         ConditionOperator::NotExists => matches.is_empty(),
@@ -164,7 +538,81 @@ fn evaluate_file_search(
     })
 }
 
+/// Cherche `value` dans chaque fichier correspondant au motif. Avec le quantificateur `all`,
+/// tous les fichiers doivent satisfaire l'opérateur; sans fichier, la condition est fausse.
+fn evaluate_file_content(
+    view: &dyn FolderView,
+    base_path: &Path,
+    normalized: &NormalizedConditionTest,
+) -> Result<ConditionEvaluation> {
+    let pattern = normalized
+        .pattern
+        .clone()
+        .ok_or_else(|| anyhow!("Motif de fichier manquant"))?;
+    let scope = normalized.scope.unwrap_or(ConditionScope::CurrentFolder);
+    let quantifier = normalized.quantifier.unwrap_or(ConditionQuantifier::Any);
+    let value = normalized.value.clone().unwrap_or_default();
+    let regex = match normalized.operator {
+        ConditionOperator::Regex => Some(build_regex(&value, normalized.flags.as_deref())?),
+        _ => None,
+    };
+
+    let files: Vec<PathBuf> =
+        collect_matches(view, base_path, &pattern, scope, normalized.ignore_case)?
+            .into_iter()
+            .filter(|path| view.is_file(path))
+            .collect();
+    let mut matching = 0usize;
+    let mut first_mismatch: Option<String> = None;
+    for file in &files {
+        let bytes = view
+            .read(file)
+            .map_err(|err| anyhow!("Lecture impossible de {}: {}", file.display(), err))?;
+        let content = String::from_utf8_lossy(&bytes);
+        let matched = match (normalized.operator, &regex) {
+            (ConditionOperator::Regex, Some(regex)) => regex.is_match(&content),
+            (ConditionOperator::NotContains, _) => !content.contains(&value),
+            _ => content.contains(&value),
+        };
+        if matched {
+            matching += 1;
+        } else if first_mismatch.is_none() {
+            let rel = file.strip_prefix(base_path).unwrap_or(file);
+            first_mismatch = Some(path_to_forward_string(rel));
+        }
+    }
+
+    let raw_result = match quantifier {
+        ConditionQuantifier::Any => matching > 0,
+        ConditionQuantifier::All => !files.is_empty() && matching == files.len(),
+    };
+    let final_result = apply_negate(raw_result, normalized.negate);
+    let detail = match (quantifier, first_mismatch) {
+        (ConditionQuantifier::All, Some(file)) => format!(", premier fichier non conforme: {file}"),
+        _ => String::new(),
+    };
+    let summary = format!(
+        "Contenu des fichiers \"{}\" (portée: {}) {} \"{}\" -> {}/{} fichier(s) conforme(s) ({}){}, résultat {}{}",
+        pattern,
+        scope_label(scope),
+        operator_phrase_for_content(normalized.operator),
+        value,
+        matching,
+        files.len(),
+        quantifier_label(quantifier),
+        detail,
+        truth_label(final_result),
+        negate_suffix(normalized.negate),
+    );
+
+    Ok(ConditionEvaluation {
+        result: final_result,
+        summary,
+    })
+}
+
 fn evaluate_file_count(
+    view: &dyn FolderView,
     base_path: &Path,
     normalized: &NormalizedConditionTest,
 ) -> Result<ConditionEvaluation> {
@@ -173,8 +621,8 @@ fn evaluate_file_count(
         .clone()
         .unwrap_or_else(|| "*".to_string());
     let scope = normalized.scope.unwrap_or(ConditionScope::CurrentFolder);
-    let matches = collect_matches(base_path, &pattern, scope)?;
-    let file_count = matches.iter().filter(|path| path.is_file()).count() as i64;
+    let matches = collect_matches(view, base_path, &pattern, scope, normalized.ignore_case)?;
+    let file_count = matches.iter().filter(|path| view.is_file(path)).count() as i64;
 
     let threshold_str = normalized.value.clone().unwrap_or_else(|| "0".to_string());
     let threshold: i64 = threshold_str
@@ -206,126 +654,241 @@ fn evaluate_file_count(
     })
 }
 
-fn collect_matches(base_path: &Path, pattern: &str, scope: ConditionScope) -> Result<Vec<PathBuf>> {
-    let normalized_pattern = pattern.replace('\\', "/");
-    let has_wildcards = normalized_pattern.contains('*') || normalized_pattern.contains('?');
-
-    if !has_wildcards {
-        let target = resolve_in_folder(base_path, &normalized_pattern)?;
-        if target.exists() {
-            return Ok(vec![target]);
-        }
-        return Ok(Vec::new());
-    }
-
-    let regex = wildcard_to_regex(&normalized_pattern)?;
-    let mut matches = Vec::new();
-    let mut iterator = WalkDir::new(base_path).follow_links(false).into_iter();
-    let max_depth = match scope {
-        ConditionScope::CurrentFolder => depth_hint(&normalized_pattern),
-        ConditionScope::Recursive => usize::MAX,
-    };
+/// Compare une mesure des fichiers correspondant au motif (taille totale, date de modification
+/// ou nombre de lignes) à une valeur avec unité: `10MB`, `2025-03-01T23:59`, `500 lignes`.
+fn evaluate_file_metric(
+    view: &dyn FolderView,
+    base_path: &Path,
+    normalized: &NormalizedConditionTest,
+) -> Result<ConditionEvaluation> {
+    let pattern = normalized
+        .pattern
+        .clone()
+        .unwrap_or_else(|| "*".to_string());
+    let scope = normalized.scope.unwrap_or(ConditionScope::CurrentFolder);
+    let raw_value = normalized.value.clone().unwrap_or_default();
+    let files: Vec<PathBuf> =
+        collect_matches(view, base_path, &pattern, scope, normalized.ignore_case)?
+            .into_iter()
+            .filter(|path| view.is_file(path))
+            .collect();
 
-    while let Some(entry) = iterator.next() {
-        let entry = match entry {
-            Ok(value) => value,
-            Err(err) => return Err(anyhow!(err)),
-        };
-        let depth = entry.depth();
-        if depth == 0 {
-            continue;
+    let (label, measured, expected, ordering) = match normalized.selector {
+        ConditionSelector::TotalSize => {
+            let expected = parse_size(&raw_value)?;
+            let mut total = 0u64;
+            for file in &files {
+                total += view.size(file)?;
+            }
+            (
+                "Taille totale",
+                format_size(total),
+                format_size(expected),
+                Some(total.cmp(&expected)),
+            )
         }
-        if scope == ConditionScope::CurrentFolder && depth > max_depth {
-            if entry.file_type().is_dir() {
-                iterator.skip_current_dir();
+        ConditionSelector::LineCount => {
+            let expected = parse_line_count(&raw_value)?;
+            let mut total = 0u64;
+            for file in &files {
+                total += count_lines(&view.read(file)?);
             }
-            continue;
+            (
+                "Nombre de lignes",
+                format!("{total} ligne(s)"),
+                format!("{expected} ligne(s)"),
+                Some(total.cmp(&expected)),
+            )
         }
-        let rel = entry
-            .path()
-            .strip_prefix(base_path)
-            .map_err(|err| anyhow!(err))?;
-        let rel_str = path_to_forward_string(rel);
-        if regex.is_match(&rel_str) {
-            matches.push(entry.into_path());
+        _ => {
+            let newest = normalized.selector == ConditionSelector::NewestModified;
+            let (deadline, precision) = parse_deadline(&raw_value)?;
+            let mut selected: Option<NaiveDateTime> = None;
+            for file in &files {
+                let modified = DateTime::<Local>::from(view.modified(file)?).naive_local();
+                selected = match selected {
+                    Some(current) if (modified > current) != newest => Some(current),
+                    _ => Some(modified),
+                };
+            }
+            let label = if newest {
+                "Modification la plus récente"
+            } else {
+                "Modification la plus ancienne"
+            };
+            let expected = deadline.format(precision.format()).to_string();
+            // Comparaison à la précision de la date saisie: une échéance à la minute inclut
+            // toute la minute.
+            match selected {
+                Some(modified) => {
+                    let modified = precision.truncate(modified);
+                    let measured = modified.format(precision.format()).to_string();
+                    (label, measured, expected, Some(modified.cmp(&deadline)))
+                }
+                None => {
+                    // Sans fichier, il n'y a pas de date à comparer: la condition est fausse,
+                    // même inversée.
+                    let summary = format!(
+                        "{} \"{}\" (portée: {}) -> aucun fichier, rien à comparer {} {}, résultat faux",
+                        label,
+                        pattern,
+                        scope_label(scope),
+                        operator_symbol(normalized.operator),
+                        expected,
+                    );
+                    return Ok(ConditionEvaluation {
+                        result: false,
+                        summary,
+                    });
+                }
+            }
         }
+    };
+
+    let raw_result = match (normalized.operator, ordering) {
+        (ConditionOperator::Equals, Some(ordering)) => ordering == std::cmp::Ordering::Equal,
+        (ConditionOperator::GreaterThan, Some(ordering)) => ordering == std::cmp::Ordering::Greater,
+        (ConditionOperator::LessThan, Some(ordering)) => ordering == std::cmp::Ordering::Less,
+        _ => false,
+    };
+
+    let final_result = apply_negate(raw_result, normalized.negate);
+    let summary = format!(
+        "{} \"{}\" (portée: {}) -> {}, comparé {} {}, résultat {}{}",
+        label,
+        pattern,
+        scope_label(scope),
+        measured,
+        operator_symbol(normalized.operator),
+        expected,
+        truth_label(final_result),
+        negate_suffix(normalized.negate),
+    );
+
+    Ok(ConditionEvaluation {
+        result: final_result,
+        summary,
+    })
+}
+
+fn split_number(value: &str) -> (&str, &str) {
+    let trimmed = value.trim();
+    let end = trimmed
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+        .unwrap_or(trimmed.len());
+    (&trimmed[..end], trimmed[end..].trim())
+}
+
+fn parse_size(value: &str) -> Result<u64> {
+    let (number, unit) = split_number(value);
+    let invalid = || anyhow!("Taille invalide: {} (ex. 10MB, 512 Ko)", value.trim());
+    let number: f64 = number.replace(',', ".").parse().map_err(|_| invalid())?;
+    let multiplier: u64 = match unit.to_lowercase().as_str() {
+        "" | "b" | "o" | "octet" | "octets" | "byte" | "bytes" => 1,
+        "k" | "kb" | "ko" | "kib" | "kio" => 1024,
+        "m" | "mb" | "mo" | "mib" | "mio" => 1024 * 1024,
+        "g" | "gb" | "go" | "gib" | "gio" => 1024 * 1024 * 1024,
+        _ => return Err(invalid()),
+    };
+    Ok((number * multiplier as f64).round() as u64)
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["o", "Ko", "Mo", "Go"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
     }
+    if unit == 0 {
+        format!("{bytes} o")
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
 
-    Ok(matches)
+fn parse_line_count(value: &str) -> Result<u64> {
+    let (number, unit) = split_number(value);
+    match unit.to_lowercase().as_str() {
+        "" | "line" | "lines" | "ligne" | "lignes" => number
+            .parse()
+            .map_err(|_| anyhow!("Nombre de lignes invalide: {}", value.trim())),
+        _ => Err(anyhow!("Nombre de lignes invalide: {}", value.trim())),
+    }
 }
 
-fn wildcard_to_regex(pattern: &str) -> Result<Regex> {
-    let mut regex = String::from("^");
-    let mut literal = String::new();
-    let mut chars = pattern.chars().peekable();
+fn count_lines(bytes: &[u8]) -> u64 {
+    let newlines = bytes.iter().filter(|byte| **byte == b'\n').count() as u64;
+    match bytes.last() {
+        Some(last) if *last != b'\n' => newlines + 1,
+        _ => newlines,
+    }
+}
 
-    while let Some(ch) = chars.next() {
-        match ch {
-            '*' => {
-                if !literal.is_empty() {
-                    regex.push_str(&regex::escape(&literal));
-                    literal.clear();
-                }
-                if matches!(chars.peek(), Some('*')) {
-                    while matches!(chars.peek(), Some('*')) {
-                        chars.next();
-                    }
-                    regex.push_str(".*");
-                } else {
-                    regex.push_str("[^/]*");
-                }
-            }
-            '?' => {
-                if !literal.is_empty() {
-                    regex.push_str(&regex::escape(&literal));
-                    literal.clear();
-                }
-                regex.push_str("[^/]");
-            }
-            '/' => {
-                if !literal.is_empty() {
-                    regex.push_str(&regex::escape(&literal));
-                    literal.clear();
-                }
-                regex.push('/');
-            }
-            '\\' => {
-                if !literal.is_empty() {
-                    regex.push_str(&regex::escape(&literal));
-                    literal.clear();
-                }
-                regex.push('/');
-            }
-            _ => literal.push(ch),
+/// Retourne l'échéance normalisée et le format correspondant à sa précision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeadlinePrecision {
+    Day,
+    Minute,
+    Second,
+}
+
+impl DeadlinePrecision {
+    fn format(self) -> &'static str {
+        match self {
+            DeadlinePrecision::Day => "%Y-%m-%d",
+            DeadlinePrecision::Minute => "%Y-%m-%dT%H:%M",
+            DeadlinePrecision::Second => "%Y-%m-%dT%H:%M:%S",
         }
     }
 
-    if !literal.is_empty() {
-        regex.push_str(&regex::escape(&literal));
+    fn truncate(self, value: NaiveDateTime) -> NaiveDateTime {
+        let time = value.time();
+        let truncated = match self {
+            DeadlinePrecision::Day => NaiveTime::MIN,
+            DeadlinePrecision::Minute => {
+                NaiveTime::from_hms_opt(time.hour(), time.minute(), 0).unwrap_or(time)
+            }
+            DeadlinePrecision::Second => {
+                NaiveTime::from_hms_opt(time.hour(), time.minute(), time.second()).unwrap_or(time)
+            }
+        };
+        value.date().and_time(truncated)
     }
-
-    regex.push('$');
-    Regex::new(&regex).map_err(|err| anyhow!(err))
 }
 
-fn depth_hint(pattern: &str) -> usize {
-    let cleaned = pattern.trim_matches('/');
-    if cleaned.is_empty() {
-        1
-    } else {
-        cleaned
-            .split('/')
-            .filter(|segment| !segment.is_empty())
-            .count()
-            .max(1)
+fn parse_deadline(value: &str) -> Result<(NaiveDateTime, DeadlinePrecision)> {
+    let normalized = value.trim().replacen(' ', "T", 1);
+    for precision in [DeadlinePrecision::Second, DeadlinePrecision::Minute] {
+        if let Ok(parsed) = NaiveDateTime::parse_from_str(&normalized, precision.format()) {
+            return Ok((parsed, precision));
+        }
     }
+    if let Ok(parsed) = NaiveDate::parse_from_str(&normalized, DeadlinePrecision::Day.format()) {
+        return Ok((parsed.and_time(NaiveTime::MIN), DeadlinePrecision::Day));
+    }
+    Err(anyhow!(
+        "Date invalide: {} (ex. 2025-03-01T23:59)",
+        value.trim()
+    ))
 }
 
-fn path_to_forward_string(path: &Path) -> String {
-    path.components()
-        .map(|component| component.as_os_str().to_string_lossy())
-        .collect::<Vec<_>>()
-        .join("/")
+fn collect_matches(
+    view: &dyn FolderView,
+    base_path: &Path,
+    pattern: &str,
+    scope: ConditionScope,
+    case_insensitive: bool,
+) -> Result<Vec<PathBuf>> {
+    view.find(
+        base_path,
+        pattern,
+        GlobOptions {
+            recursive: scope == ConditionScope::Recursive,
+            case_insensitive,
+        },
+    )
 }
 
 fn default_operator_for_selector(selector: ConditionSelector) -> ConditionOperator {
@@ -333,6 +896,13 @@ fn default_operator_for_selector(selector: ConditionSelector) -> ConditionOperat
         ConditionSelector::CurrentFolderName => ConditionOperator::Equals,
         ConditionSelector::FileSearch => ConditionOperator::Exists,
         ConditionSelector::FileCount => ConditionOperator::Equals,
+        ConditionSelector::CapturedValue => ConditionOperator::Equals,
+        ConditionSelector::FileContent => ConditionOperator::Contains,
+        ConditionSelector::TotalSize
+        | ConditionSelector::NewestModified
+        | ConditionSelector::OldestModified
+        | ConditionSelector::LineCount => ConditionOperator::GreaterThan,
+        ConditionSelector::CommandExitCode => ConditionOperator::Equals,
     }
 }
 
@@ -346,12 +916,25 @@ fn is_operator_allowed(selector: ConditionSelector, operator: ConditionOperator)
             operator,
             ConditionOperator::Exists | ConditionOperator::NotExists
         ),
-        ConditionSelector::FileCount => matches!(
+        ConditionSelector::FileCount
+        | ConditionSelector::TotalSize
+        | ConditionSelector::NewestModified
+        | ConditionSelector::OldestModified
+        | ConditionSelector::LineCount => matches!(
             operator,
             ConditionOperator::Equals
                 | ConditionOperator::GreaterThan
                 | ConditionOperator::LessThan
         ),
+        ConditionSelector::CapturedValue => true,
+        ConditionSelector::CommandExitCode => !matches!(
+            operator,
+            ConditionOperator::Exists | ConditionOperator::NotExists
+        ),
+        ConditionSelector::FileContent => matches!(
+            operator,
+            ConditionOperator::Contains | ConditionOperator::NotContains | ConditionOperator::Regex
+        ),
     }
 }
 
@@ -364,6 +947,34 @@ fn operator_phrase_for_folder(operator: ConditionOperator) -> &'static str {
     }
 }
 
+fn operator_phrase_for_captured(operator: ConditionOperator) -> &'static str {
+    match operator {
+        ConditionOperator::Equals => "doit être égale à",
+        ConditionOperator::Contains => "doit contenir",
+        ConditionOperator::NotContains => "ne doit pas contenir",
+        ConditionOperator::Regex => "doit correspondre à la regex",
+        ConditionOperator::Exists => "doit exister",
+        ConditionOperator::NotExists => "ne doit pas exister",
+        ConditionOperator::GreaterThan => "doit être supérieure à",
+        ConditionOperator::LessThan => "doit être inférieure à",
+    }
+}
+
+fn operator_phrase_for_content(operator: ConditionOperator) -> &'static str {
+    match operator {
+        ConditionOperator::NotContains => "ne doit pas contenir",
+        ConditionOperator::Regex => "doit correspondre à la regex",
+        _ => "doit contenir",
+    }
+}
+
+fn quantifier_label(quantifier: ConditionQuantifier) -> &'static str {
+    match quantifier {
+        ConditionQuantifier::Any => "au moins un fichier",
+        ConditionQuantifier::All => "tous les fichiers",
+    }
+}
+
 fn scope_label(scope: ConditionScope) -> &'static str {
     match scope {
         ConditionScope::CurrentFolder => "dossier courant",
@@ -411,6 +1022,311 @@ fn apply_negate(value: bool, negate: bool) -> bool {
     }
 }
 
-fn build_regex(pattern: &str) -> Result<Regex> {
-    Regex::new(pattern).map_err(|err| anyhow!(err))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn leaf(
+        selector: ConditionSelector,
+        operator: ConditionOperator,
+        pattern: &str,
+    ) -> ConditionTest {
+        ConditionTest {
+            selector: Some(selector),
+            operator: Some(operator),
+            value: None,
+            pattern: Some(pattern.to_string()),
+            scope: None,
+            exists: None,
+            variable: None,
+            flags: None,
+            quantifier: None,
+            command: None,
+            runner: None,
+            shell: None,
+            timeout_seconds: None,
+            ignore_case: false,
+            all: None,
+            any: None,
+            not: None,
+            negate: false,
+        }
+    }
+
+    fn group(all: Option<Vec<ConditionTest>>, any: Option<Vec<ConditionTest>>) -> ConditionTest {
+        ConditionTest {
+            all,
+            any,
+            ..leaf(ConditionSelector::FileSearch, ConditionOperator::Exists, "")
+        }
+    }
+
+    #[test]
+    fn compound_conditions_report_the_deciding_branch() {
+        let folder = tempfile::tempdir().unwrap();
+        fs::write(folder.path().join("pom.xml"), "<project/>").unwrap();
+        fs::create_dir(folder.path().join("target")).unwrap();
+
+        let has_pom = leaf(
+            ConditionSelector::FileSearch,
+            ConditionOperator::Exists,
+            "pom.xml",
+        );
+        let no_target = leaf(
+            ConditionSelector::FileSearch,
+            ConditionOperator::NotExists,
+            "target",
+        );
+        let test = group(Some(vec![has_pom.clone(), no_target.clone()]), None);
+        let evaluation = evaluate_condition_for_folder(
+            folder.path(),
+            "Alice",
+            &test,
+            &HashMap::new(),
+            &mut commands_unavailable,
+        )
+        .unwrap();
+        assert!(!evaluation.result);
+        assert!(evaluation.summary.contains("décidé par la condition 2/2"));
+
+        let test = group(None, Some(vec![no_target, has_pom]));
+        let evaluation = evaluate_condition_for_folder(
+            folder.path(),
+            "Alice",
+            &test,
+            &HashMap::new(),
+            &mut commands_unavailable,
+        )
+        .unwrap();
+        assert!(evaluation.result);
+        assert!(evaluation.summary.contains("décidé par la condition 2/2"));
+
+        let mut negated = group(Some(Vec::new()), None);
+        negated.negate = true;
+        let evaluation = evaluate_condition_for_folder(
+            folder.path(),
+            "Alice",
+            &negated,
+            &HashMap::new(),
+            &mut commands_unavailable,
+        )
+        .unwrap();
+        assert!(!evaluation.result);
+        assert!(evaluation.summary.starts_with("NON ["));
+    }
+
+    #[test]
+    fn file_content_applies_quantifier_over_matched_files() {
+        let folder = tempfile::tempdir().unwrap();
+        fs::create_dir(folder.path().join("src")).unwrap();
+        fs::write(
+            folder.path().join("src").join("Main.java"),
+            "public static void main(String[] args) {}",
+        )
+        .unwrap();
+        fs::write(folder.path().join("src").join("Util.java"), "class Util {}").unwrap();
+
+        let mut test = leaf(
+            ConditionSelector::FileContent,
+            ConditionOperator::Regex,
+            "**/*.java",
+        );
+        test.value = Some(r"PUBLIC\s+STATIC\s+VOID\s+MAIN".to_string());
+        test.flags = Some("i".to_string());
+        test.scope = Some(ConditionScope::Recursive);
+        let evaluation = evaluate_condition_for_folder(
+            folder.path(),
+            "Alice",
+            &test,
+            &HashMap::new(),
+            &mut commands_unavailable,
+        )
+        .unwrap();
+        assert!(evaluation.result);
+
+        test.quantifier = Some(ConditionQuantifier::All);
+        let evaluation = evaluate_condition_for_folder(
+            folder.path(),
+            "Alice",
+            &test,
+            &HashMap::new(),
+            &mut commands_unavailable,
+        )
+        .unwrap();
+        assert!(!evaluation.result);
+        assert!(evaluation.summary.contains("src/Util.java"));
+
+        test.pattern = Some("*.java".to_string());
+        test.scope = Some(ConditionScope::CurrentFolder);
+        let evaluation = evaluate_condition_for_folder(
+            folder.path(),
+            "Alice",
+            &test,
+            &HashMap::new(),
+            &mut commands_unavailable,
+        )
+        .unwrap();
+        assert!(!evaluation.result);
+        assert!(evaluation.summary.contains("0/0"));
+    }
+
+    #[test]
+    fn metric_selectors_parse_units() {
+        assert_eq!(parse_size("10MB").unwrap(), 10 * 1024 * 1024);
+        assert_eq!(parse_size("1,5 ko").unwrap(), 1536);
+        assert!(parse_size("10 parsecs").is_err());
+        assert_eq!(parse_line_count("500 lines").unwrap(), 500);
+        assert_eq!(
+            parse_deadline("2025-03-01 23:59").unwrap(),
+            (
+                NaiveDate::from_ymd_opt(2025, 3, 1)
+                    .unwrap()
+                    .and_hms_opt(23, 59, 0)
+                    .unwrap(),
+                DeadlinePrecision::Minute
+            )
+        );
+
+        let folder = tempfile::tempdir().unwrap();
+        fs::write(folder.path().join("a.txt"), "un\ndeux\ntrois").unwrap();
+        fs::write(folder.path().join("b.txt"), "quatre\n").unwrap();
+
+        let mut test = leaf(
+            ConditionSelector::LineCount,
+            ConditionOperator::Equals,
+            "*.txt",
+        );
+        test.value = Some("4 lignes".to_string());
+        let evaluation = evaluate_condition_for_folder(
+            folder.path(),
+            "Alice",
+            &test,
+            &HashMap::new(),
+            &mut commands_unavailable,
+        )
+        .unwrap();
+        assert!(evaluation.result, "{}", evaluation.summary);
+
+        let mut test = leaf(
+            ConditionSelector::TotalSize,
+            ConditionOperator::LessThan,
+            "*",
+        );
+        test.value = Some("1 Ko".to_string());
+        let evaluation = evaluate_condition_for_folder(
+            folder.path(),
+            "Alice",
+            &test,
+            &HashMap::new(),
+            &mut commands_unavailable,
+        )
+        .unwrap();
+        assert!(evaluation.result, "{}", evaluation.summary);
+
+        let mut test = leaf(
+            ConditionSelector::NewestModified,
+            ConditionOperator::GreaterThan,
+            "*.txt",
+        );
+        test.value = Some("2000-01-01".to_string());
+        let evaluation = evaluate_condition_for_folder(
+            folder.path(),
+            "Alice",
+            &test,
+            &HashMap::new(),
+            &mut commands_unavailable,
+        )
+        .unwrap();
+        assert!(evaluation.result, "{}", evaluation.summary);
+    }
+
+    #[test]
+    fn modification_dates_compare_at_the_deadline_precision() {
+        let modified = NaiveDate::from_ymd_opt(2025, 3, 1)
+            .unwrap()
+            .and_hms_milli_opt(23, 59, 42, 500)
+            .unwrap();
+        let (deadline, precision) = parse_deadline("2025-03-01T23:59").unwrap();
+        assert_eq!(precision.truncate(modified), deadline);
+        let (deadline, precision) = parse_deadline("2025-03-01").unwrap();
+        assert_eq!(precision.truncate(modified), deadline);
+        let (deadline, precision) = parse_deadline("2025-03-01 23:59:41").unwrap();
+        assert!(precision.truncate(modified) > deadline);
+    }
+
+    #[test]
+    fn modification_date_without_file_is_false_even_negated() {
+        let folder = tempfile::tempdir().unwrap();
+        for negate in [false, true] {
+            let mut test = leaf(
+                ConditionSelector::OldestModified,
+                ConditionOperator::LessThan,
+                "*.class",
+            );
+            test.value = Some("2100-01-01".to_string());
+            test.negate = negate;
+            let evaluation = evaluate_condition_for_folder(
+                folder.path(),
+                "Alice",
+                &test,
+                &HashMap::new(),
+                &mut commands_unavailable,
+            )
+            .unwrap();
+            assert!(!evaluation.result, "{}", evaluation.summary);
+            assert!(evaluation.summary.contains("aucun fichier"));
+        }
+    }
+
+    #[test]
+    fn command_condition_compares_exit_code_or_stdout() {
+        let folder = tempfile::tempdir().unwrap();
+        let mut test = leaf(
+            ConditionSelector::CommandExitCode,
+            ConditionOperator::Equals,
+            "",
+        );
+        test.command = Some("javac Main.java".to_string());
+        let mut launched = Vec::new();
+        let mut runner = |command: &ConditionCommand| {
+            launched.push(command.command.clone());
+            Ok(ConditionCommandOutput {
+                exit_code: Some(1),
+                stdout: "1 error".to_string(),
+            })
+        };
+        let evaluation = evaluate_condition_for_folder(
+            folder.path(),
+            "Alice",
+            &test,
+            &HashMap::new(),
+            &mut runner,
+        )
+        .unwrap();
+        assert!(!evaluation.result);
+        assert!(evaluation.summary.contains("code de sortie 1 comparé = 0"));
+
+        test.operator = Some(ConditionOperator::Contains);
+        test.value = Some("error".to_string());
+        let evaluation = evaluate_condition_for_folder(
+            folder.path(),
+            "Alice",
+            &test,
+            &HashMap::new(),
+            &mut runner,
+        )
+        .unwrap();
+        assert!(evaluation.result);
+        assert_eq!(launched.len(), 2);
+
+        assert!(evaluate_condition_for_folder(
+            folder.path(),
+            "Alice",
+            &test,
+            &HashMap::new(),
+            &mut commands_unavailable
+        )
+        .is_err());
+    }
 }
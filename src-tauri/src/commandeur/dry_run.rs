@@ -0,0 +1,1022 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{anyhow, Result};
+use walkdir::WalkDir;
+
+use crate::commandeur::conditions::{
+    captured_value_dependency, command_dependency, commands_unavailable, evaluate_condition_in,
+};
+use crate::commandeur::globs::{
+    check_match_count, expand_glob_targets, glob_options, glob_target, loop_items, select_matches,
+    FolderView, GlobOptions,
+};
+use crate::commandeur::models::{
+    CommandeurDryRunResult, CommandeurFolderPlan, CommandeurOperation, CommandeurPlannedChange,
+    CommandeurPlannedStep, CommandeurWorkflow, OperationDetails, PlannedChangeKind,
+    PlannedStepStatus, PythonEntry, ReplaceMode,
+};
+use crate::commandeur::templating::{
+    interpolate_condition, interpolate_operation, TemplateContext,
+};
+use crate::commandeur::utils::{build_regex, compute_rename_destination, ensure_replaceable};
+use crate::commandeur::workspace::{resolve_in_folder, AppState};
+
+const DRY_RUN_ID: &str = "dry-run";
+
+/// Vue simulée d'un dossier étudiant : les créations, suppressions et modifications
+/// planifiées sont gardées en mémoire pour que les opérations suivantes (conditions et motifs
+/// compris) les voient, sans jamais toucher au disque.
+#[derive(Default)]
+struct VirtualFolder {
+    created: HashSet<PathBuf>,
+    created_dirs: HashSet<PathBuf>,
+    removed: HashSet<PathBuf>,
+    contents: HashMap<PathBuf, String>,
+    /// Chemins déplacés, de la destination vers la source sur le disque: un dossier renommé
+    /// garde ainsi son contenu.
+    aliases: Vec<(PathBuf, PathBuf)>,
+}
+
+impl VirtualFolder {
+    /// Emplacement sur le disque du contenu visible à `path`.
+    fn real_path(&self, path: &Path) -> PathBuf {
+        for (destination, source) in self.aliases.iter().rev() {
+            if let Ok(rest) = path.strip_prefix(destination) {
+                return rebase(source, rest);
+            }
+        }
+        path.to_path_buf()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        if self.created_dirs.contains(path) {
+            return true;
+        }
+        !self.created.contains(path) && self.exists(path) && self.real_path(path).is_dir()
+    }
+
+    fn read_text(&self, path: &Path) -> Result<String> {
+        if let Some(content) = self.contents.get(path) {
+            return Ok(content.clone());
+        }
+        fs::read_to_string(self.real_path(path)).map_err(|err| anyhow!(err))
+    }
+
+    /// Oublie ce qui a été planifié à `path` et en dessous, avant de le remplacer.
+    fn forget(&mut self, path: &Path) {
+        self.created.retain(|created| !created.starts_with(path));
+        self.created_dirs
+            .retain(|created| !created.starts_with(path));
+        self.contents.retain(|known, _| !known.starts_with(path));
+        self.removed.retain(|removed| !removed.starts_with(path));
+    }
+
+    fn create(&mut self, path: &Path, content: Option<String>) {
+        self.forget(path);
+        self.created.insert(path.to_path_buf());
+        if let Some(value) = content {
+            self.contents.insert(path.to_path_buf(), value);
+        }
+    }
+
+    fn create_dir(&mut self, path: &Path) {
+        self.removed.retain(|removed| !removed.starts_with(path));
+        self.created.insert(path.to_path_buf());
+        self.created_dirs.insert(path.to_path_buf());
+    }
+
+    fn remove(&mut self, path: &Path) {
+        self.created.retain(|created| !created.starts_with(path));
+        self.created_dirs
+            .retain(|created| !created.starts_with(path));
+        self.contents.retain(|known, _| !known.starts_with(path));
+        self.removed.insert(path.to_path_buf());
+    }
+
+    /// Reproduit `source` et tout ce qu'il contient (sur le disque ou planifié) à
+    /// `destination`.
+    fn duplicate(&mut self, source: &Path, destination: &Path) {
+        let real = self.real_path(source);
+        let on_disk = !self.created.contains(source) && real.exists();
+        let created: Vec<PathBuf> = self
+            .created
+            .iter()
+            .filter(|path| path.starts_with(source))
+            .cloned()
+            .collect();
+        let removed: Vec<PathBuf> = self
+            .removed
+            .iter()
+            .filter(|path| path.starts_with(source))
+            .cloned()
+            .collect();
+        self.forget(destination);
+        if on_disk {
+            self.aliases.push((destination.to_path_buf(), real));
+        }
+        for path in created {
+            let target = rebase(destination, path.strip_prefix(source).unwrap_or(&path));
+            if self.created_dirs.contains(&path) {
+                self.created_dirs.insert(target.clone());
+            }
+            if let Some(content) = self.contents.get(&path).cloned() {
+                self.contents.insert(target.clone(), content);
+            }
+            self.created.insert(target);
+        }
+        for path in removed {
+            self.removed.insert(rebase(
+                destination,
+                path.strip_prefix(source).unwrap_or(&path),
+            ));
+        }
+    }
+
+    fn relocate(&mut self, source: &Path, destination: &Path) {
+        self.duplicate(source, destination);
+        self.remove(source);
+    }
+
+    /// Chemins visibles sous `base_path`: ceux du disque, ceux des dossiers déplacés et ceux
+    /// créés par la simulation.
+    fn entries(&self, base_path: &Path) -> Vec<PathBuf> {
+        let mut roots = vec![(base_path.to_path_buf(), base_path.to_path_buf())];
+        roots.extend(
+            self.aliases
+                .iter()
+                .filter(|(destination, _)| destination.starts_with(base_path))
+                .cloned(),
+        );
+        let mut paths: HashSet<PathBuf> = self
+            .created
+            .iter()
+            .filter(|path| path.starts_with(base_path))
+            .cloned()
+            .collect();
+        for (visible, real) in roots {
+            for entry in WalkDir::new(&real)
+                .follow_links(false)
+                .into_iter()
+                .flatten()
+            {
+                if let Ok(rest) = entry.path().strip_prefix(&real) {
+                    paths.insert(rebase(&visible, rest));
+                }
+            }
+        }
+        paths.into_iter().filter(|path| self.exists(path)).collect()
+    }
+}
+
+impl FolderView for VirtualFolder {
+    fn exists(&self, path: &Path) -> bool {
+        if self.created.contains(path) {
+            return true;
+        }
+        if self.removed.iter().any(|removed| path.starts_with(removed)) {
+            return false;
+        }
+        self.real_path(path).exists()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.exists(path) && !self.is_dir(path)
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>> {
+        match self.contents.get(path) {
+            Some(content) => Ok(content.clone().into_bytes()),
+            None => Ok(fs::read(self.real_path(path))?),
+        }
+    }
+
+    fn size(&self, path: &Path) -> Result<u64> {
+        match self.contents.get(path) {
+            Some(content) => Ok(content.len() as u64),
+            None => Ok(fs::metadata(self.real_path(path))?.len()),
+        }
+    }
+
+    fn modified(&self, path: &Path) -> Result<SystemTime> {
+        if self.created.contains(path) {
+            return Ok(SystemTime::now());
+        }
+        Ok(fs::metadata(self.real_path(path))?.modified()?)
+    }
+
+    fn find(&self, base_path: &Path, pattern: &str, options: GlobOptions) -> Result<Vec<PathBuf>> {
+        select_matches(self, base_path, pattern, options, self.entries(base_path))
+    }
+}
+
+/// `base` suivi de `rest`, sans séparateur final quand `rest` est vide.
+fn rebase(base: &Path, rest: &Path) -> PathBuf {
+    if rest.as_os_str().is_empty() {
+        base.to_path_buf()
+    } else {
+        base.join(rest)
+    }
+}
+
+struct StepOutcome {
+    status: PlannedStepStatus,
+    message: String,
+    changes: Vec<CommandeurPlannedChange>,
+}
+
+impl StepOutcome {
+    fn planned(message: impl Into<String>, changes: Vec<CommandeurPlannedChange>) -> Self {
+        Self {
+            status: PlannedStepStatus::Planned,
+            message: message.into(),
+            changes,
+        }
+    }
+
+    fn no_change(message: impl Into<String>) -> Self {
+        Self {
+            status: PlannedStepStatus::NoChange,
+            message: message.into(),
+            changes: Vec::new(),
+        }
+    }
+}
+
+pub fn dry_run_workflow(
+    state: &AppState,
+    workspace_id: &str,
+    workflow: &CommandeurWorkflow,
+) -> Result<CommandeurDryRunResult> {
+    let workspace_arc = state.get_workspace(workspace_id)?;
+    let workspace = workspace_arc
+        .lock()
+        .map_err(|_| anyhow!("Accès concurrent au workspace"))?;
+
+    let mut folders = Vec::new();
+    for (index, folder) in workspace.sub_folders.iter().enumerate() {
+        let base_path = workspace.folder_absolute_path(folder);
+        let mut plan = CommandeurFolderPlan {
+            folder: folder.clone(),
+            steps: Vec::new(),
+            halts_execution: false,
+        };
+        let template = if base_path.exists() {
+            TemplateContext::for_folder(
+                &workflow.variables,
+                folder,
+                index,
+                &workspace.root_path,
+                DRY_RUN_ID,
+            )
+            .map_err(|err| format!("Variables du workflow invalides: {err}"))
+        } else {
+            Err(format!("Le dossier {folder} est introuvable"))
+        };
+        let template = match template {
+            Ok(template) => template,
+            Err(message) => {
+                plan.steps.push(CommandeurPlannedStep {
+                    operation_id: "__workspace__".into(),
+                    operation_label: "Workspace".into(),
+                    parent_operation_id: None,
+                    status: PlannedStepStatus::WouldFail,
+                    message,
+                    changes: Vec::new(),
+                });
+                plan.halts_execution = true;
+                folders.push(plan);
+                continue;
+            }
+        };
+        let mut overlay = VirtualFolder::default();
+        let completed = plan_operations(
+            &workflow.operations,
+            None,
+            folder,
+            &base_path,
+            &template,
+            &mut overlay,
+            &mut plan.steps,
+        );
+        plan.halts_execution = !completed;
+        folders.push(plan);
+    }
+
+    let planned_changes = folders
+        .iter()
+        .flat_map(|plan| plan.steps.iter())
+        .map(|step| step.changes.len())
+        .sum();
+    let failing_folders = folders
+        .iter()
+        .filter(|plan| {
+            plan.steps
+                .iter()
+                .any(|step| step.status == PlannedStepStatus::WouldFail)
+        })
+        .count();
+
+    Ok(CommandeurDryRunResult {
+        folders,
+        planned_changes,
+        failing_folders,
+    })
+}
+
+/// Planifie une liste d'opérations. Retourne `false` si une opération bloquante
+/// échouerait, auquel cas la suite n'est pas planifiée (comme à l'exécution).
+fn plan_operations(
+    operations: &[CommandeurOperation],
+    parent_id: Option<&str>,
+    folder: &str,
+    base_path: &Path,
+    template: &TemplateContext,
+    overlay: &mut VirtualFolder,
+    steps: &mut Vec<CommandeurPlannedStep>,
+) -> bool {
+    for operation in operations {
+        if !operation.enabled() {
+            steps.push(step_for(
+                operation,
+                parent_id,
+                StepOutcome {
+                    status: PlannedStepStatus::Disabled,
+                    message: "Opération désactivée".into(),
+                    changes: Vec::new(),
+                },
+            ));
+            continue;
+        }
+
+        if let OperationDetails::If {
+            test,
+            then,
+            else_branch,
+        } = &operation.details
+        {
+            if let Some(name) = captured_value_dependency(test) {
+                steps.push(step_for(
+                    operation,
+                    parent_id,
+                    StepOutcome::no_change(format!(
+                        "Condition sur la valeur capturée {name}: évaluée à l'exécution, branches non simulées"
+                    )),
+                ));
+                continue;
+            }
+            if let Some(command) = command_dependency(test) {
+                steps.push(step_for(
+                    operation,
+                    parent_id,
+                    StepOutcome::no_change(format!(
+                        "Condition sur la commande \"{command}\": évaluée à l'exécution, branches non simulées"
+                    )),
+                ));
+                continue;
+            }
+            let evaluation = interpolate_condition(test, template).and_then(|test| {
+                evaluate_condition_in(
+                    &*overlay,
+                    base_path,
+                    folder,
+                    &test,
+                    &HashMap::new(),
+                    &mut commands_unavailable,
+                )
+            });
+            match evaluation {
+                Ok(evaluation) => {
+                    let branch_label = if evaluation.result { "then" } else { "else" };
+                    steps.push(step_for(
+                        operation,
+                        parent_id,
+                        StepOutcome::planned(
+                            format!("Condition : {} -> {}", evaluation.summary, branch_label),
+                            Vec::new(),
+                        ),
+                    ));
+                    let branch: &[CommandeurOperation] = if evaluation.result {
+                        then
+                    } else {
+                        else_branch.as_deref().unwrap_or(&[])
+                    };
+                    if !plan_operations(
+                        branch,
+                        Some(operation.id()),
+                        folder,
+                        base_path,
+                        template,
+                        overlay,
+                        steps,
+                    ) {
+                        return false;
+                    }
+                }
+                Err(err) => {
+                    steps.push(failed_step(operation, parent_id, err));
+                    if !operation.continue_on_error() {
+                        return false;
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let OperationDetails::ForEach {
+            pattern,
+            items,
+            operations,
+        } = &operation.details
+        {
+            let resolved = pattern
+                .as_deref()
+                .map(|pattern| template.render(pattern))
+                .transpose()
+                .and_then(|pattern| {
+                    let items = items
+                        .iter()
+                        .map(|item| template.render(item))
+                        .collect::<Result<Vec<_>>>()?;
+                    loop_items(
+                        &*overlay,
+                        base_path,
+                        pattern.as_deref(),
+                        &items,
+                        glob_options(operation),
+                    )
+                });
+            match resolved {
+                Ok(items) => {
+                    steps.push(step_for(
+                        operation,
+                        parent_id,
+                        StepOutcome::planned(
+                            format!("Boucle sur {} élément(s)", items.len()),
+                            Vec::new(),
+                        ),
+                    ));
+                    let mut item_template = template.clone();
+                    for item in &items {
+                        item_template.bind_item(item);
+                        if !plan_operations(
+                            operations,
+                            Some(operation.id()),
+                            folder,
+                            base_path,
+                            &item_template,
+                            overlay,
+                            steps,
+                        ) {
+                            return false;
+                        }
+                    }
+                }
+                Err(err) => {
+                    steps.push(failed_step(operation, parent_id, err));
+                    if !operation.continue_on_error() {
+                        return false;
+                    }
+                }
+            }
+            continue;
+        }
+
+        let planned = interpolate_operation(operation, template)
+            .and_then(|resolved| plan_with_globs(&resolved, base_path, overlay));
+        match planned {
+            Ok(outcome) => steps.push(step_for(operation, parent_id, outcome)),
+            Err(err) => {
+                steps.push(failed_step(operation, parent_id, err));
+                if !operation.continue_on_error() {
+                    return false;
+                }
+            }
+        }
+    }
+    true
+}
+
+/// Planifie une opération à cible générique chemin par chemin et regroupe les changements.
+fn plan_with_globs(
+    operation: &CommandeurOperation,
+    base_path: &Path,
+    overlay: &mut VirtualFolder,
+) -> Result<StepOutcome> {
+    let expanded = expand_glob_targets(operation, base_path, &*overlay)?;
+    let expanded = match expanded {
+        Some(expanded) => expanded,
+        None => return plan_operation(operation, base_path, overlay),
+    };
+    let pattern = glob_target(operation).unwrap_or_default();
+    check_match_count(operation, expanded.len())
+        .map_err(|err| anyhow!("Motif {pattern}: {err}"))?;
+    if expanded.is_empty() {
+        if let OperationDetails::DeleteFile { required: true, .. } = operation.details {
+            return Err(anyhow!(
+                "Suppression requise impossible: aucun chemin ne correspond au motif {pattern}"
+            ));
+        }
+        return Ok(StepOutcome::no_change(format!(
+            "Aucun chemin ne correspond au motif {pattern}"
+        )));
+    }
+    let mut changes = Vec::new();
+    for concrete in &expanded {
+        changes.extend(plan_operation(concrete, base_path, overlay)?.changes);
+    }
+    let message = format!(
+        "{} chemin(s) correspondant au motif {pattern}",
+        expanded.len()
+    );
+    Ok(if changes.is_empty() {
+        StepOutcome::no_change(message)
+    } else {
+        StepOutcome::planned(message, changes)
+    })
+}
+
+fn plan_operation(
+    operation: &CommandeurOperation,
+    base_path: &Path,
+    overlay: &mut VirtualFolder,
+) -> Result<StepOutcome> {
+    match &operation.details {
+        OperationDetails::CreateFile {
+            target,
+            overwrite,
+            content,
+        } => {
+            let target_path = resolve_in_folder(base_path, target)?;
+            if overlay.exists(&target_path) {
+                if !overwrite {
+                    return Ok(StepOutcome::no_change(format!(
+                        "Le fichier existe déjà, création ignorée: {target}"
+                    )));
+                }
+                overlay.create(&target_path, Some(content.clone()));
+                return Ok(StepOutcome::planned(
+                    format!("Fichier écrasé: {target}"),
+                    vec![change(
+                        PlannedChangeKind::OverwriteFile,
+                        target,
+                        None,
+                        Some(format!("{} octet(s)", content.len())),
+                    )],
+                ));
+            }
+            overlay.create(&target_path, Some(content.clone()));
+            Ok(StepOutcome::planned(
+                format!("Fichier créé: {target}"),
+                vec![change(
+                    PlannedChangeKind::CreateFile,
+                    target,
+                    None,
+                    Some(format!("{} octet(s)", content.len())),
+                )],
+            ))
+        }
+        OperationDetails::DeleteFile { target, required } => {
+            let target_path = resolve_in_folder(base_path, target)?;
+            if !overlay.exists(&target_path) {
+                if *required {
+                    return Err(anyhow!(
+                        "Suppression requise impossible: fichier introuvable"
+                    ));
+                }
+                return Ok(StepOutcome::no_change(format!(
+                    "Fichier à supprimer introuvable: {target}"
+                )));
+            }
+            overlay.remove(&target_path);
+            Ok(StepOutcome::planned(
+                format!("Fichier supprimé: {target}"),
+                vec![change(PlannedChangeKind::Delete, target, None, None)],
+            ))
+        }
+        OperationDetails::Copy {
+            source,
+            destination,
+            overwrite,
+        } => {
+            let source_path = resolve_in_folder(base_path, source)?;
+            if !overlay.exists(&source_path) {
+                return Err(anyhow!("Source introuvable: {}", source_path.display()));
+            }
+            if overlay.is_dir(&source_path) {
+                return Err(anyhow!("La copie de dossiers n'est pas supportée"));
+            }
+            let dest_path = resolve_in_folder(base_path, destination)?;
+            if overlay.exists(&dest_path) {
+                if !overwrite {
+                    return Err(anyhow!(
+                        "La destination existe déjà: {}",
+                        dest_path.display()
+                    ));
+                }
+                ensure_replaceable(destination, false, overlay.is_dir(&dest_path))?;
+            }
+            match overlay.read_text(&source_path) {
+                Ok(content) => overlay.create(&dest_path, Some(content)),
+                Err(_) => overlay.duplicate(&source_path, &dest_path),
+            }
+            Ok(StepOutcome::planned(
+                format!("Fichier copié de {source} vers {destination}"),
+                vec![change(
+                    PlannedChangeKind::Copy,
+                    source,
+                    Some(destination.clone()),
+                    None,
+                )],
+            ))
+        }
+        OperationDetails::Exec {
+            command, args, cwd, ..
+        } => {
+            if let Some(cwd_fragment) = cwd {
+                let cwd_path = resolve_in_folder(base_path, cwd_fragment)?;
+                if !overlay.exists(&cwd_path) {
+                    return Err(anyhow!("Répertoire de travail introuvable: {cwd_fragment}"));
+                }
+            }
+            let command_line = if args.is_empty() {
+                command.clone()
+            } else {
+                format!("{} {}", command, args.join(" "))
+            };
+            Ok(StepOutcome::planned(
+                format!("Commande exécutée: {command}"),
+                vec![change(
+                    PlannedChangeKind::RunCommand,
+                    cwd.as_deref().unwrap_or("."),
+                    None,
+                    Some(format!("{command_line} (effets non simulés)")),
+                )],
+            ))
+        }
+        OperationDetails::ReplaceInFile {
+            target,
+            search,
+            replace,
+            mode,
+            flags,
+        } => {
+            let target_path = resolve_in_folder(base_path, target)?;
+            if !overlay.exists(&target_path) {
+                return Err(anyhow!("Fichier introuvable: {target}"));
+            }
+            let original = overlay.read_text(&target_path)?;
+            let (updated, count) = match mode {
+                ReplaceMode::Plain => (
+                    original.replace(search, replace),
+                    original.matches(search.as_str()).count(),
+                ),
+                ReplaceMode::Regex => {
+                    let regex = build_regex(search, flags.as_deref())?;
+                    let count = regex.find_iter(&original).count();
+                    (
+                        regex.replace_all(&original, replace.as_str()).to_string(),
+                        count,
+                    )
+                }
+            };
+            if count == 0 {
+                return Ok(StepOutcome::no_change(format!(
+                    "Aucun remplacement pour {target}"
+                )));
+            }
+            overlay.create(&target_path, Some(updated));
+            Ok(StepOutcome::planned(
+                format!("{count} occurrence(s) remplacée(s) dans {target}"),
+                vec![change(
+                    PlannedChangeKind::ModifyFile,
+                    target,
+                    None,
+                    Some(format!("{count} occurrence(s)")),
+                )],
+            ))
+        }
+        OperationDetails::Rename {
+            target,
+            mode,
+            value,
+            search,
+            replace,
+        } => {
+            let source_path = resolve_in_folder(base_path, target)?;
+            if !overlay.exists(&source_path) {
+                return Err(anyhow!("Fichier ou dossier introuvable: {target}"));
+            }
+            let dest_path = compute_rename_destination(
+                &source_path,
+                mode,
+                value,
+                search.as_deref(),
+                replace.as_deref(),
+            )?;
+            if overlay.exists(&dest_path) {
+                return Err(anyhow!(
+                    "La destination existe déjà: {}",
+                    dest_path.display()
+                ));
+            }
+            overlay.relocate(&source_path, &dest_path);
+            let dest_relative = relative_to(base_path, &dest_path);
+            Ok(StepOutcome::planned(
+                format!("Renommé {target} -> {dest_relative}"),
+                vec![change(
+                    PlannedChangeKind::Rename,
+                    target,
+                    Some(dest_relative),
+                    None,
+                )],
+            ))
+        }
+        OperationDetails::Move {
+            source,
+            destination,
+            overwrite,
+        } => {
+            let source_path = resolve_in_folder(base_path, source)?;
+            if !overlay.exists(&source_path) {
+                return Err(anyhow!("Source introuvable: {source}"));
+            }
+            let dest_path = resolve_in_folder(base_path, destination)?;
+            if overlay.exists(&dest_path) {
+                if !overwrite {
+                    return Err(anyhow!("Destination déjà existante: {destination}"));
+                }
+                ensure_replaceable(
+                    destination,
+                    overlay.is_dir(&source_path),
+                    overlay.is_dir(&dest_path),
+                )?;
+            }
+            overlay.relocate(&source_path, &dest_path);
+            Ok(StepOutcome::planned(
+                format!("Déplacement {source} -> {destination}"),
+                vec![change(
+                    PlannedChangeKind::Move,
+                    source,
+                    Some(destination.clone()),
+                    None,
+                )],
+            ))
+        }
+        OperationDetails::Mkdir {
+            target,
+            recursive,
+            skip_if_exists,
+        } => {
+            let target_path = resolve_in_folder(base_path, target)?;
+            if overlay.exists(&target_path) {
+                if *skip_if_exists {
+                    return Ok(StepOutcome::no_change(format!(
+                        "Dossier déjà présent: {target}"
+                    )));
+                }
+                return Err(anyhow!("Le dossier existe déjà: {target}"));
+            }
+            if !recursive {
+                if let Some(parent) = target_path.parent() {
+                    if !overlay.exists(parent) {
+                        return Err(anyhow!("Le dossier parent n'existe pas: {target}"));
+                    }
+                }
+            }
+            overlay.create_dir(&target_path);
+            Ok(StepOutcome::planned(
+                format!("Dossier créé: {target}"),
+                vec![change(
+                    PlannedChangeKind::CreateDirectory,
+                    target,
+                    None,
+                    None,
+                )],
+            ))
+        }
+        OperationDetails::Python {
+            inline_script,
+            script_path,
+            entry,
+            ..
+        } => {
+            let script_label = match entry {
+                PythonEntry::Inline => {
+                    if inline_script
+                        .as_ref()
+                        .map(|s| s.trim().is_empty())
+                        .unwrap_or(true)
+                    {
+                        return Err(anyhow!("Script inline manquant"));
+                    }
+                    "script inline".to_string()
+                }
+                PythonEntry::File => {
+                    let fragment = script_path
+                        .as_ref()
+                        .ok_or_else(|| anyhow!("Chemin du script requis"))?;
+                    let path = resolve_in_folder(base_path, fragment)?;
+                    if !overlay.exists(&path) {
+                        return Err(anyhow!("Script Python introuvable: {}", path.display()));
+                    }
+                    fragment.clone()
+                }
+            };
+            Ok(StepOutcome::planned(
+                "Script Python exécuté",
+                vec![change(
+                    PlannedChangeKind::RunCommand,
+                    ".",
+                    None,
+                    Some(format!("python {script_label} (effets non simulés)")),
+                )],
+            ))
+        }
+        OperationDetails::If { .. } => Ok(StepOutcome::no_change("Condition")),
+        OperationDetails::ForEach { .. } => Ok(StepOutcome::no_change("Boucle")),
+    }
+}
+
+fn step_for(
+    operation: &CommandeurOperation,
+    parent_id: Option<&str>,
+    outcome: StepOutcome,
+) -> CommandeurPlannedStep {
+    CommandeurPlannedStep {
+        operation_id: operation.id().to_string(),
+        operation_label: operation.label().to_string(),
+        parent_operation_id: parent_id.map(str::to_string),
+        status: outcome.status,
+        message: outcome.message,
+        changes: outcome.changes,
+    }
+}
+
+fn failed_step(
+    operation: &CommandeurOperation,
+    parent_id: Option<&str>,
+    err: anyhow::Error,
+) -> CommandeurPlannedStep {
+    step_for(
+        operation,
+        parent_id,
+        StepOutcome {
+            status: PlannedStepStatus::WouldFail,
+            message: format!("Échec prévu: {err}"),
+            changes: Vec::new(),
+        },
+    )
+}
+
+fn change(
+    kind: PlannedChangeKind,
+    path: &str,
+    destination: Option<String>,
+    details: Option<String>,
+) -> CommandeurPlannedChange {
+    CommandeurPlannedChange {
+        kind,
+        path: path.to_string(),
+        destination,
+        details,
+    }
+}
+
+fn relative_to(base_path: &Path, path: &Path) -> String {
+    path.strip_prefix(base_path)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commandeur::storage::Storage;
+    use crate::commandeur::workspace::{prepare_workspace, WorkspaceOptions};
+
+    #[test]
+    fn dry_run_chains_planned_changes_without_touching_disk() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let alice = temp.path().join("Alice");
+        let bob = temp.path().join("Bob");
+        fs::create_dir_all(&alice).expect("create alice");
+        fs::create_dir_all(&bob).expect("create bob");
+        fs::write(bob.join("notes.txt"), "existing").expect("write notes");
+
+        let data = tempfile::tempdir().expect("tempdir");
+        let state = AppState::with_storage(Storage::at(data.path()));
+        let summary = prepare_workspace(
+            &state,
+            temp.path().to_str().unwrap(),
+            &WorkspaceOptions::default(),
+        )
+        .expect("prepare workspace");
+
+        let workflow: CommandeurWorkflow = serde_json::from_value(serde_json::json!({
+            "name": "Préparation",
+            "operations": [
+                { "id": "create", "label": "Créer", "kind": "create-file",
+                  "target": "notes.txt", "content": "TODO TODO" },
+                { "id": "replace", "label": "Remplacer", "kind": "replace-in-file",
+                  "target": "notes.txt", "search": "TODO", "replace": "OK", "mode": "plain" },
+                { "id": "rename", "label": "Renommer", "kind": "rename",
+                  "target": "notes.txt", "mode": "change-extension", "value": "md" }
+            ]
+        }))
+        .expect("workflow");
+
+        let result = dry_run_workflow(&state, &summary.workspace_id, &workflow).expect("dry run");
+        assert_eq!(result.folders.len(), 2);
+
+        let alice_plan = &result.folders[0];
+        let statuses: Vec<_> = alice_plan.steps.iter().map(|step| step.status).collect();
+        assert_eq!(statuses, vec![PlannedStepStatus::Planned; 3]);
+        assert_eq!(
+            alice_plan.steps[1].message,
+            "2 occurrence(s) remplacée(s) dans notes.txt"
+        );
+
+        let bob_plan = &result.folders[1];
+        assert_eq!(bob_plan.steps[0].status, PlannedStepStatus::NoChange);
+        assert_eq!(bob_plan.steps[1].status, PlannedStepStatus::NoChange);
+        assert_eq!(
+            bob_plan.steps[2].changes[0].destination.as_deref(),
+            Some("notes.md")
+        );
+
+        assert!(!alice.join("notes.txt").exists());
+        assert!(!alice.join("notes.md").exists());
+        assert_eq!(
+            fs::read_to_string(bob.join("notes.txt")).expect("read notes"),
+            "existing"
+        );
+    }
+
+    #[test]
+    fn conditions_and_globs_see_planned_changes() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let alice = temp.path().join("Alice");
+        fs::create_dir_all(alice.join("build/classes")).expect("create build");
+        fs::write(alice.join("build/classes/Main.class"), "").expect("write class");
+
+        let workflow: CommandeurWorkflow = serde_json::from_value(serde_json::json!({
+            "name": "Nettoyage",
+            "operations": [
+                { "id": "rapport", "label": "Rapport", "kind": "create-file",
+                  "target": "build/rapport.txt", "content": "ok" },
+                { "id": "sortie", "label": "Sortie", "kind": "move",
+                  "source": "build", "destination": "out" },
+                { "id": "si", "label": "Si rapport", "kind": "if",
+                  "test": { "selector": "file-search", "operator": "exists",
+                            "pattern": "out/*.txt" },
+                  "then": [
+                      { "id": "classes", "label": "Classes", "kind": "delete-file",
+                        "target": "out/**/*.class", "required": true }
+                  ] },
+                { "id": "valider", "label": "Valider", "kind": "replace-in-file",
+                  "target": "out/*.txt", "search": "ok", "replace": "OK", "mode": "plain" }
+            ]
+        }))
+        .expect("workflow");
+
+        let template =
+            TemplateContext::for_folder(&HashMap::new(), "Alice", 0, temp.path(), DRY_RUN_ID)
+                .expect("template");
+        let mut overlay = VirtualFolder::default();
+        let mut steps = Vec::new();
+        assert!(plan_operations(
+            &workflow.operations,
+            None,
+            "Alice",
+            &alice,
+            &template,
+            &mut overlay,
+            &mut steps,
+        ));
+
+        let messages: Vec<(&str, &str)> = steps
+            .iter()
+            .map(|step| (step.operation_id.as_str(), step.message.as_str()))
+            .collect();
+        assert!(messages[2].1.ends_with("-> then"), "{messages:?}");
+        assert_eq!(
+            &messages[3..],
+            &[
+                (
+                    "classes",
+                    "1 chemin(s) correspondant au motif out/**/*.class"
+                ),
+                ("valider", "1 chemin(s) correspondant au motif out/*.txt"),
+            ]
+        );
+        assert!(steps
+            .iter()
+            .all(|step| step.status == PlannedStepStatus::Planned));
+        assert!(alice.join("build/classes/Main.class").is_file());
+        assert!(!alice.join("out").exists());
+    }
+}
@@ -12,6 +12,27 @@ pub enum CommandeurError {
         #[source]
         source: anyhow::Error,
     },
+    #[error("Operation '{operation_label}' interrompue après {timeout_seconds} s (délai dépassé)")]
+    OperationTimedOut {
+        operation_id: String,
+        operation_label: String,
+        continue_on_error: bool,
+        timeout_seconds: u64,
+    },
     #[error("Exécution interrompue: {reason}")]
     ExecutionInterrupted { reason: String },
 }
+
+impl CommandeurError {
+    pub fn continue_on_error(&self) -> bool {
+        match self {
+            CommandeurError::OperationFailed {
+                continue_on_error, ..
+            }
+            | CommandeurError::OperationTimedOut {
+                continue_on_error, ..
+            } => *continue_on_error,
+            _ => false,
+        }
+    }
+}
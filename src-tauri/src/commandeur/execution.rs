@@ -1,39 +1,85 @@
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::io::Write;
-use std::path::Path;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Result};
+use chrono::Utc;
+use rayon::prelude::*;
 use serde::Serialize;
 use tauri::Window;
+use walkdir::WalkDir;
 
-use crate::commandeur::conditions::evaluate_condition_for_folder;
+use crate::commandeur::artifacts::persist_process_output;
+use crate::commandeur::conditions::{
+    evaluate_condition_for_folder, ConditionCommand, ConditionCommandOutput,
+};
 use crate::commandeur::errors::CommandeurError;
 use crate::commandeur::execution_control::{ExecutionControl, ExecutionInterrupt, ExecutionStatus};
+use crate::commandeur::globs::{
+    check_match_count, expand_glob_targets, glob_options, glob_target, loop_items, operation_path,
+    Disk,
+};
+use crate::commandeur::history::{load_run_result, record_history_entry, record_run, run_status};
+use crate::commandeur::journal::{load_journal, ExecutionJournal, JournalCondition, JournalWriter};
 use crate::commandeur::models::{
-    CommandeurExecutionLogEntry, CommandeurExecutionResult, CommandeurOperation,
-    CommandeurValidationMessage, CommandeurWorkflow, OperationDetails, PythonEntry, ReplaceMode,
-    ValidationLevel,
+    CaptureSource, CommandeurExecutionLogEntry, CommandeurExecutionOptions,
+    CommandeurExecutionResult, CommandeurExecutionSummary, CommandeurFolderStatus,
+    CommandeurFolderSummary, CommandeurOperation, CommandeurOperationTimeout,
+    CommandeurProcessArtifact, CommandeurValidationMessage, CommandeurWorkflow, ConditionRunner,
+    FolderRunStatus, OperationDetails, OperationRunStatus, OutputCapture, PythonEntry, ReplaceMode,
+    RunHistoryEntry, ShellKind, ValidationLevel,
+};
+use crate::commandeur::process::{
+    build_command, run_process, system_shell, OutputBatch, OutputLine, ProcessOutcome,
+    ProcessOutput,
 };
 use crate::commandeur::python::ExecutionEnv;
+use crate::commandeur::report::write_html_report;
 use crate::commandeur::reporting::{
-    push_folder_validation, push_log, push_log_with_meta, push_workspace_log,
+    push_folder_log, push_folder_validation, push_log_with_meta, push_workspace_log,
+};
+use crate::commandeur::run_log::{
+    write_run_log, RunLog, RunLogCondition, RunLogFolder, RunLogOperation, RunLogWorkflow,
+    RunLogWorkspace, RUN_LOG_FORMAT_VERSION,
 };
-use crate::commandeur::utils::{build_regex, compute_rename_destination};
+use crate::commandeur::selection::{select_folders, write_folder_statuses};
+use crate::commandeur::snapshots::create_snapshot;
+use crate::commandeur::storage::Storage;
+use crate::commandeur::summary::{folder_summary, summary_columns, write_summary_csv, FolderCells};
+use crate::commandeur::templating::{interpolate_operation, TemplateContext};
+use crate::commandeur::utils::{build_regex, compute_rename_destination, ensure_replaceable};
 use crate::commandeur::workspace::{
-    ensure_parent_dir, repack_zip, resolve_in_folder, write_execution_log, AppState,
-    WorkspaceHandle, WorkspaceMode,
+    ensure_parent_dir, execution_log_path, new_id, repack_zip, resolve_in_folder,
+    write_execution_log, AppState, WorkspaceHandle, WorkspaceMode,
 };
 
 const LOG_EVENT: &str = "commandeur://execution-log";
 const VALIDATION_EVENT: &str = "commandeur://execution-validation";
 const PROGRESS_EVENT: &str = "commandeur://execution-progress";
+const OUTPUT_EVENT: &str = "commandeur://execution-output";
+const COPY_CHUNK_SIZE: usize = 1024 * 1024;
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct ExecutionProgressPayload {
     operations_processed: usize,
     operations_total: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    folder: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecutionOutputPayload<'a> {
+    operation_id: &'a str,
+    folder: &'a str,
+    lines: Vec<OutputLine>,
+    dropped_lines: usize,
 }
 
 fn emit_event<T: Serialize>(window: Option<&Window>, name: &str, payload: &T) {
@@ -42,34 +88,220 @@ fn emit_event<T: Serialize>(window: Option<&Window>, name: &str, payload: &T) {
     }
 }
 
+/// État partagé entre les workers d'une exécution.
+struct ExecutionShared<'a> {
+    storage: &'a Storage,
+    workspace: &'a WorkspaceHandle,
+    window: Option<&'a Window>,
+    control: &'a ExecutionControl,
+    env: Mutex<ExecutionEnv>,
+    halted: AtomicBool,
+    operations_processed: AtomicUsize,
+    operations_total: usize,
+    default_timeout_seconds: Option<u64>,
+    run_id: &'a str,
+    run_dir: PathBuf,
+    journal: JournalWriter,
+    /// Bilan de l'exécution reprise, pour les opérations déjà effectuées.
+    previous_summary: Option<CommandeurExecutionSummary>,
+    /// Entrée de l'historique tenue à jour pendant l'exécution.
+    history: Mutex<RunHistoryEntry>,
+}
+
+impl ExecutionShared<'_> {
+    fn ensure_python(&self) -> Result<String> {
+        let mut env = self
+            .env
+            .lock()
+            .map_err(|_| anyhow!("Accès concurrent à l'environnement Python"))?;
+        env.ensure_python()
+    }
+
+    fn record_failed_folder(&self) -> Result<()> {
+        let mut entry = self
+            .history
+            .lock()
+            .map_err(|_| anyhow!("Accès concurrent à l'historique"))?;
+        entry.failed_folder_count += 1;
+        record_history_entry(self.storage, &entry)
+    }
+
+    fn previous_row(&self, folder: &str) -> Option<&CommandeurFolderSummary> {
+        self.previous_summary
+            .as_ref()
+            .and_then(|summary| summary.folders.iter().find(|row| row.folder == folder))
+    }
+
+    fn record_progress(&self, folder: &str) {
+        let processed = self.operations_processed.fetch_add(1, Ordering::SeqCst) + 1;
+        if self.operations_total > 0 {
+            let payload = ExecutionProgressPayload {
+                operations_processed: processed,
+                operations_total: self.operations_total,
+                folder: Some(folder.to_string()),
+            };
+            emit_event(self.window, PROGRESS_EVENT, &payload);
+        }
+    }
+}
+
+/// Résultat du traitement d'un sous-dossier, fusionné dans l'ordre des dossiers à la fin.
+struct FolderOutcome {
+    folder: String,
+    log_entries: Vec<CommandeurExecutionLogEntry>,
+    warnings: Vec<CommandeurValidationMessage>,
+    errors: Vec<CommandeurValidationMessage>,
+    operations_run: usize,
+    timeouts: Vec<CommandeurOperationTimeout>,
+    artifacts: Vec<CommandeurProcessArtifact>,
+    interrupted_reason: Option<String>,
+    /// Faux si le dossier a été abandonné en cours de route (arrêt, ou échec bloquant dans
+    /// un autre dossier en mode parallèle).
+    finished: bool,
+    operations: Vec<RunLogOperation>,
+    duration_ms: u64,
+    cells: FolderCells,
+    captured: HashMap<String, String>,
+    conditions: Vec<RunLogCondition>,
+}
+
+struct FolderRun<'s, 'a> {
+    shared: &'s ExecutionShared<'a>,
+    folder: &'s str,
+    base_path: PathBuf,
+    template: TemplateContext,
+    captured: HashMap<String, String>,
+    log_entries: Vec<CommandeurExecutionLogEntry>,
+    warnings: Vec<CommandeurValidationMessage>,
+    errors: Vec<CommandeurValidationMessage>,
+    timeouts: Vec<CommandeurOperationTimeout>,
+    artifacts: Vec<CommandeurProcessArtifact>,
+    process_sequence: usize,
+    cells: FolderCells,
+    conditions: Vec<RunLogCondition>,
+    /// Opérations effectuées lors de l'exécution reprise.
+    completed: Vec<String>,
+    /// Conditions déjà évaluées lors de l'exécution reprise.
+    decided: BTreeMap<String, JournalCondition>,
+    /// Profondeur de boucle: les opérations d'une boucle ne sont pas consignées une à une.
+    loop_depth: usize,
+}
+
+/// Suite à donner au dossier après l'échec d'une opération.
+enum FailureDisposition {
+    Continue,
+    Halt,
+    Interrupted(String),
+}
+
 pub fn execute_workflow(
     state: &AppState,
     window: Option<&Window>,
     workspace_id: &str,
     workflow: &CommandeurWorkflow,
+    options: &CommandeurExecutionOptions,
+    control: ExecutionControl,
+) -> Result<CommandeurExecutionResult> {
+    let workspace_arc = state.get_workspace(workspace_id)?;
+    let workspace = workspace_arc
+        .lock()
+        .map_err(|_| anyhow!("Accès concurrent au workspace"))?;
+    let folders: Vec<(usize, String)> = match &options.selection {
+        Some(selection) => select_folders(state.storage(), &workspace, selection)?,
+        None => workspace.sub_folders.iter().cloned().enumerate().collect(),
+    };
+    let run_id = new_id();
+    let journal = ExecutionJournal::new(&run_id, &workspace, workflow, options, &folders);
+    run_workflow(
+        state.storage(),
+        &workspace,
+        window,
+        workflow,
+        journal,
+        control,
+    )
+}
+
+/// Reprend l'exécution `run_id` là où elle s'est arrêtée: les dossiers terminés et les
+/// opérations déjà effectuées sont ignorés, les valeurs capturées et les options de
+/// lancement (instantané, workflow d'origine) restaurées. Une condition interrompue garde
+/// le résultat consigné et seules les opérations de sa branche non encore effectuées sont
+/// exécutées; une boucle interrompue est rejouée en entier.
+pub fn resume_execution(
+    state: &AppState,
+    window: Option<&Window>,
+    workspace_id: &str,
+    run_id: &str,
     control: ExecutionControl,
 ) -> Result<CommandeurExecutionResult> {
     let workspace_arc = state.get_workspace(workspace_id)?;
     let workspace = workspace_arc
         .lock()
         .map_err(|_| anyhow!("Accès concurrent au workspace"))?;
+    let previous = load_journal(state.storage(), run_id)?;
+    previous.check_workspace(&workspace)?;
+    if previous.pending_folders().next().is_none() {
+        return Err(anyhow!("L'exécution {run_id} est déjà terminée"));
+    }
+    let journal = previous.resumed(&new_id());
+    let workflow = journal.workflow.clone();
+    run_workflow(
+        state.storage(),
+        &workspace,
+        window,
+        &workflow,
+        journal,
+        control,
+    )
+}
+
+fn run_workflow(
+    storage: &Storage,
+    workspace: &WorkspaceHandle,
+    window: Option<&Window>,
+    workflow: &CommandeurWorkflow,
+    journal: ExecutionJournal,
+    control: ExecutionControl,
+) -> Result<CommandeurExecutionResult> {
+    let options = journal.options.clone();
+    let started_at = Utc::now().to_rfc3339();
+    let run_id = journal.run_id.clone();
+    let resumed_from = journal.resumed_from.clone();
+    let workflow_id = options.workflow_id.clone();
+    let run_dir = storage.run_dir(&run_id)?;
 
-    let mut env = ExecutionEnv::new();
     let mut log_entries = Vec::new();
     let mut warnings = Vec::new();
     let mut errors = Vec::new();
     let mut operations_run = 0usize;
+    let mut timeouts = Vec::new();
+    let mut artifacts = Vec::new();
     let mut interrupted_reason: Option<String> = None;
 
-    let enabled_operation_count = workflow.operations.iter().filter(|op| op.enabled()).count();
-    let folder_count = workspace.sub_folders.len();
-    let total_operations = enabled_operation_count.saturating_mul(folder_count);
-    let mut operations_processed = 0usize;
+    let selected_count = journal.folders.len();
+    let folders: Vec<(usize, String)> = journal
+        .pending_folders()
+        .map(|entry| (entry.index, entry.folder.clone()))
+        .collect();
+    let folder_names: Vec<String> = folders.iter().map(|(_, folder)| folder.clone()).collect();
+    let total_operations: usize = journal
+        .pending_folders()
+        .map(|entry| {
+            workflow
+                .operations
+                .iter()
+                .filter(|op| {
+                    op.enabled() && !entry.completed_operations.iter().any(|id| id == op.id())
+                })
+                .count()
+        })
+        .sum();
 
     if total_operations > 0 {
         let payload = ExecutionProgressPayload {
             operations_processed: 0,
             operations_total: total_operations,
+            folder: None,
         };
         emit_event(window, PROGRESS_EVENT, &payload);
     }
@@ -84,127 +316,266 @@ pub fn execute_workflow(
     };
     let start_entry = push_workspace_log(&mut log_entries, ValidationLevel::Info, workflow_banner);
     emit_event(window, LOG_EVENT, &start_entry);
+    if let Some(previous_run) = &resumed_from {
+        let entry = push_workspace_log(
+            &mut log_entries,
+            ValidationLevel::Info,
+            format!(
+                "Reprise de l'exécution {previous_run}: {}/{} dossier(s) restant(s)",
+                folders.len(),
+                selected_count
+            ),
+        );
+        emit_event(window, LOG_EVENT, &entry);
+    } else if options.selection.is_some() {
+        let (level, message) = if folders.is_empty() {
+            (
+                ValidationLevel::Warning,
+                "Aucun dossier ne correspond à la sélection".to_string(),
+            )
+        } else {
+            (
+                ValidationLevel::Info,
+                format!(
+                    "Sélection: {}/{} dossier(s)",
+                    selected_count,
+                    workspace.sub_folders.len()
+                ),
+            )
+        };
+        let entry = push_workspace_log(&mut log_entries, level, message);
+        emit_event(window, LOG_EVENT, &entry);
+    }
 
-    'folder_loop: for folder in &workspace.sub_folders {
-        if let Err(interrupt) = control.checkpoint() {
-            interrupted_reason = Some(interrupt.reason);
-            break;
-        }
-        let base_path = workspace.folder_absolute_path(folder);
-        if !base_path.exists() {
-            let missing_entry = push_workspace_log(
-                &mut log_entries,
-                ValidationLevel::Error,
-                format!("Le dossier {folder} est introuvable"),
-            );
-            emit_event(window, LOG_EVENT, &missing_entry);
-            let error_message = CommandeurValidationMessage {
-                operation_id: "__workspace__".into(),
-                operation_label: Some("Workspace".into()),
-                level: ValidationLevel::Error,
-                message: format!("Le dossier {folder} est introuvable"),
-                details: None,
-                folders: Some(vec![folder.clone()]),
-            };
-            emit_event(window, VALIDATION_EVENT, &error_message);
-            errors.push(error_message);
-            break 'folder_loop;
-        }
+    let snapshot_path = if options.snapshot {
+        let path = create_snapshot(storage, &run_id, workspace, &folder_names)?;
+        let entry = push_workspace_log(
+            &mut log_entries,
+            ValidationLevel::Info,
+            format!("Instantané créé avant exécution: {}", path.display()),
+        );
+        emit_event(window, LOG_EVENT, &entry);
+        Some(path)
+    } else {
+        None
+    };
 
-        for operation in &workflow.operations {
-            if !operation.enabled() {
-                continue;
-            }
+    let workers = workflow
+        .max_workers
+        .unwrap_or(1)
+        .clamp(1, folders.len().max(1));
+    if workers > 1 {
+        let entry = push_workspace_log(
+            &mut log_entries,
+            ValidationLevel::Info,
+            format!("Exécution parallèle sur {workers} workers"),
+        );
+        emit_event(window, LOG_EVENT, &entry);
+    }
 
-            if let Err(interrupt) = control.checkpoint() {
-                interrupted_reason = Some(interrupt.reason);
-                break 'folder_loop;
-            }
+    let previous_statuses: Vec<CommandeurFolderStatus> = journal
+        .folders
+        .iter()
+        .filter_map(|entry| {
+            entry.status.map(|status| CommandeurFolderStatus {
+                folder: entry.folder.clone(),
+                status,
+            })
+        })
+        .collect();
+    let all_folders: Vec<String> = journal
+        .folders
+        .iter()
+        .map(|entry| entry.folder.clone())
+        .collect();
 
-            let operation_result = execute_operation_for_folder(
-                &workspace,
-                operation,
-                folder,
-                &base_path,
-                window,
-                Some(&control),
-                &mut env,
-                &mut log_entries,
-                &mut warnings,
-            );
+    let run_log_workflow = RunLogWorkflow::new(workflow);
+    let started_entry = RunHistoryEntry {
+        run_id: run_id.clone(),
+        resumed_from: resumed_from.clone(),
+        workflow_id,
+        workflow_name: run_log_workflow.name.clone(),
+        workflow_version: run_log_workflow.version.clone(),
+        workflow_hash: run_log_workflow.hash.clone(),
+        source_path: workspace.source_path.to_string_lossy().to_string(),
+        started_at: started_at.clone(),
+        finished_at: started_at.clone(),
+        status: FolderRunStatus::Interrupted,
+        operations_run: 0,
+        folder_count: all_folders.len(),
+        // Dossiers en échec lors de l'exécution reprise; complété à mesure que les dossiers
+        // se terminent.
+        failed_folder_count: previous_statuses
+            .iter()
+            .filter(|entry| entry.status != FolderRunStatus::Succeeded)
+            .count(),
+        warning_count: 0,
+        error_count: 0,
+        log_file_path: String::new(),
+        run_log_path: None,
+        run_directory: run_dir.to_string_lossy().to_string(),
+        output_archive_path: None,
+        snapshot_path: snapshot_path
+            .as_ref()
+            .map(|path| path.to_string_lossy().to_string()),
+    };
 
-            operations_processed = operations_processed.saturating_add(1);
-            if total_operations > 0 {
-                let payload = ExecutionProgressPayload {
-                    operations_processed,
-                    operations_total: total_operations,
-                };
-                emit_event(window, PROGRESS_EVENT, &payload);
-            }
+    let shared = ExecutionShared {
+        storage,
+        workspace,
+        window,
+        control: &control,
+        env: Mutex::new(ExecutionEnv::new()),
+        halted: AtomicBool::new(false),
+        operations_processed: AtomicUsize::new(0),
+        operations_total: total_operations,
+        default_timeout_seconds: workflow.default_timeout_seconds,
+        run_id: &run_id,
+        run_dir: run_dir.clone(),
+        journal: JournalWriter::create(&run_dir, journal)?,
+        previous_summary: resumed_from
+            .as_deref()
+            .and_then(|previous| load_run_result(storage, previous).ok())
+            .map(|result| result.summary),
+        history: Mutex::new(started_entry.clone()),
+    };
 
-            match operation_result {
-                Ok(()) => {
-                    operations_run += 1;
-                }
-                Err(err) => match err {
-                    CommandeurError::OperationFailed {
-                        operation_id,
-                        operation_label,
-                        continue_on_error,
-                        source,
-                    } => {
-                        let detail = source.to_string();
-                        let entry = push_log_with_meta(
-                            &mut log_entries,
-                            &operation_id,
-                            &operation_label,
-                            ValidationLevel::Error,
-                            format!("[{folder}] Échec: {detail}"),
-                        );
-                        emit_event(window, LOG_EVENT, &entry);
-                        let error_message = CommandeurValidationMessage {
-                            operation_id: operation_id.clone(),
-                            operation_label: Some(operation_label.clone()),
-                            level: ValidationLevel::Error,
-                            message: format!("Erreur lors de l'opération pour {folder}"),
-                            details: Some(detail.clone()),
-                            folders: Some(vec![folder.clone()]),
-                        };
-                        emit_event(window, VALIDATION_EVENT, &error_message);
-                        errors.push(error_message);
-                        if !continue_on_error {
-                            break 'folder_loop;
-                        }
-                    }
-                    CommandeurError::WorkspaceNotFound => {
-                        let entry = push_workspace_log(
-                            &mut log_entries,
-                            ValidationLevel::Error,
-                            "Workspace introuvable pendant l'exécution",
-                        );
-                        emit_event(window, LOG_EVENT, &entry);
-                        break 'folder_loop;
-                    }
-                    CommandeurError::ExecutionInterrupted { reason } => {
-                        interrupted_reason = Some(reason);
-                        break 'folder_loop;
-                    }
-                },
+    if let Err(err) = record_history_entry(storage, &started_entry) {
+        let entry = push_workspace_log(
+            &mut log_entries,
+            ValidationLevel::Warning,
+            format!("Exécution non enregistrée dans l'historique: {err}"),
+        );
+        emit_event(window, LOG_EVENT, &entry);
+    }
+
+    let mut outcomes: Vec<FolderOutcome> = if workers > 1 {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(workers)
+            .build()
+            .map_err(|err| anyhow!("Impossible de démarrer les workers: {}", err))?;
+        pool.install(|| {
+            folders
+                .par_iter()
+                .filter_map(|(index, folder)| run_folder(&shared, workflow, *index, folder))
+                .collect()
+        })
+    } else {
+        let mut outcomes = Vec::new();
+        for (index, folder) in &folders {
+            match run_folder(&shared, workflow, *index, folder) {
+                Some(outcome) => outcomes.push(outcome),
+                None => break,
             }
         }
-        if interrupted_reason.is_some() {
-            break;
-        }
-    }
+        outcomes
+    };
 
-    let log_file_path = write_execution_log(&workspace, &log_entries, &warnings, &errors)?;
+    let folder_statuses: Vec<CommandeurFolderStatus> = all_folders
+        .iter()
+        .map(|folder| {
+            let previous = previous_statuses
+                .iter()
+                .find(|previous| &previous.folder == folder)
+                .map(|previous| previous.status);
+            let status = match outcomes.iter().find(|outcome| &outcome.folder == folder) {
+                None => previous.unwrap_or(FolderRunStatus::Skipped),
+                Some(outcome) if !outcome.errors.is_empty() => FolderRunStatus::Failed,
+                Some(outcome) if outcome.interrupted_reason.is_some() || !outcome.finished => {
+                    FolderRunStatus::Interrupted
+                }
+                Some(_) => FolderRunStatus::Succeeded,
+            };
+            CommandeurFolderStatus {
+                folder: folder.clone(),
+                status,
+            }
+        })
+        .collect();
+    if let Err(err) = write_folder_statuses(&run_dir, &folder_statuses) {
+        let entry = push_workspace_log(
+            &mut log_entries,
+            ValidationLevel::Warning,
+            format!("Bilan par dossier non enregistré: {err}"),
+        );
+        emit_event(window, LOG_EVENT, &entry);
+    }
 
-    let output_archive_path = match workspace.mode {
-        WorkspaceMode::Zip => Some(repack_zip(&workspace)?),
-        WorkspaceMode::Directory => None,
+    let summary = CommandeurExecutionSummary {
+        columns: summary_columns(&workflow.operations),
+        folders: folder_statuses
+            .iter()
+            .map(|entry| {
+                match outcomes
+                    .iter_mut()
+                    .find(|outcome| outcome.folder == entry.folder)
+                {
+                    Some(outcome) => folder_summary(
+                        &workflow.operations,
+                        &entry.folder,
+                        entry.status,
+                        Some(outcome.duration_ms),
+                        std::mem::take(&mut outcome.cells),
+                    ),
+                    // Dossier terminé lors d'une exécution précédente ou non traité.
+                    None => match shared.previous_row(&entry.folder) {
+                        Some(previous) => CommandeurFolderSummary {
+                            status: entry.status,
+                            ..previous.clone()
+                        },
+                        None => folder_summary(
+                            &workflow.operations,
+                            &entry.folder,
+                            entry.status,
+                            None,
+                            FolderCells::default(),
+                        ),
+                    },
+                }
+            })
+            .collect(),
     };
 
-    let log_path_string = log_file_path.to_string_lossy().to_string();
+    let run_log_folders: Vec<RunLogFolder> = folder_statuses
+        .iter()
+        .map(|entry| {
+            let outcome = outcomes
+                .iter()
+                .find(|outcome| outcome.folder == entry.folder);
+            RunLogFolder {
+                folder: entry.folder.clone(),
+                status: entry.status,
+                duration_ms: outcome.map(|outcome| outcome.duration_ms),
+                operations: outcome
+                    .map(|outcome| outcome.operations.clone())
+                    .unwrap_or_default(),
+                captured: outcome
+                    .map(|outcome| {
+                        outcome
+                            .captured
+                            .iter()
+                            .map(|(name, value)| (name.clone(), value.clone()))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                conditions: outcome
+                    .map(|outcome| outcome.conditions.clone())
+                    .unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    for outcome in outcomes {
+        log_entries.extend(outcome.log_entries);
+        warnings.extend(outcome.warnings);
+        errors.extend(outcome.errors);
+        operations_run += outcome.operations_run;
+        timeouts.extend(outcome.timeouts);
+        artifacts.extend(outcome.artifacts);
+        if interrupted_reason.is_none() {
+            interrupted_reason = outcome.interrupted_reason;
+        }
+    }
 
     if let Some(reason) = interrupted_reason.as_ref() {
         let status = control.status();
@@ -218,597 +589,1497 @@ pub fn execute_workflow(
         }
     }
 
-    Ok(CommandeurExecutionResult {
-        success: errors.is_empty() && interrupted_reason.is_none(),
+    let output_archive_path = match workspace.mode {
+        WorkspaceMode::Zip => Some(repack_zip(workspace)?),
+        WorkspaceMode::Directory => None,
+    };
+
+    let success = errors.is_empty() && interrupted_reason.is_none();
+    let mut run_log = RunLog {
+        format_version: RUN_LOG_FORMAT_VERSION,
+        run_id: run_id.clone(),
+        resumed_from: resumed_from.clone(),
+        started_at: started_at.clone(),
+        finished_at: Utc::now().to_rfc3339(),
+        success,
+        interrupted_reason: interrupted_reason.clone(),
+        workflow: run_log_workflow,
+        workspace: RunLogWorkspace::new(workspace),
+        folders: run_log_folders,
+        entries: log_entries.clone(),
+        warnings: warnings.clone(),
+        errors: errors.clone(),
+        timeouts: timeouts.clone(),
+        artifacts: artifacts.clone(),
+        summary: summary.clone(),
+    };
+
+    // Les journaux sont écrits en dernier, une fois tous les avertissements de fin connus: un
+    // fichier ne peut signaler que les échecs d'écriture qui le précèdent.
+    let report_path = match write_html_report(&run_dir, &run_log) {
+        Ok(path) => Some(path.to_string_lossy().to_string()),
+        Err(err) => {
+            let entry = push_workspace_log(
+                &mut log_entries,
+                ValidationLevel::Warning,
+                format!("Rapport HTML non enregistré: {err}"),
+            );
+            emit_event(window, LOG_EVENT, &entry);
+            None
+        }
+    };
+
+    let summary_csv_path = match write_summary_csv(&run_dir, &summary) {
+        Ok(path) => Some(path.to_string_lossy().to_string()),
+        Err(err) => {
+            let entry = push_workspace_log(
+                &mut log_entries,
+                ValidationLevel::Warning,
+                format!("Tableau récapitulatif non enregistré: {err}"),
+            );
+            emit_event(window, LOG_EVENT, &entry);
+            None
+        }
+    };
+
+    run_log.entries = log_entries.clone();
+    let run_log_path = match write_run_log(&run_dir, &run_log) {
+        Ok(path) => Some(path.to_string_lossy().to_string()),
+        Err(err) => {
+            let entry = push_workspace_log(
+                &mut log_entries,
+                ValidationLevel::Warning,
+                format!("Journal structuré non enregistré: {err}"),
+            );
+            emit_event(window, LOG_EVENT, &entry);
+            None
+        }
+    };
+
+    let log_file_path = execution_log_path(storage)?;
+    let mut result = CommandeurExecutionResult {
+        run_id,
+        success,
         operations_run,
-        log_file_path: log_path_string,
+        log_file_path: log_file_path.to_string_lossy().to_string(),
         log_entries,
         warnings,
         errors,
         output_archive_path: output_archive_path.map(|p| p.to_string_lossy().to_string()),
-    })
+        snapshot_path: snapshot_path.map(|p| p.to_string_lossy().to_string()),
+        timeouts,
+        run_directory: run_dir.to_string_lossy().to_string(),
+        artifacts,
+        folder_statuses,
+        run_log_path,
+        summary,
+        summary_csv_path,
+        report_path,
+    };
+    let history_entry = RunHistoryEntry {
+        finished_at: run_log.finished_at.clone(),
+        status: run_status(success, interrupted_reason.is_some()),
+        operations_run: result.operations_run,
+        folder_count: result.folder_statuses.len(),
+        failed_folder_count: result
+            .folder_statuses
+            .iter()
+            .filter(|entry| entry.status != FolderRunStatus::Succeeded)
+            .count(),
+        warning_count: result.warnings.len(),
+        error_count: result.errors.len(),
+        log_file_path: result.log_file_path.clone(),
+        run_log_path: result.run_log_path.clone(),
+        run_directory: result.run_directory.clone(),
+        output_archive_path: result.output_archive_path.clone(),
+        ..started_entry
+    };
+    if let Err(err) = record_run(storage, &history_entry, &result) {
+        let entry = push_workspace_log(
+            &mut result.log_entries,
+            ValidationLevel::Warning,
+            format!("Exécution non enregistrée dans l'historique: {err}"),
+        );
+        emit_event(window, LOG_EVENT, &entry);
+    }
+    write_execution_log(
+        &log_file_path,
+        workspace,
+        &result.log_entries,
+        &result.warnings,
+        &result.errors,
+    )?;
+    Ok(result)
 }
 
-fn execute_operation_for_folder(
-    workspace: &WorkspaceHandle,
-    operation: &CommandeurOperation,
+/// Exécute toutes les opérations d'un sous-dossier. Retourne `None` si l'exécution a été
+/// arrêtée (échec bloquant ou arrêt manuel) avant que ce dossier ne soit commencé.
+fn run_folder(
+    shared: &ExecutionShared<'_>,
+    workflow: &CommandeurWorkflow,
+    index: usize,
     folder: &str,
-    base_path: &Path,
-    window: Option<&Window>,
-    control: Option<&ExecutionControl>,
-    env: &mut ExecutionEnv,
-    log_entries: &mut Vec<CommandeurExecutionLogEntry>,
-    warnings: &mut Vec<CommandeurValidationMessage>,
-) -> Result<(), CommandeurError> {
-    if let Some(ctrl) = control {
-        ctrl.checkpoint().map_err(|ExecutionInterrupt { reason }| {
-            CommandeurError::ExecutionInterrupted { reason }
-        })?;
-    }
-    if let Some(comment) = operation.comment() {
-        let trimmed = comment.trim();
-        if !trimmed.is_empty() {
-            let entry = push_log(
-                log_entries,
-                operation,
-                ValidationLevel::Info,
-                format!("[{folder}] Note: {trimmed}"),
+) -> Option<FolderOutcome> {
+    if shared.halted.load(Ordering::SeqCst) {
+        return None;
+    }
+    let started = Instant::now();
+    let mut outcome = FolderOutcome {
+        folder: folder.to_string(),
+        log_entries: Vec::new(),
+        warnings: Vec::new(),
+        errors: Vec::new(),
+        operations_run: 0,
+        timeouts: Vec::new(),
+        artifacts: Vec::new(),
+        interrupted_reason: None,
+        finished: false,
+        operations: Vec::new(),
+        duration_ms: 0,
+        cells: FolderCells::default(),
+        captured: HashMap::new(),
+        conditions: Vec::new(),
+    };
+    if let Err(interrupt) = shared.control.checkpoint() {
+        shared.halted.store(true, Ordering::SeqCst);
+        outcome.interrupted_reason = Some(interrupt.reason);
+        return Some(outcome);
+    }
+
+    let base_path = shared.workspace.folder_absolute_path(folder);
+    if !base_path.exists() {
+        halt_on_folder_error(
+            shared,
+            &mut outcome,
+            folder,
+            format!("Le dossier {folder} est introuvable"),
+        );
+        return Some(outcome);
+    }
+
+    let template = match TemplateContext::for_folder(
+        &workflow.variables,
+        folder,
+        index,
+        &shared.workspace.root_path,
+        shared.run_id,
+    ) {
+        Ok(template) => template,
+        Err(err) => {
+            halt_on_folder_error(
+                shared,
+                &mut outcome,
+                folder,
+                format!("Variables du workflow invalides: {err}"),
             );
-            emit_event(window, LOG_EVENT, &entry);
+            return Some(outcome);
+        }
+    };
+
+    let mut run = FolderRun {
+        shared,
+        folder,
+        base_path,
+        template,
+        captured: HashMap::new(),
+        log_entries: Vec::new(),
+        warnings: Vec::new(),
+        errors: Vec::new(),
+        timeouts: Vec::new(),
+        artifacts: Vec::new(),
+        process_sequence: 0,
+        cells: FolderCells::default(),
+        conditions: Vec::new(),
+        completed: Vec::new(),
+        decided: BTreeMap::new(),
+        loop_depth: 0,
+    };
+
+    if let Some(entry) = shared.journal.folder(folder) {
+        for (name, value) in entry.captured {
+            run.template.set(name.clone(), value.clone());
+            run.captured.insert(name, value);
         }
+        run.completed = entry.completed_operations;
+        run.decided = entry.conditions;
     }
 
-    match &operation.details {
-        OperationDetails::CreateFile {
-            target,
-            overwrite,
-            content,
-        } => {
-            let target_path = resolve_in_folder(base_path, target)
-                .map_err(|err| operation_failed(operation, err))?;
-            ensure_parent_dir(&target_path).map_err(|err| operation_failed(operation, err))?;
-            if target_path.exists() && !overwrite {
-                let validation = push_folder_validation(
-                    warnings,
-                    operation,
-                    folder,
-                    ValidationLevel::Warning,
-                    format!("[{folder}] Le fichier existe déjà, création ignorée"),
-                    Some(target_path.display().to_string()),
-                );
-                emit_event(window, VALIDATION_EVENT, &validation);
-                let entry = push_log(
-                    log_entries,
-                    operation,
-                    ValidationLevel::Info,
-                    format!("[{folder}] Fichier existant conservé: {target}"),
-                );
-                emit_event(window, LOG_EVENT, &entry);
-            } else {
-                fs::write(&target_path, content).map_err(|err| operation_failed(operation, err))?;
-                let entry = push_log(
-                    log_entries,
+    let mut finished = true;
+    for operation in &workflow.operations {
+        if !operation.enabled() {
+            continue;
+        }
+        if run.skip_completed(operation) {
+            outcome
+                .operations
+                .push(operation_report(operation, FolderRunStatus::Skipped, 0));
+            continue;
+        }
+        if shared.halted.load(Ordering::SeqCst) {
+            finished = false;
+            break;
+        }
+        if let Err(interrupt) = shared.control.checkpoint() {
+            outcome.interrupted_reason = Some(interrupt.reason);
+            shared.halted.store(true, Ordering::SeqCst);
+            finished = false;
+            break;
+        }
+
+        let operation_started = Instant::now();
+        let operation_result = run.execute_recorded(operation);
+        shared.record_progress(folder);
+        let duration_ms = operation_started.elapsed().as_millis() as u64;
+
+        match operation_result {
+            Ok(()) => {
+                outcome.operations_run += 1;
+                outcome.operations.push(operation_report(
                     operation,
-                    ValidationLevel::Info,
-                    format!("[{folder}] Fichier créé: {target}"),
-                );
-                emit_event(window, LOG_EVENT, &entry);
+                    FolderRunStatus::Succeeded,
+                    duration_ms,
+                ));
             }
-        }
-        OperationDetails::DeleteFile { target, required } => {
-            let target_path = resolve_in_folder(base_path, target)
-                .map_err(|err| operation_failed(operation, err))?;
-            if !target_path.exists() {
-                let message = format!("[{folder}] Fichier à supprimer introuvable: {target}");
-                if *required {
-                    let validation = push_folder_validation(
-                        warnings,
-                        operation,
-                        folder,
-                        ValidationLevel::Error,
-                        message.clone(),
-                        None,
-                    );
-                    emit_event(window, VALIDATION_EVENT, &validation);
-                    return Err(operation_failed(
+            Err(err) => match run.record_failure(err) {
+                FailureDisposition::Continue => {
+                    outcome.operations.push(operation_report(
                         operation,
-                        anyhow!("Suppression requise impossible: fichier introuvable"),
+                        FolderRunStatus::Failed,
+                        duration_ms,
                     ));
-                } else {
-                    let validation = push_folder_validation(
-                        warnings,
+                }
+                FailureDisposition::Halt => {
+                    outcome.operations.push(operation_report(
                         operation,
-                        folder,
-                        ValidationLevel::Warning,
-                        message,
-                        None,
-                    );
-                    emit_event(window, VALIDATION_EVENT, &validation);
+                        FolderRunStatus::Failed,
+                        duration_ms,
+                    ));
+                    shared.halted.store(true, Ordering::SeqCst);
+                    finished = false;
+                    break;
                 }
-            } else {
-                remove_path(&target_path).map_err(|err| operation_failed(operation, err))?;
-                let entry = push_log(
-                    log_entries,
-                    operation,
-                    ValidationLevel::Info,
-                    format!("[{folder}] Fichier supprimé: {target}"),
-                );
-                emit_event(window, LOG_EVENT, &entry);
-            }
-        }
-        OperationDetails::Copy {
-            source,
-            destination,
-            overwrite,
-        } => {
-            let source_path = resolve_in_folder(base_path, source)
-                .map_err(|err| operation_failed(operation, err))?;
-            if !source_path.exists() {
-                return Err(operation_failed(
-                    operation,
-                    anyhow!("Source introuvable: {}", source_path.display()),
-                ));
-            }
-            if source_path.is_dir() {
-                return Err(operation_failed(
-                    operation,
-                    anyhow!("La copie de dossiers n'est pas supportée"),
-                ));
-            }
-            let dest_path = resolve_in_folder(base_path, destination)
-                .map_err(|err| operation_failed(operation, err))?;
-            ensure_parent_dir(&dest_path).map_err(|err| operation_failed(operation, err))?;
-            if dest_path.exists() {
-                if *overwrite {
-                    remove_path(&dest_path).map_err(|err| operation_failed(operation, err))?;
-                } else {
-                    return Err(operation_failed(
+                FailureDisposition::Interrupted(reason) => {
+                    outcome.operations.push(operation_report(
                         operation,
-                        anyhow!("La destination existe déjà: {}", dest_path.display()),
+                        FolderRunStatus::Interrupted,
+                        duration_ms,
                     ));
+                    outcome.interrupted_reason = Some(reason);
+                    shared.halted.store(true, Ordering::SeqCst);
+                    finished = false;
+                    break;
                 }
-            }
-            fs::copy(&source_path, &dest_path).map_err(|err| operation_failed(operation, err))?;
-            let entry = push_log(
-                log_entries,
-                operation,
-                ValidationLevel::Info,
-                format!(
-                    "[{folder}] Fichier copié de {} vers {}",
-                    source, destination
-                ),
-            );
-            emit_event(window, LOG_EVENT, &entry);
+            },
         }
-        OperationDetails::Exec {
-            command,
-            args,
-            shell,
-            cwd,
-            env: custom_env,
-        } => {
-            let mut cmd = build_command(shell, command, args)
-                .map_err(|err| operation_failed(operation, err))?;
-            let cwd_path = if let Some(cwd_fragment) = cwd {
-                resolve_in_folder(base_path, cwd_fragment)
-                    .map_err(|err| operation_failed(operation, err))?
-            } else {
-                base_path.to_path_buf()
-            };
-            cmd.current_dir(&cwd_path);
-            if let Some(env_map) = custom_env {
-                cmd.envs(env_map.iter().map(|(k, v)| (k, v)));
-            }
-            let output = cmd
-                .output()
-                .map_err(|err| operation_failed(operation, err))?;
-            if !output.status.success() {
-                let stdout_raw = String::from_utf8_lossy(&output.stdout);
-                let stderr_raw = String::from_utf8_lossy(&output.stderr);
-                let stdout = sanitize_output(&stdout_raw);
-                let stderr = sanitize_output(&stderr_raw);
-                return Err(operation_failed(
-                    operation,
-                    anyhow!(
-                        "Commande échouée (code: {:?})\nSTDOUT:\n{}\nSTDERR:\n{}",
-                        output.status.code(),
-                        stdout,
-                        stderr
-                    ),
-                ));
-            }
-            let entry = push_log(
-                log_entries,
-                operation,
-                ValidationLevel::Info,
-                format!("[{folder}] Commande exécutée: {command}"),
+        run.complete_operation(operation);
+    }
+
+    if finished {
+        let status = if run.errors.is_empty() {
+            FolderRunStatus::Succeeded
+        } else {
+            FolderRunStatus::Failed
+        };
+        if let Err(err) = shared.journal.finish_folder(folder, status) {
+            let entry = push_log_with_meta(
+                &mut run.log_entries,
+                "__workspace__",
+                "Workspace",
+                Some(folder),
+                ValidationLevel::Warning,
+                format!("Journal de reprise non mis à jour: {err}"),
             );
-            emit_event(window, LOG_EVENT, &entry);
+            emit_event(shared.window, LOG_EVENT, &entry);
         }
-        OperationDetails::ReplaceInFile {
-            target,
-            search,
-            replace,
-            mode,
-            flags,
-        } => {
-            let target_path = resolve_in_folder(base_path, target)
-                .map_err(|err| operation_failed(operation, err))?;
-            if !target_path.exists() {
-                return Err(operation_failed(
-                    operation,
-                    anyhow!("Fichier introuvable: {target}"),
-                ));
-            }
-            let original =
-                fs::read_to_string(&target_path).map_err(|err| operation_failed(operation, err))?;
-            let (updated, count) = match mode {
-                ReplaceMode::Plain => {
-                    let occurrences = original.matches(search).count();
-                    (original.replace(search, replace), occurrences)
-                }
-                ReplaceMode::Regex => {
-                    let regex = build_regex(search, flags.as_deref())
-                        .map_err(|err| operation_failed(operation, err))?;
-                    let count = regex.find_iter(&original).count();
-                    if count == 0 {
-                        (original.clone(), 0)
-                    } else {
-                        (
-                            regex.replace_all(&original, replace.as_str()).to_string(),
-                            count,
-                        )
-                    }
-                }
-            };
-            if count == 0 {
-                let validation = push_folder_validation(
-                    warnings,
-                    operation,
-                    folder,
-                    ValidationLevel::Info,
-                    format!("[{folder}] Aucun remplacement pour {target}"),
-                    None,
+        if status == FolderRunStatus::Failed {
+            if let Err(err) = shared.record_failed_folder() {
+                let entry = push_log_with_meta(
+                    &mut run.log_entries,
+                    "__workspace__",
+                    "Workspace",
+                    Some(folder),
+                    ValidationLevel::Warning,
+                    format!("Historique des exécutions non mis à jour: {err}"),
                 );
-                emit_event(window, VALIDATION_EVENT, &validation);
-            } else {
-                fs::write(&target_path, updated).map_err(|err| operation_failed(operation, err))?;
-                let entry = push_log(
-                    log_entries,
-                    operation,
-                    ValidationLevel::Info,
-                    format!(
-                        "[{folder}] {} occurrence(s) remplacée(s) dans {target}",
-                        count
+                emit_event(shared.window, LOG_EVENT, &entry);
+            }
+        }
+    }
+
+    outcome.finished = finished;
+    outcome.log_entries.extend(run.log_entries);
+    outcome.warnings.extend(run.warnings);
+    outcome.errors.extend(run.errors);
+    outcome.timeouts.extend(run.timeouts);
+    outcome.artifacts.extend(run.artifacts);
+    outcome.cells = run.cells;
+    outcome.captured = run.captured;
+    outcome.conditions = run.conditions;
+    outcome.duration_ms = started.elapsed().as_millis() as u64;
+    Some(outcome)
+}
+
+/// Supprime une entrée sans suivre les liens. Sous Windows, un lien vers un dossier se
+/// supprime comme un dossier.
+/// Dossier réel, sans suivre un lien symbolique (un lien est remplacé comme un fichier).
+fn is_real_dir(path: &Path) -> bool {
+    fs::symlink_metadata(path)
+        .map(|metadata| metadata.is_dir())
+        .unwrap_or(false)
+}
+
+fn remove_entry(path: &Path, file_type: fs::FileType) -> std::io::Result<()> {
+    if file_type.is_dir() {
+        fs::remove_dir(path)
+    } else if file_type.is_symlink() {
+        fs::remove_file(path).or_else(|_| fs::remove_dir(path))
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+fn operation_report(
+    operation: &CommandeurOperation,
+    status: FolderRunStatus,
+    duration_ms: u64,
+) -> RunLogOperation {
+    RunLogOperation {
+        operation_id: operation.id().to_string(),
+        operation_label: operation.label().to_string(),
+        status,
+        duration_ms,
+    }
+}
+
+fn halt_on_folder_error(
+    shared: &ExecutionShared<'_>,
+    outcome: &mut FolderOutcome,
+    folder: &str,
+    message: String,
+) {
+    let entry = push_log_with_meta(
+        &mut outcome.log_entries,
+        "__workspace__",
+        "Workspace",
+        Some(folder),
+        ValidationLevel::Error,
+        message.clone(),
+    );
+    emit_event(shared.window, LOG_EVENT, &entry);
+    let error_message = CommandeurValidationMessage {
+        operation_id: "__workspace__".into(),
+        operation_label: Some("Workspace".into()),
+        level: ValidationLevel::Error,
+        message,
+        details: None,
+        folders: Some(vec![folder.to_string()]),
+    };
+    emit_event(shared.window, VALIDATION_EVENT, &error_message);
+    outcome.errors.push(error_message);
+    shared.halted.store(true, Ordering::SeqCst);
+}
+
+impl FolderRun<'_, '_> {
+    /// Ignore une opération déjà effectuée avant la reprise, en reprenant son bilan.
+    fn skip_completed(&mut self, operation: &CommandeurOperation) -> bool {
+        if self.loop_depth > 0 || !self.completed.iter().any(|id| id == operation.id()) {
+            return false;
+        }
+        let folder = self.folder;
+        self.log(
+            operation,
+            ValidationLevel::Info,
+            format!("[{folder}] Déjà effectuée, ignorée à la reprise"),
+        );
+        self.cells
+            .restore(self.shared.previous_row(folder), operation);
+        true
+    }
+
+    fn complete_operation(&mut self, operation: &CommandeurOperation) {
+        if self.loop_depth > 0 {
+            return;
+        }
+        let folder = self.folder;
+        if let Err(err) =
+            self.shared
+                .journal
+                .complete_operation(folder, operation.id(), &self.captured)
+        {
+            self.log(
+                operation,
+                ValidationLevel::Warning,
+                format!("[{folder}] Journal de reprise non mis à jour: {err}"),
+            );
+        }
+    }
+
+    /// Résultat d'une condition déjà évaluée avant l'interruption (hors boucle).
+    fn decided_condition(&mut self, operation: &CommandeurOperation) -> Option<JournalCondition> {
+        if self.loop_depth > 0 {
+            return None;
+        }
+        let decided = self.decided.remove(operation.id())?;
+        let folder = self.folder;
+        self.log(
+            operation,
+            ValidationLevel::Info,
+            format!("[{folder}] Condition déjà évaluée, résultat repris sans la réévaluer"),
+        );
+        Some(decided)
+    }
+
+    fn record_condition(&mut self, operation: &CommandeurOperation, condition: JournalCondition) {
+        if self.loop_depth > 0 {
+            return;
+        }
+        let folder = self.folder;
+        if let Err(err) = self
+            .shared
+            .journal
+            .record_condition(folder, operation.id(), condition)
+        {
+            self.log(
+                operation,
+                ValidationLevel::Warning,
+                format!("[{folder}] Journal de reprise non mis à jour: {err}"),
+            );
+        }
+    }
+
+    fn log(&mut self, operation: &CommandeurOperation, level: ValidationLevel, message: String) {
+        let entry = push_folder_log(
+            &mut self.log_entries,
+            operation,
+            self.folder,
+            level,
+            message,
+        );
+        emit_event(self.shared.window, LOG_EVENT, &entry);
+    }
+
+    fn warn(
+        &mut self,
+        operation: &CommandeurOperation,
+        level: ValidationLevel,
+        message: String,
+        details: Option<String>,
+    ) {
+        let validation = push_folder_validation(
+            &mut self.warnings,
+            operation,
+            self.folder,
+            level,
+            message,
+            details,
+        );
+        emit_event(self.shared.window, VALIDATION_EVENT, &validation);
+    }
+
+    fn run_child(
+        &mut self,
+        operation: &CommandeurOperation,
+        cmd: Command,
+        command_line: &str,
+        timeout_seconds: Option<u64>,
+    ) -> Result<ProcessOutput, CommandeurError> {
+        let timeout_seconds = timeout_seconds
+            .or(self.shared.default_timeout_seconds)
+            .filter(|seconds| *seconds > 0);
+        let window = self.shared.window;
+        let folder = self.folder;
+        let operation_id = operation.id();
+        let mut forward_output = |batch: OutputBatch| {
+            let payload = ExecutionOutputPayload {
+                operation_id,
+                folder,
+                lines: batch.lines,
+                dropped_lines: batch.dropped_lines,
+            };
+            emit_event(window, OUTPUT_EVENT, &payload);
+        };
+        let outcome = run_process(
+            cmd,
+            timeout_seconds.map(Duration::from_secs),
+            self.shared.control,
+            &mut forward_output,
+        )
+        .map_err(|err| operation_failed(operation, err))?;
+        let (output, timed_out) = match outcome {
+            ProcessOutcome::Completed(output) => (output, false),
+            ProcessOutcome::TimedOut(output) => (output, true),
+            ProcessOutcome::Interrupted { reason } => {
+                return Err(CommandeurError::ExecutionInterrupted { reason })
+            }
+        };
+        self.record_artifact(operation, command_line, &output, timed_out);
+        if timed_out {
+            return Err(CommandeurError::OperationTimedOut {
+                operation_id: operation.id().to_string(),
+                operation_label: operation.label().to_string(),
+                continue_on_error: operation.continue_on_error(),
+                timeout_seconds: timeout_seconds.unwrap_or_default(),
+            });
+        }
+        Ok(output)
+    }
+
+    fn run_condition_command(
+        &mut self,
+        operation: &CommandeurOperation,
+        base_path: &Path,
+        command: &ConditionCommand,
+    ) -> Result<ConditionCommandOutput, CommandeurError> {
+        let (mut cmd, command_line) = match command.runner {
+            ConditionRunner::Python => {
+                let interpreter = self
+                    .shared
+                    .ensure_python()
+                    .map_err(|err| operation_failed(operation, err))?;
+                let mut cmd = Command::new(&interpreter);
+                cmd.arg("-c").arg(&command.command);
+                (cmd, format!("{interpreter} -c <condition>"))
+            }
+            ConditionRunner::Command => {
+                // Une condition est une ligne de commande complète (`javac *.java`): le shell
+                // « Système » est celui de la plateforme.
+                let cmd = match command.shell {
+                    ShellKind::Default => system_shell(&command.command),
+                    ref shell => build_command(shell, &command.command, &[])
+                        .map_err(|err| operation_failed(operation, err))?,
+                };
+                (cmd, command.command.clone())
+            }
+        };
+        cmd.current_dir(base_path);
+        let output = self.run_child(operation, cmd, &command_line, command.timeout_seconds)?;
+        Ok(ConditionCommandOutput {
+            exit_code: output.status.and_then(|status| status.code()),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        })
+    }
+
+    fn apply_capture(
+        &mut self,
+        operation: &CommandeurOperation,
+        capture: &OutputCapture,
+        output: &ProcessOutput,
+    ) -> Result<(), CommandeurError> {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let value = match capture.source {
+            CaptureSource::Stdout => stdout.trim().to_string(),
+            CaptureSource::ExitCode => output
+                .status
+                .and_then(|status| status.code())
+                .map(|code| code.to_string())
+                .unwrap_or_default(),
+            CaptureSource::Regex => {
+                let pattern = capture.pattern.as_deref().unwrap_or_default();
+                let regex = build_regex(pattern, capture.flags.as_deref())
+                    .map_err(|err| operation_failed(operation, err))?;
+                let captures = regex.captures(&stdout).ok_or_else(|| {
+                    operation_failed(
+                        operation,
+                        anyhow!("Aucune correspondance pour la capture {}", capture.name),
+                    )
+                })?;
+                captures
+                    .get(1)
+                    .or_else(|| captures.get(0))
+                    .map(|m| m.as_str().trim().to_string())
+                    .unwrap_or_default()
+            }
+        };
+        let name = capture.name.trim().to_string();
+        let folder = self.folder;
+        let preview: String = value.chars().take(200).collect();
+        self.log(
+            operation,
+            ValidationLevel::Info,
+            format!("[{folder}] Valeur capturée {name} = {preview}"),
+        );
+        self.template.set(name.clone(), value.clone());
+        self.captured.insert(name, value);
+        Ok(())
+    }
+
+    fn record_artifact(
+        &mut self,
+        operation: &CommandeurOperation,
+        command_line: &str,
+        output: &ProcessOutput,
+        timed_out: bool,
+    ) {
+        self.process_sequence += 1;
+        let folder = self.folder;
+        match persist_process_output(
+            &self.shared.run_dir,
+            folder,
+            self.process_sequence,
+            operation,
+            command_line,
+            output,
+            timed_out,
+        ) {
+            Ok(artifact) => {
+                let entry = CommandeurExecutionLogEntry::new(
+                    operation.id(),
+                    operation.label(),
+                    ValidationLevel::Info,
+                    format!(
+                        "[{folder}] Sorties enregistrées ({} ms)",
+                        artifact.duration_ms
                     ),
+                )
+                .with_folder(folder)
+                .with_artifacts(vec![
+                    artifact.stdout_path.clone(),
+                    artifact.stderr_path.clone(),
+                    artifact.result_path.clone(),
+                ]);
+                self.log_entries.push(entry.clone());
+                emit_event(self.shared.window, LOG_EVENT, &entry);
+                self.artifacts.push(artifact);
+            }
+            Err(err) => self.warn(
+                operation,
+                ValidationLevel::Warning,
+                "Impossible d'enregistrer les sorties du processus".into(),
+                Some(err.to_string()),
+            ),
+        }
+    }
+
+    /// Journalise l'échec d'une opération et indique si le dossier peut poursuivre.
+    fn record_failure(&mut self, err: CommandeurError) -> FailureDisposition {
+        let shared = self.shared;
+        let folder = self.folder;
+        match err {
+            CommandeurError::OperationFailed {
+                operation_id,
+                operation_label,
+                continue_on_error,
+                source,
+            } => {
+                let detail = source.to_string();
+                let entry = push_log_with_meta(
+                    &mut self.log_entries,
+                    &operation_id,
+                    &operation_label,
+                    Some(folder),
+                    ValidationLevel::Error,
+                    format!("[{folder}] Échec: {detail}"),
+                );
+                emit_event(shared.window, LOG_EVENT, &entry);
+                let error_message = CommandeurValidationMessage {
+                    operation_id: operation_id.clone(),
+                    operation_label: Some(operation_label.clone()),
+                    level: ValidationLevel::Error,
+                    message: format!("Erreur lors de l'opération pour {folder}"),
+                    details: Some(detail.clone()),
+                    folders: Some(vec![folder.to_string()]),
+                };
+                emit_event(shared.window, VALIDATION_EVENT, &error_message);
+                self.errors.push(error_message);
+                if continue_on_error {
+                    FailureDisposition::Continue
+                } else {
+                    FailureDisposition::Halt
+                }
+            }
+            CommandeurError::OperationTimedOut {
+                operation_id,
+                operation_label,
+                continue_on_error,
+                timeout_seconds,
+            } => {
+                let entry = push_log_with_meta(
+                    &mut self.log_entries,
+                    &operation_id,
+                    &operation_label,
+                    Some(folder),
+                    ValidationLevel::Error,
+                    format!("[{folder}] Délai dépassé ({timeout_seconds} s), processus arrêté"),
+                );
+                emit_event(shared.window, LOG_EVENT, &entry);
+                let error_message = CommandeurValidationMessage {
+                    operation_id: operation_id.clone(),
+                    operation_label: Some(operation_label.clone()),
+                    level: ValidationLevel::Error,
+                    message: format!("Délai dépassé pour {folder}"),
+                    details: Some(format!("Le processus a été tué après {timeout_seconds} s")),
+                    folders: Some(vec![folder.to_string()]),
+                };
+                emit_event(shared.window, VALIDATION_EVENT, &error_message);
+                self.errors.push(error_message);
+                self.timeouts.push(CommandeurOperationTimeout {
+                    operation_id,
+                    operation_label,
+                    folder: folder.to_string(),
+                    timeout_seconds,
+                });
+                if continue_on_error {
+                    FailureDisposition::Continue
+                } else {
+                    FailureDisposition::Halt
+                }
+            }
+            CommandeurError::WorkspaceNotFound => {
+                let entry = push_log_with_meta(
+                    &mut self.log_entries,
+                    "__workspace__",
+                    "Workspace",
+                    Some(folder),
+                    ValidationLevel::Error,
+                    "Workspace introuvable pendant l'exécution",
                 );
-                emit_event(window, LOG_EVENT, &entry);
+                emit_event(shared.window, LOG_EVENT, &entry);
+                FailureDisposition::Halt
+            }
+            CommandeurError::ExecutionInterrupted { reason } => {
+                FailureDisposition::Interrupted(reason)
             }
         }
-        OperationDetails::Rename {
-            target,
-            mode,
-            value,
-            search,
-            replace,
-        } => {
-            let source_path = resolve_in_folder(base_path, target)
-                .map_err(|err| operation_failed(operation, err))?;
-            if !source_path.exists() {
-                return Err(operation_failed(
-                    operation,
-                    anyhow!("Fichier ou dossier introuvable: {target}"),
-                ));
+    }
+
+    /// Exécute les opérations d'une boucle pour chaque élément. Un échec d'une opération
+    /// `continueOnError` est journalisé comme au premier niveau et la boucle continue.
+    fn run_loop(
+        &mut self,
+        items: &[String],
+        operations: &[CommandeurOperation],
+    ) -> Result<(), CommandeurError> {
+        for item in items {
+            self.template.bind_item(item);
+            for child in operations {
+                if !child.enabled() {
+                    continue;
+                }
+                if let Err(err) = self.execute_recorded(child) {
+                    if !err.continue_on_error() {
+                        return Err(err);
+                    }
+                    self.record_failure(err);
+                }
             }
-            let dest_path = compute_rename_destination(
-                &source_path,
-                mode,
-                value,
-                search.as_deref(),
-                replace.as_deref(),
+        }
+        Ok(())
+    }
+
+    /// Point de pause et d'arrêt, appelé entre les opérations et dans leurs boucles.
+    fn checkpoint(&self) -> Result<(), CommandeurError> {
+        self.shared
+            .control
+            .checkpoint()
+            .map_err(
+                |ExecutionInterrupt { reason }| CommandeurError::ExecutionInterrupted { reason },
             )
+    }
+
+    /// Exécute l'opération et consigne son statut dans le bilan du dossier.
+    fn execute_recorded(&mut self, operation: &CommandeurOperation) -> Result<(), CommandeurError> {
+        let started = Instant::now();
+        let result = self.execute_operation(operation);
+        let duration_ms = started.elapsed().as_millis() as u64;
+        let (status, error) = match &result {
+            Ok(()) => (OperationRunStatus::Ok, None),
+            Err(CommandeurError::ExecutionInterrupted { reason }) => {
+                (OperationRunStatus::Interrupted, Some(reason.clone()))
+            }
+            Err(err) => (OperationRunStatus::Failed, Some(err.to_string())),
+        };
+        self.cells
+            .record(operation.id(), status, Some(duration_ms), error);
+        result
+    }
+
+    fn execute_operation(
+        &mut self,
+        operation: &CommandeurOperation,
+    ) -> Result<(), CommandeurError> {
+        self.checkpoint()?;
+        let resolved = interpolate_operation(operation, &self.template)
             .map_err(|err| operation_failed(operation, err))?;
-            if dest_path.exists() {
-                return Err(operation_failed(
+        let operation = &resolved;
+        let folder = self.folder;
+        let control = self.shared.control;
+        let _activity = control.enter_operation(folder, operation.id(), operation.label());
+        if let Some(comment) = operation.comment() {
+            let trimmed = comment.trim();
+            if !trimmed.is_empty() {
+                self.log(
                     operation,
-                    anyhow!("La destination existe déjà: {}", dest_path.display()),
-                ));
+                    ValidationLevel::Info,
+                    format!("[{folder}] Note: {trimmed}"),
+                );
             }
-            ensure_parent_dir(&dest_path).map_err(|err| operation_failed(operation, err))?;
-            fs::rename(&source_path, &dest_path).map_err(|err| operation_failed(operation, err))?;
-            let dest_name = dest_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("?");
-            let entry = push_log(
-                log_entries,
+        }
+
+        let expanded = expand_glob_targets(operation, &self.base_path, &Disk)
+            .map_err(|err| operation_failed(operation, err))?;
+        match expanded {
+            Some(expanded) => self.apply_expanded(operation, &expanded),
+            None => self.apply_operation(operation),
+        }
+    }
+
+    /// Applique une opération à cible générique à chaque chemin correspondant.
+    fn apply_expanded(
+        &mut self,
+        operation: &CommandeurOperation,
+        expanded: &[CommandeurOperation],
+    ) -> Result<(), CommandeurError> {
+        let folder = self.folder;
+        let pattern = glob_target(operation).unwrap_or_default();
+        if let Err(err) = check_match_count(operation, expanded.len()) {
+            self.warn(
                 operation,
-                ValidationLevel::Info,
-                format!("[{folder}] Renommé {target} -> {dest_name}"),
+                ValidationLevel::Error,
+                format!("[{folder}] Motif {pattern}: {err}"),
+                None,
             );
-            emit_event(window, LOG_EVENT, &entry);
+            return Err(operation_failed(operation, err));
         }
-        OperationDetails::Move {
-            source,
-            destination,
-            overwrite,
-        } => {
-            let source_path = resolve_in_folder(base_path, source)
-                .map_err(|err| operation_failed(operation, err))?;
-            if !source_path.exists() {
+        if expanded.is_empty() {
+            let message = format!("[{folder}] Aucun chemin ne correspond au motif {pattern}");
+            if let OperationDetails::DeleteFile { required: true, .. } = operation.details {
+                self.warn(operation, ValidationLevel::Error, message, None);
                 return Err(operation_failed(
                     operation,
-                    anyhow!("Source introuvable: {source}"),
+                    anyhow!("Suppression requise impossible: aucun chemin correspondant"),
                 ));
             }
-            let dest_path = resolve_in_folder(base_path, destination)
+            self.warn(operation, ValidationLevel::Warning, message, None);
+            return Ok(());
+        }
+        self.log(
+            operation,
+            ValidationLevel::Info,
+            format!(
+                "[{folder}] {} chemin(s) correspondant au motif {pattern}",
+                expanded.len()
+            ),
+        );
+        let mut first_error = None;
+        let mut failed = 0usize;
+        for concrete in expanded {
+            if let Err(err) = self
+                .checkpoint()
+                .and_then(|()| self.apply_operation(concrete))
+            {
+                if let CommandeurError::ExecutionInterrupted { .. } = err {
+                    return Err(err);
+                }
+                failed += 1;
+                self.warn(
+                    operation,
+                    ValidationLevel::Error,
+                    format!(
+                        "[{folder}] Échec sur {}: {err}",
+                        operation_path(concrete).unwrap_or_default()
+                    ),
+                    None,
+                );
+                first_error.get_or_insert(err);
+            }
+        }
+        self.log(
+            operation,
+            ValidationLevel::Info,
+            format!(
+                "[{folder}] Motif {pattern}: {} réussi(s), {failed} échec(s)",
+                expanded.len() - failed
+            ),
+        );
+        match first_error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Supprime un fichier ou un dossier; un dossier est vidé entrée par entrée pour que la
+    /// pause et l'arrêt restent possibles sur une grande arborescence. Un lien symbolique est
+    /// supprimé lui-même, sans toucher à sa cible.
+    fn remove_path(
+        &self,
+        operation: &CommandeurOperation,
+        path: &Path,
+    ) -> Result<(), CommandeurError> {
+        let file_type = match fs::symlink_metadata(path) {
+            Ok(metadata) => metadata.file_type(),
+            Err(_) => return Ok(()),
+        };
+        if !file_type.is_dir() {
+            return remove_entry(path, file_type).map_err(|err| operation_failed(operation, err));
+        }
+        for entry in WalkDir::new(path)
+            .follow_root_links(false)
+            .contents_first(true)
+        {
+            self.checkpoint()?;
+            let entry = entry.map_err(|err| operation_failed(operation, err))?;
+            remove_entry(entry.path(), entry.file_type())
                 .map_err(|err| operation_failed(operation, err))?;
-            ensure_parent_dir(&dest_path).map_err(|err| operation_failed(operation, err))?;
-            if dest_path.exists() {
-                if *overwrite {
-                    remove_path(&dest_path).map_err(|err| operation_failed(operation, err))?;
+        }
+        Ok(())
+    }
+
+    /// Copie par blocs avec un point de pause entre chaque bloc. Une copie interrompue est
+    /// effacée pour ne pas laisser de fichier tronqué.
+    fn copy_file(
+        &self,
+        operation: &CommandeurOperation,
+        source: &Path,
+        destination: &Path,
+    ) -> Result<(), CommandeurError> {
+        let copy = || -> Result<(), CommandeurError> {
+            let mut reader =
+                fs::File::open(source).map_err(|err| operation_failed(operation, err))?;
+            let mut writer =
+                fs::File::create(destination).map_err(|err| operation_failed(operation, err))?;
+            let mut buffer = vec![0u8; COPY_CHUNK_SIZE];
+            loop {
+                self.checkpoint()?;
+                let read = reader
+                    .read(&mut buffer)
+                    .map_err(|err| operation_failed(operation, err))?;
+                if read == 0 {
+                    break;
+                }
+                writer
+                    .write_all(&buffer[..read])
+                    .map_err(|err| operation_failed(operation, err))?;
+            }
+            let permissions = fs::metadata(source)
+                .map_err(|err| operation_failed(operation, err))?
+                .permissions();
+            fs::set_permissions(destination, permissions)
+                .map_err(|err| operation_failed(operation, err))
+        };
+        let result = copy();
+        if let Err(CommandeurError::ExecutionInterrupted { .. }) = &result {
+            let _ = fs::remove_file(destination);
+        }
+        result
+    }
+
+    fn apply_operation(&mut self, operation: &CommandeurOperation) -> Result<(), CommandeurError> {
+        let folder = self.folder;
+        let base_path = self.base_path.clone();
+        let base_path = base_path.as_path();
+        match &operation.details {
+            OperationDetails::CreateFile {
+                target,
+                overwrite,
+                content,
+            } => {
+                let target_path = resolve_in_folder(base_path, target)
+                    .map_err(|err| operation_failed(operation, err))?;
+                ensure_parent_dir(&target_path).map_err(|err| operation_failed(operation, err))?;
+                if target_path.exists() && !overwrite {
+                    self.warn(
+                        operation,
+                        ValidationLevel::Warning,
+                        format!("[{folder}] Le fichier existe déjà, création ignorée"),
+                        Some(target_path.display().to_string()),
+                    );
+                    self.log(
+                        operation,
+                        ValidationLevel::Info,
+                        format!("[{folder}] Fichier existant conservé: {target}"),
+                    );
+                } else {
+                    fs::write(&target_path, content)
+                        .map_err(|err| operation_failed(operation, err))?;
+                    self.log(
+                        operation,
+                        ValidationLevel::Info,
+                        format!("[{folder}] Fichier créé: {target}"),
+                    );
+                }
+            }
+            OperationDetails::DeleteFile { target, required } => {
+                let target_path = resolve_in_folder(base_path, target)
+                    .map_err(|err| operation_failed(operation, err))?;
+                if !target_path.exists() {
+                    let message = format!("[{folder}] Fichier à supprimer introuvable: {target}");
+                    if *required {
+                        self.warn(operation, ValidationLevel::Error, message.clone(), None);
+                        return Err(operation_failed(
+                            operation,
+                            anyhow!("Suppression requise impossible: fichier introuvable"),
+                        ));
+                    } else {
+                        self.warn(operation, ValidationLevel::Warning, message, None);
+                    }
                 } else {
+                    self.remove_path(operation, &target_path)?;
+                    self.log(
+                        operation,
+                        ValidationLevel::Info,
+                        format!("[{folder}] Fichier supprimé: {target}"),
+                    );
+                }
+            }
+            OperationDetails::Copy {
+                source,
+                destination,
+                overwrite,
+            } => {
+                let source_path = resolve_in_folder(base_path, source)
+                    .map_err(|err| operation_failed(operation, err))?;
+                if !source_path.exists() {
                     return Err(operation_failed(
                         operation,
-                        anyhow!("Destination déjà existante: {destination}"),
+                        anyhow!("Source introuvable: {}", source_path.display()),
                     ));
                 }
+                if source_path.is_dir() {
+                    return Err(operation_failed(
+                        operation,
+                        anyhow!("La copie de dossiers n'est pas supportée"),
+                    ));
+                }
+                let dest_path = resolve_in_folder(base_path, destination)
+                    .map_err(|err| operation_failed(operation, err))?;
+                ensure_parent_dir(&dest_path).map_err(|err| operation_failed(operation, err))?;
+                if dest_path.exists() {
+                    if *overwrite {
+                        ensure_replaceable(destination, false, is_real_dir(&dest_path))
+                            .map_err(|err| operation_failed(operation, err))?;
+                        self.remove_path(operation, &dest_path)?;
+                    } else {
+                        return Err(operation_failed(
+                            operation,
+                            anyhow!("La destination existe déjà: {}", dest_path.display()),
+                        ));
+                    }
+                }
+                self.copy_file(operation, &source_path, &dest_path)?;
+                self.log(
+                    operation,
+                    ValidationLevel::Info,
+                    format!(
+                        "[{folder}] Fichier copié de {} vers {}",
+                        source, destination
+                    ),
+                );
             }
-            fs::rename(&source_path, &dest_path).map_err(|err| operation_failed(operation, err))?;
-            let entry = push_log(
-                log_entries,
-                operation,
-                ValidationLevel::Info,
-                format!("[{folder}] Déplacement {source} -> {destination}"),
-            );
-            emit_event(window, LOG_EVENT, &entry);
-        }
-        OperationDetails::Mkdir {
-            target,
-            recursive,
-            skip_if_exists,
-        } => {
-            let target_path = resolve_in_folder(base_path, target)
-                .map_err(|err| operation_failed(operation, err))?;
-            if target_path.exists() {
-                if *skip_if_exists {
-                    let validation = push_folder_validation(
-                        warnings,
+            OperationDetails::Exec {
+                command,
+                args,
+                shell,
+                cwd,
+                env: custom_env,
+                timeout_seconds,
+                capture,
+            } => {
+                let mut cmd = build_command(shell, command, args)
+                    .map_err(|err| operation_failed(operation, err))?;
+                let cwd_path = if let Some(cwd_fragment) = cwd {
+                    resolve_in_folder(base_path, cwd_fragment)
+                        .map_err(|err| operation_failed(operation, err))?
+                } else {
+                    base_path.to_path_buf()
+                };
+                cmd.current_dir(&cwd_path);
+                if let Some(env_map) = custom_env {
+                    cmd.envs(env_map.iter().map(|(k, v)| (k, v)));
+                }
+                let command_line = if args.is_empty() {
+                    command.clone()
+                } else {
+                    format!("{} {}", command, args.join(" "))
+                };
+                let output = self.run_child(operation, cmd, &command_line, *timeout_seconds)?;
+                if !capture_allows_failure(capture.as_ref())
+                    && !output.status.map_or(false, |status| status.success())
+                {
+                    let stdout_raw = String::from_utf8_lossy(&output.stdout);
+                    let stderr_raw = String::from_utf8_lossy(&output.stderr);
+                    let stdout = sanitize_output(&stdout_raw);
+                    let stderr = sanitize_output(&stderr_raw);
+                    return Err(operation_failed(
+                        operation,
+                        anyhow!(
+                            "Commande échouée (code: {:?})\nSTDOUT:\n{}\nSTDERR:\n{}",
+                            output.status.and_then(|status| status.code()),
+                            stdout,
+                            stderr
+                        ),
+                    ));
+                }
+                if let Some(capture) = capture {
+                    self.apply_capture(operation, capture, &output)?;
+                }
+                self.log(
+                    operation,
+                    ValidationLevel::Info,
+                    format!("[{folder}] Commande exécutée: {command}"),
+                );
+            }
+            OperationDetails::ReplaceInFile {
+                target,
+                search,
+                replace,
+                mode,
+                flags,
+            } => {
+                let target_path = resolve_in_folder(base_path, target)
+                    .map_err(|err| operation_failed(operation, err))?;
+                if !target_path.exists() {
+                    return Err(operation_failed(
+                        operation,
+                        anyhow!("Fichier introuvable: {target}"),
+                    ));
+                }
+                let original = fs::read_to_string(&target_path)
+                    .map_err(|err| operation_failed(operation, err))?;
+                let (updated, count) = match mode {
+                    ReplaceMode::Plain => {
+                        let occurrences = original.matches(search).count();
+                        (original.replace(search, replace), occurrences)
+                    }
+                    ReplaceMode::Regex => {
+                        let regex = build_regex(search, flags.as_deref())
+                            .map_err(|err| operation_failed(operation, err))?;
+                        let count = regex.find_iter(&original).count();
+                        if count == 0 {
+                            (original.clone(), 0)
+                        } else {
+                            (
+                                regex.replace_all(&original, replace.as_str()).to_string(),
+                                count,
+                            )
+                        }
+                    }
+                };
+                if count == 0 {
+                    self.warn(
                         operation,
-                        folder,
                         ValidationLevel::Info,
-                        format!("[{folder}] Dossier déjà présent: {target}"),
+                        format!("[{folder}] Aucun remplacement pour {target}"),
                         None,
                     );
-                    emit_event(window, VALIDATION_EVENT, &validation);
-                    return Ok(());
+                } else {
+                    fs::write(&target_path, updated)
+                        .map_err(|err| operation_failed(operation, err))?;
+                    self.log(
+                        operation,
+                        ValidationLevel::Info,
+                        format!(
+                            "[{folder}] {} occurrence(s) remplacée(s) dans {target}",
+                            count
+                        ),
+                    );
                 }
             }
-            if *recursive {
-                fs::create_dir_all(&target_path).map_err(|err| operation_failed(operation, err))?;
-            } else {
-                fs::create_dir(&target_path).map_err(|err| operation_failed(operation, err))?;
-            }
-            let entry = push_log(
-                log_entries,
-                operation,
-                ValidationLevel::Info,
-                format!("[{folder}] Dossier créé: {target}"),
-            );
-            emit_event(window, LOG_EVENT, &entry);
-        }
-        OperationDetails::Python {
-            inline_script,
-            script_path,
-            entry,
-        } => {
-            let interpreter = env
-                .ensure_python()
+            OperationDetails::Rename {
+                target,
+                mode,
+                value,
+                search,
+                replace,
+            } => {
+                let source_path = resolve_in_folder(base_path, target)
+                    .map_err(|err| operation_failed(operation, err))?;
+                if !source_path.exists() {
+                    return Err(operation_failed(
+                        operation,
+                        anyhow!("Fichier ou dossier introuvable: {target}"),
+                    ));
+                }
+                let dest_path = compute_rename_destination(
+                    &source_path,
+                    mode,
+                    value,
+                    search.as_deref(),
+                    replace.as_deref(),
+                )
                 .map_err(|err| operation_failed(operation, err))?;
-            let cwd_path = base_path;
-            let mut temp_holder: Option<tempfile::NamedTempFile> = None;
-            let script_fs_path = match entry {
-                PythonEntry::Inline => {
-                    let script = inline_script.as_ref().ok_or_else(|| {
-                        operation_failed(operation, anyhow!("Script inline manquant"))
-                    })?;
-                    let mut file = tempfile::NamedTempFile::new()
-                        .map_err(|err| operation_failed(operation, err))?;
-                    file.write_all(script.as_bytes())
+                if dest_path.exists() {
+                    return Err(operation_failed(
+                        operation,
+                        anyhow!("La destination existe déjà: {}", dest_path.display()),
+                    ));
+                }
+                ensure_parent_dir(&dest_path).map_err(|err| operation_failed(operation, err))?;
+                fs::rename(&source_path, &dest_path)
+                    .map_err(|err| operation_failed(operation, err))?;
+                let dest_name = dest_path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("?");
+                self.log(
+                    operation,
+                    ValidationLevel::Info,
+                    format!("[{folder}] Renommé {target} -> {dest_name}"),
+                );
+            }
+            OperationDetails::Move {
+                source,
+                destination,
+                overwrite,
+            } => {
+                let source_path = resolve_in_folder(base_path, source)
+                    .map_err(|err| operation_failed(operation, err))?;
+                if !source_path.exists() {
+                    return Err(operation_failed(
+                        operation,
+                        anyhow!("Source introuvable: {source}"),
+                    ));
+                }
+                let dest_path = resolve_in_folder(base_path, destination)
+                    .map_err(|err| operation_failed(operation, err))?;
+                ensure_parent_dir(&dest_path).map_err(|err| operation_failed(operation, err))?;
+                if dest_path.exists() {
+                    if *overwrite {
+                        ensure_replaceable(
+                            destination,
+                            is_real_dir(&source_path),
+                            is_real_dir(&dest_path),
+                        )
                         .map_err(|err| operation_failed(operation, err))?;
-                    file.flush()
+                        self.remove_path(operation, &dest_path)?;
+                    } else {
+                        return Err(operation_failed(
+                            operation,
+                            anyhow!("Destination déjà existante: {destination}"),
+                        ));
+                    }
+                }
+                fs::rename(&source_path, &dest_path)
+                    .map_err(|err| operation_failed(operation, err))?;
+                self.log(
+                    operation,
+                    ValidationLevel::Info,
+                    format!("[{folder}] Déplacement {source} -> {destination}"),
+                );
+            }
+            OperationDetails::Mkdir {
+                target,
+                recursive,
+                skip_if_exists,
+            } => {
+                let target_path = resolve_in_folder(base_path, target)
+                    .map_err(|err| operation_failed(operation, err))?;
+                if target_path.exists() {
+                    if *skip_if_exists {
+                        self.warn(
+                            operation,
+                            ValidationLevel::Info,
+                            format!("[{folder}] Dossier déjà présent: {target}"),
+                            None,
+                        );
+                        return Ok(());
+                    }
+                }
+                if *recursive {
+                    fs::create_dir_all(&target_path)
                         .map_err(|err| operation_failed(operation, err))?;
-                    let path = file.path().to_path_buf();
-                    temp_holder = Some(file);
-                    path
-                }
-                PythonEntry::File => {
-                    let fragment = script_path.as_ref().ok_or_else(|| {
-                        operation_failed(operation, anyhow!("Chemin du script requis"))
-                    })?;
-                    resolve_in_folder(base_path, fragment)
-                        .map_err(|err| operation_failed(operation, err))?
+                } else {
+                    fs::create_dir(&target_path).map_err(|err| operation_failed(operation, err))?;
                 }
-            };
-
-            if !script_fs_path.exists() {
-                return Err(operation_failed(
+                self.log(
                     operation,
-                    anyhow!("Script Python introuvable: {}", script_fs_path.display()),
-                ));
+                    ValidationLevel::Info,
+                    format!("[{folder}] Dossier créé: {target}"),
+                );
             }
+            OperationDetails::Python {
+                inline_script,
+                script_path,
+                entry,
+                timeout_seconds,
+                capture,
+            } => {
+                let interpreter = self
+                    .shared
+                    .ensure_python()
+                    .map_err(|err| operation_failed(operation, err))?;
+                let cwd_path = base_path;
+                let mut temp_holder: Option<tempfile::NamedTempFile> = None;
+                let script_fs_path = match entry {
+                    PythonEntry::Inline => {
+                        let script = inline_script.as_ref().ok_or_else(|| {
+                            operation_failed(operation, anyhow!("Script inline manquant"))
+                        })?;
+                        let mut file = tempfile::NamedTempFile::new()
+                            .map_err(|err| operation_failed(operation, err))?;
+                        file.write_all(script.as_bytes())
+                            .map_err(|err| operation_failed(operation, err))?;
+                        file.flush()
+                            .map_err(|err| operation_failed(operation, err))?;
+                        let path = file.path().to_path_buf();
+                        temp_holder = Some(file);
+                        path
+                    }
+                    PythonEntry::File => {
+                        let fragment = script_path.as_ref().ok_or_else(|| {
+                            operation_failed(operation, anyhow!("Chemin du script requis"))
+                        })?;
+                        resolve_in_folder(base_path, fragment)
+                            .map_err(|err| operation_failed(operation, err))?
+                    }
+                };
 
-            let output = Command::new(&interpreter)
-                .arg(script_fs_path.as_os_str())
-                .current_dir(cwd_path)
-                .output()
-                .map_err(|err| operation_failed(operation, err))?;
-            drop(temp_holder);
-            if !output.status.success() {
-                let stdout_raw = String::from_utf8_lossy(&output.stdout);
-                let stderr_raw = String::from_utf8_lossy(&output.stderr);
-                let stdout = sanitize_output(&stdout_raw);
-                let stderr = sanitize_output(&stderr_raw);
-                return Err(operation_failed(
+                if !script_fs_path.exists() {
+                    return Err(operation_failed(
+                        operation,
+                        anyhow!("Script Python introuvable: {}", script_fs_path.display()),
+                    ));
+                }
+
+                let command_line = match entry {
+                    PythonEntry::Inline => format!("{interpreter} <script inline>"),
+                    PythonEntry::File => {
+                        format!(
+                            "{interpreter} {}",
+                            script_path.as_deref().unwrap_or_default()
+                        )
+                    }
+                };
+                let mut cmd = Command::new(&interpreter);
+                cmd.arg(script_fs_path.as_os_str()).current_dir(cwd_path);
+                let output = self.run_child(operation, cmd, &command_line, *timeout_seconds);
+                drop(temp_holder);
+                let output = output?;
+                if !capture_allows_failure(capture.as_ref())
+                    && !output.status.map_or(false, |status| status.success())
+                {
+                    let stdout_raw = String::from_utf8_lossy(&output.stdout);
+                    let stderr_raw = String::from_utf8_lossy(&output.stderr);
+                    let stdout = sanitize_output(&stdout_raw);
+                    let stderr = sanitize_output(&stderr_raw);
+                    return Err(operation_failed(
+                        operation,
+                        anyhow!(
+                            "Script Python échoué (code: {:?})\nSTDOUT:\n{}\nSTDERR:\n{}",
+                            output.status.and_then(|status| status.code()),
+                            stdout,
+                            stderr
+                        ),
+                    ));
+                }
+                if let Some(capture) = capture {
+                    self.apply_capture(operation, capture, &output)?;
+                }
+                self.log(
                     operation,
-                    anyhow!(
-                        "Script Python échoué (code: {:?})\nSTDOUT:\n{}\nSTDERR:\n{}",
-                        output.status.code(),
-                        stdout,
-                        stderr
-                    ),
-                ));
+                    ValidationLevel::Info,
+                    format!("[{folder}] Script Python exécuté"),
+                );
             }
-            let entry = push_log(
-                log_entries,
-                operation,
-                ValidationLevel::Info,
-                format!("[{folder}] Script Python exécuté"),
-            );
-            emit_event(window, LOG_EVENT, &entry);
-        }
-        OperationDetails::If {
-            test,
-            then,
-            else_branch,
-        } => {
-            let evaluation = evaluate_condition_for_folder(base_path, folder, test)
-                .map_err(|err| operation_failed(operation, err))?;
-            let condition = evaluation.result;
-            let iter: Box<dyn Iterator<Item = &CommandeurOperation>> = if condition {
-                Box::new(then.iter())
-            } else {
-                Box::new(else_branch.iter().flat_map(|ops| ops.iter()))
-            };
-            for child in iter {
-                if !child.enabled() {
-                    continue;
+            OperationDetails::If {
+                test,
+                then,
+                else_branch,
+            } => {
+                let evaluation = match self.decided_condition(operation) {
+                    Some(decided) => decided,
+                    None => {
+                        let captured = self.captured.clone();
+                        let mut failure: Option<CommandeurError> = None;
+                        let evaluation = {
+                            let mut run_command = |command: &ConditionCommand| {
+                                self.run_condition_command(operation, base_path, command)
+                                    .map_err(|err| {
+                                        let message = anyhow!(err.to_string());
+                                        failure = Some(err);
+                                        message
+                                    })
+                            };
+                            evaluate_condition_for_folder(
+                                base_path,
+                                folder,
+                                test,
+                                &captured,
+                                &mut run_command,
+                            )
+                        };
+                        // Un dépassement de délai ou un arrêt pendant la commande garde son
+                        // propre type.
+                        if let Some(err) = failure {
+                            return Err(err);
+                        }
+                        let evaluation =
+                            evaluation.map_err(|err| operation_failed(operation, err))?;
+                        let decided = JournalCondition {
+                            result: evaluation.result,
+                            summary: evaluation.summary,
+                        };
+                        // Consigné avant la branche pour qu'une reprise suive la même branche.
+                        self.record_condition(operation, decided.clone());
+                        decided
+                    }
+                };
+                let condition = evaluation.result;
+                self.conditions.push(RunLogCondition {
+                    operation_id: operation.id().to_string(),
+                    operation_label: operation.label().to_string(),
+                    summary: evaluation.summary.clone(),
+                    result: condition,
+                });
+                let iter: Box<dyn Iterator<Item = &CommandeurOperation>> = if condition {
+                    if let Some(else_branch) = else_branch {
+                        self.cells.branch_not_taken(else_branch);
+                    }
+                    Box::new(then.iter())
+                } else {
+                    self.cells.branch_not_taken(then);
+                    Box::new(else_branch.iter().flat_map(|ops| ops.iter()))
+                };
+                for child in iter {
+                    if !child.enabled() || self.skip_completed(child) {
+                        continue;
+                    }
+                    self.execute_recorded(child)?;
+                    self.complete_operation(child);
                 }
-                if let Err(err) = execute_operation_for_folder(
-                    workspace,
-                    child,
-                    folder,
+                self.log(
+                    operation,
+                    ValidationLevel::Info,
+                    format!(
+                        "[{folder}] Condition : {} -> {}",
+                        evaluation.summary,
+                        if condition { "then" } else { "else" }
+                    ),
+                );
+            }
+            OperationDetails::ForEach {
+                pattern,
+                items,
+                operations,
+            } => {
+                let items = loop_items(
+                    &Disk,
                     base_path,
-                    window,
-                    control,
-                    env,
-                    log_entries,
-                    warnings,
-                ) {
-                    return Err(err);
-                }
+                    pattern.as_deref(),
+                    items,
+                    glob_options(operation),
+                )
+                .map_err(|err| operation_failed(operation, err))?;
+                self.log(
+                    operation,
+                    ValidationLevel::Info,
+                    format!("[{folder}] Boucle sur {} élément(s)", items.len()),
+                );
+                let saved = self.template.saved_item();
+                self.loop_depth += 1;
+                let result = self.run_loop(&items, operations);
+                self.loop_depth -= 1;
+                self.template.restore_item(saved);
+                result?;
             }
-            let entry = push_log(
-                log_entries,
-                operation,
-                ValidationLevel::Info,
-                format!(
-                    "[{folder}] Condition : {} -> {}",
-                    evaluation.summary,
-                    if condition { "then" } else { "else" }
-                ),
-            );
-            emit_event(window, LOG_EVENT, &entry);
         }
-    }
-    Ok(())
-}
-
-fn remove_path(path: &Path) -> std::io::Result<()> {
-    if path.is_dir() {
-        fs::remove_dir_all(path)
-    } else if path.is_file() {
-        fs::remove_file(path)
-    } else {
         Ok(())
     }
 }
 
-fn build_command(
-    shell: &crate::commandeur::models::ShellKind,
-    command: &str,
-    args: &[String],
-) -> Result<Command, anyhow::Error> {
-    use crate::commandeur::models::ShellKind;
-    match shell {
-        ShellKind::Default => {
-            let mut cmd = Command::new(command);
-            cmd.args(args);
-            Ok(cmd)
-        }
-        ShellKind::Powershell => {
-            #[cfg(target_os = "windows")]
-            let shell_name = "powershell";
-            #[cfg(not(target_os = "windows"))]
-            let shell_name = "pwsh";
-            let mut cmd = Command::new(shell_name);
-            let script = join_powershell(command, args);
-            cmd.arg("-NoProfile").arg("-Command").arg(script);
-            Ok(cmd)
-        }
-        ShellKind::Bash => {
-            let mut cmd = Command::new("bash");
-            let script = if args.is_empty() {
-                command.to_string()
-            } else {
-                format!("{} {}", command, join_shell_args(args))
-            };
-            cmd.arg("-lc").arg(script);
-            Ok(cmd)
-        }
-        ShellKind::Zsh => {
-            let mut cmd = Command::new("zsh");
-            let script = if args.is_empty() {
-                command.to_string()
-            } else {
-                format!("{} {}", command, join_shell_args(args))
-            };
-            cmd.arg("-lc").arg(script);
-            Ok(cmd)
-        }
-        ShellKind::Fish => {
-            let mut cmd = Command::new("fish");
-            let script = if args.is_empty() {
-                command.to_string()
-            } else {
-                format!("{} {}", command, join_shell_args(args))
-            };
-            cmd.arg("-lc").arg(script);
-            Ok(cmd)
-        }
-    }
-}
-
-fn join_shell_args(args: &[String]) -> String {
-    args.iter()
-        .map(|arg| {
-            if arg
-                .chars()
-                .all(|c| c.is_ascii_alphanumeric() || "-_./".contains(c))
-            {
-                arg.clone()
-            } else {
-                format!("'{}'", arg.replace('\'', "'\\''"))
-            }
-        })
-        .collect::<Vec<_>>()
-        .join(" ")
-}
-
-fn join_powershell(command: &str, args: &[String]) -> String {
-    let mut parts = Vec::with_capacity(args.len() + 1);
-    parts.push(command.to_string());
-    for arg in args {
-        if arg
-            .chars()
-            .all(|c| c.is_ascii_alphanumeric() || "-_./".contains(c))
-        {
-            parts.push(arg.clone());
-        } else {
-            parts.push(format!("'{}'", arg.replace('\'', "''")));
-        }
-    }
-    parts.join(" ")
+/// Capturer le code de sortie sert justement à tester l'échec: un code non nul
+/// n'interrompt alors pas l'opération.
+fn capture_allows_failure(capture: Option<&OutputCapture>) -> bool {
+    capture.map_or(false, |capture| capture.source == CaptureSource::ExitCode)
 }
 
 fn sanitize_output(input: &str) -> String {
@@ -839,3 +2110,659 @@ fn operation_failed(
         source: err.into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commandeur::run_log::read_run_log;
+    use crate::commandeur::workspace::WorkspaceMode;
+
+    fn workspace(root: &Path, folders: &[&str]) -> WorkspaceHandle {
+        for folder in folders {
+            fs::create_dir_all(root.join(folder)).unwrap();
+        }
+        WorkspaceHandle {
+            id: new_id(),
+            mode: WorkspaceMode::Directory,
+            source_path: root.to_path_buf(),
+            root_path: root.to_path_buf(),
+            extracted: None,
+            created_at: Utc::now(),
+            sub_folders: folders.iter().map(|folder| folder.to_string()).collect(),
+            expanded_archives: Vec::new(),
+        }
+    }
+
+    /// Données de l'exécution rangées dans le dossier temporaire du test.
+    fn storage(workspace: &WorkspaceHandle) -> Storage {
+        Storage::at(workspace.root_path.join(".commandeur"))
+    }
+
+    fn execute(
+        workspace: &WorkspaceHandle,
+        workflow: serde_json::Value,
+    ) -> CommandeurExecutionResult {
+        execute_with(workspace, workflow, ExecutionControl::new())
+    }
+
+    fn execute_with(
+        workspace: &WorkspaceHandle,
+        workflow: serde_json::Value,
+        control: ExecutionControl,
+    ) -> CommandeurExecutionResult {
+        let workflow: CommandeurWorkflow = serde_json::from_value(workflow).unwrap();
+        let folders: Vec<(usize, String)> =
+            workspace.sub_folders.iter().cloned().enumerate().collect();
+        let journal = ExecutionJournal::new(
+            &new_id(),
+            workspace,
+            &workflow,
+            &CommandeurExecutionOptions::default(),
+            &folders,
+        );
+        run_workflow(
+            &storage(workspace),
+            workspace,
+            None,
+            &workflow,
+            journal,
+            control,
+        )
+        .unwrap()
+    }
+
+    fn status_of(result: &CommandeurExecutionResult, folder: &str) -> FolderRunStatus {
+        result
+            .folder_statuses
+            .iter()
+            .find(|entry| entry.folder == folder)
+            .map(|entry| entry.status)
+            .unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn deleting_a_symlinked_directory_keeps_its_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let outside = dir.path().join("hors-workspace");
+        fs::create_dir_all(outside.join("sous-dossier")).unwrap();
+        fs::write(outside.join("garde.txt"), "à conserver").unwrap();
+        fs::write(outside.join("sous-dossier/garde.txt"), "à conserver").unwrap();
+        let root = dir.path().join("rendus");
+        let workspace = workspace(&root, &["Alice"]);
+        let alice = root.join("Alice");
+        fs::create_dir_all(alice.join("build")).unwrap();
+        std::os::unix::fs::symlink(&outside, alice.join("lien")).unwrap();
+        std::os::unix::fs::symlink(&outside, alice.join("build/lien")).unwrap();
+
+        let result = execute(
+            &workspace,
+            serde_json::json!({
+                "name": "Nettoyage",
+                "version": "1.0",
+                "operations": [
+                    { "id": "lien", "label": "Lien", "kind": "delete-file", "target": "lien" },
+                    { "id": "build", "label": "Build", "kind": "delete-file", "target": "build" }
+                ]
+            }),
+        );
+
+        assert!(result.success, "{:?}", result.errors);
+        assert!(fs::symlink_metadata(alice.join("lien")).is_err());
+        assert!(!alice.join("build").exists());
+        assert!(outside.join("garde.txt").is_file());
+        assert!(outside.join("sous-dossier/garde.txt").is_file());
+    }
+
+    #[test]
+    fn overwrite_never_replaces_a_directory_with_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = workspace(dir.path(), &["Alice"]);
+        let alice = dir.path().join("Alice");
+        fs::create_dir_all(alice.join("rendu")).unwrap();
+        fs::write(alice.join("rendu/Main.java"), "class Main {}").unwrap();
+        fs::write(alice.join("notes.txt"), "notes").unwrap();
+
+        for kind in ["copy", "move"] {
+            let result = execute(
+                &workspace,
+                serde_json::json!({
+                    "name": "Remplacement",
+                    "version": "1.0",
+                    "operations": [
+                        { "id": "remplacer", "label": "Remplacer", "kind": kind,
+                          "source": "notes.txt", "destination": "rendu", "overwrite": true }
+                    ]
+                }),
+            );
+            assert!(!result.success, "{kind}");
+            assert!(
+                result.errors[0]
+                    .details
+                    .as_deref()
+                    .map_or(false, |details| details.contains("est un dossier")),
+                "{:?}",
+                result.errors
+            );
+            assert!(alice.join("rendu/Main.java").is_file());
+            assert!(alice.join("notes.txt").is_file());
+        }
+    }
+
+    #[test]
+    fn parallel_run_attributes_logs_and_cells_to_each_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let folders = ["Alice", "Bob", "Chloé", "David"];
+        let workspace = workspace(dir.path(), &folders);
+
+        let result = execute(
+            &workspace,
+            serde_json::json!({
+                "name": "Corrections",
+                "version": "1.0",
+                "maxWorkers": 3,
+                "operations": [
+                    {
+                        "id": "nom",
+                        "label": "Nom",
+                        "kind": "create-file",
+                        "target": "nom.txt",
+                        "content": "{{folder}}"
+                    },
+                    {
+                        "id": "copie",
+                        "label": "Copie",
+                        "kind": "copy",
+                        "source": "nom.txt",
+                        "destination": "copie.txt"
+                    }
+                ]
+            }),
+        );
+
+        assert!(result.success, "{:?}", result.errors);
+        assert_eq!(result.operations_run, 8);
+        for folder in folders {
+            let copy = fs::read_to_string(dir.path().join(folder).join("copie.txt")).unwrap();
+            assert_eq!(copy, folder);
+            assert_eq!(status_of(&result, folder), FolderRunStatus::Succeeded);
+            let entries: Vec<&CommandeurExecutionLogEntry> = result
+                .log_entries
+                .iter()
+                .filter(|entry| entry.folder.as_deref() == Some(folder))
+                .collect();
+            assert!(!entries.is_empty());
+            assert!(entries
+                .iter()
+                .all(|entry| entry.message.starts_with(&format!("[{folder}]"))));
+            let row = result
+                .summary
+                .folders
+                .iter()
+                .find(|row| row.folder == folder)
+                .unwrap();
+            assert!(row
+                .cells
+                .iter()
+                .all(|cell| cell.status == OperationRunStatus::Ok));
+        }
+        // Les journaux sont fusionnés dans l'ordre des dossiers, pas dans l'ordre d'arrivée.
+        let order: Vec<&str> = result
+            .log_entries
+            .iter()
+            .filter_map(|entry| entry.folder.as_deref())
+            .collect();
+        let mut sorted = order.clone();
+        sorted.sort_by_key(|folder| folders.iter().position(|f| f == folder));
+        assert_eq!(order, sorted);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn default_shell_condition_expands_globs() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = workspace(dir.path(), &["Alice", "Bob"]);
+        fs::write(dir.path().join("Alice/Main.java"), "class Main {}").unwrap();
+
+        let result = execute(
+            &workspace,
+            serde_json::json!({
+                "name": "Corrections",
+                "version": "1.0",
+                "operations": [
+                    {
+                        "id": "sources",
+                        "label": "Sources Java",
+                        "kind": "if",
+                        "test": {
+                            "selector": "command-exit-code",
+                            "command": "ls *.java | grep -q Main",
+                            "shell": "default",
+                            "value": "0"
+                        },
+                        "then": [
+                            {
+                                "id": "marquer",
+                                "label": "Marquer",
+                                "kind": "create-file",
+                                "target": "java.txt",
+                                "content": "ok"
+                            }
+                        ]
+                    }
+                ]
+            }),
+        );
+
+        assert!(result.success, "{:?}", result.errors);
+        assert!(dir.path().join("Alice/java.txt").is_file());
+        assert!(!dir.path().join("Bob/java.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn folder_names_are_quoted_in_shell_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = "O'Brien; touch pwned";
+        let workspace = workspace(dir.path(), &[folder]);
+
+        let result = execute(
+            &workspace,
+            serde_json::json!({
+                "name": "Corrections",
+                "version": "1.0",
+                "operations": [
+                    {
+                        "id": "nommer",
+                        "label": "Nommer",
+                        "kind": "exec",
+                        "command": "printf %s {{folder}} > nom.txt",
+                        "shell": "bash"
+                    }
+                ]
+            }),
+        );
+
+        assert!(result.success, "{:?}", result.errors);
+        let written = fs::read_to_string(dir.path().join(folder).join("nom.txt")).unwrap();
+        assert_eq!(written, folder);
+        assert!(!dir.path().join(folder).join("pwned").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn captures_feed_templates_and_conditions() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = workspace(dir.path(), &["Alice"]);
+        let exec = |id: &str, command: &str, capture: serde_json::Value| {
+            serde_json::json!({
+                "id": id,
+                "label": id,
+                "kind": "exec",
+                "command": command,
+                "shell": "bash",
+                "capture": capture,
+                "continueOnError": true
+            })
+        };
+
+        let result = execute(
+            &workspace,
+            serde_json::json!({
+                "name": "Corrections",
+                "version": "1.0",
+                "operations": [
+                    exec("brut", "printf '  Note: 17/20  \\n'", serde_json::json!({
+                        "name": "brut",
+                        "source": "stdout"
+                    })),
+                    exec("note", "echo 'Note: 17/20'", serde_json::json!({
+                        "name": "note",
+                        "source": "regex",
+                        "pattern": "Note: (\\d+)"
+                    })),
+                    exec("fraction", "echo 'Note: 17/20'", serde_json::json!({
+                        "name": "fraction",
+                        "source": "regex",
+                        "pattern": "\\d+/\\d+"
+                    })),
+                    exec("code", "exit 3", serde_json::json!({
+                        "name": "code",
+                        "source": "exit-code"
+                    })),
+                    exec("absent", "echo 'aucune note'", serde_json::json!({
+                        "name": "absent",
+                        "source": "regex",
+                        "pattern": "Note: (\\d+)"
+                    })),
+                    {
+                        "id": "resultat",
+                        "label": "Résultat",
+                        "kind": "create-file",
+                        "target": "resultat.txt",
+                        "content": "{{brut}}|{{note}}|{{fraction}}|{{code}}"
+                    },
+                    {
+                        "id": "reussite",
+                        "label": "Réussite",
+                        "kind": "if",
+                        "test": {
+                            "selector": "captured-value",
+                            "variable": "note",
+                            "operator": "equals",
+                            "value": "17"
+                        },
+                        "then": [
+                            {
+                                "id": "reussi",
+                                "label": "Réussi",
+                                "kind": "create-file",
+                                "target": "reussi.txt",
+                                "content": "{{note}}"
+                            }
+                        ]
+                    }
+                ]
+            }),
+        );
+
+        let alice = dir.path().join("Alice");
+        assert_eq!(
+            fs::read_to_string(alice.join("resultat.txt")).unwrap(),
+            "Note: 17/20|17|17/20|3"
+        );
+        assert_eq!(fs::read_to_string(alice.join("reussi.txt")).unwrap(), "17");
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        assert_eq!(result.errors[0].operation_id, "absent");
+        assert!(result.errors[0]
+            .details
+            .as_deref()
+            .unwrap_or_default()
+            .contains("Aucune correspondance pour la capture absent"));
+    }
+
+    #[test]
+    fn resume_skips_condition_branch_operations_already_done() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = workspace(dir.path(), &["Alice"]);
+        let alice = dir.path().join("Alice");
+        fs::write(alice.join("main.c"), "int main() {}").unwrap();
+        let workflow = serde_json::json!({
+            "name": "Corrections",
+            "version": "1.0",
+            "operations": [
+                {
+                    "id": "verifier",
+                    "label": "Vérifier",
+                    "kind": "if",
+                    "test": { "exists": "main.c" },
+                    "then": [
+                        {
+                            "id": "sauvegarder",
+                            "label": "Sauvegarder",
+                            "kind": "copy",
+                            "source": "main.c",
+                            "destination": "main.c.orig"
+                        },
+                        {
+                            "id": "nettoyer",
+                            "label": "Nettoyer",
+                            "kind": "delete-file",
+                            "target": "a.out",
+                            "required": true
+                        }
+                    ]
+                }
+            ]
+        });
+
+        let first = execute(&workspace, workflow.clone());
+        assert!(!first.success);
+        assert!(alice.join("main.c.orig").is_file());
+
+        fs::write(alice.join("a.out"), "").unwrap();
+        let storage = storage(&workspace);
+        let journal = load_journal(&storage, &first.run_id)
+            .unwrap()
+            .resumed(&new_id());
+        let workflow: CommandeurWorkflow = serde_json::from_value(workflow).unwrap();
+        let resumed = run_workflow(
+            &storage,
+            &workspace,
+            None,
+            &workflow,
+            journal,
+            ExecutionControl::new(),
+        )
+        .unwrap();
+
+        // Sans reprise fine, la copie sans écrasement échouerait sur la destination existante.
+        assert!(resumed.success, "{:?}", resumed.errors);
+        assert!(!alice.join("a.out").exists());
+        assert!(resumed
+            .log_entries
+            .iter()
+            .any(|entry| entry.operation_id == "sauvegarder"
+                && entry.message.contains("ignorée à la reprise")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resume_reuses_the_recorded_condition_result() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = workspace(dir.path(), &["Alice"]);
+        let alice = dir.path().join("Alice");
+        let workflow = serde_json::json!({
+            "name": "Corrections",
+            "version": "1.0",
+            "operations": [
+                {
+                    "id": "premiere",
+                    "label": "Première correction",
+                    "kind": "if",
+                    "test": {
+                        "selector": "command-exit-code",
+                        "command": "test ! -f marque && touch marque",
+                        "shell": "bash",
+                        "value": "0"
+                    },
+                    "then": [
+                        {
+                            "id": "nettoyer",
+                            "label": "Nettoyer",
+                            "kind": "delete-file",
+                            "target": "a.out",
+                            "required": true
+                        }
+                    ]
+                }
+            ]
+        });
+
+        let first = execute(&workspace, workflow.clone());
+        assert!(!first.success);
+        assert!(alice.join("marque").is_file());
+
+        // Réévaluée, la condition serait fausse puisque la commande a laissé sa marque.
+        fs::write(alice.join("a.out"), "").unwrap();
+        let storage = storage(&workspace);
+        let journal = load_journal(&storage, &first.run_id)
+            .unwrap()
+            .resumed(&new_id());
+        let workflow: CommandeurWorkflow = serde_json::from_value(workflow).unwrap();
+        let resumed = run_workflow(
+            &storage,
+            &workspace,
+            None,
+            &workflow,
+            journal,
+            ExecutionControl::new(),
+        )
+        .unwrap();
+
+        assert!(resumed.success, "{:?}", resumed.errors);
+        assert!(!alice.join("a.out").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn history_counts_failed_folders_as_they_finish() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = workspace(dir.path(), &["Alice", "Bob"]);
+        fs::write(dir.path().join("Bob/a.out"), "").unwrap();
+
+        let result = execute(
+            &workspace,
+            serde_json::json!({
+                "name": "Corrections",
+                "version": "1.0",
+                "operations": [
+                    {
+                        "id": "nettoyer",
+                        "label": "Nettoyer",
+                        "kind": "delete-file",
+                        "target": "a.out",
+                        "required": true,
+                        "continueOnError": true
+                    },
+                    {
+                        "id": "historique",
+                        "label": "Historique",
+                        "kind": "exec",
+                        "command": "cp {{workspaceRoot}}/.commandeur/logs/history.json historique.json",
+                        "shell": "bash"
+                    }
+                ]
+            }),
+        );
+
+        assert_eq!(status_of(&result, "Alice"), FolderRunStatus::Failed);
+        let seen = |folder: &str| -> Vec<RunHistoryEntry> {
+            let content = fs::read_to_string(dir.path().join(folder).join("historique.json"));
+            serde_json::from_str(&content.unwrap()).unwrap()
+        };
+        assert_eq!(seen("Alice")[0].failed_folder_count, 0);
+        assert_eq!(seen("Bob")[0].failed_folder_count, 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parallel_halt_marks_abandoned_folders_as_interrupted() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = workspace(dir.path(), &["Alice", "Bob", "Chloé"]);
+        fs::write(dir.path().join("Alice/rendu.txt"), "").unwrap();
+        fs::write(dir.path().join("Chloé/rendu.txt"), "").unwrap();
+
+        let result = execute(
+            &workspace,
+            serde_json::json!({
+                "name": "Corrections",
+                "version": "1.0",
+                "maxWorkers": 3,
+                "operations": [
+                    {
+                        "id": "verifier",
+                        "label": "Vérifier",
+                        "kind": "exec",
+                        "command": "test -f rendu.txt || exit 1; sleep 1",
+                        "shell": "bash"
+                    },
+                    {
+                        "id": "noter",
+                        "label": "Noter",
+                        "kind": "create-file",
+                        "target": "note.txt",
+                        "content": "ok"
+                    }
+                ]
+            }),
+        );
+
+        assert!(!result.success);
+        assert_eq!(status_of(&result, "Bob"), FolderRunStatus::Failed);
+        for folder in ["Alice", "Chloé"] {
+            let status = status_of(&result, folder);
+            assert!(
+                matches!(
+                    status,
+                    FolderRunStatus::Interrupted | FolderRunStatus::Skipped
+                ),
+                "{folder}: {status:?}"
+            );
+            assert!(!dir.path().join(folder).join("note.txt").exists());
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stop_reaches_every_parallel_worker() {
+        let dir = tempfile::tempdir().unwrap();
+        let folders = ["Alice", "Bob", "Chloé"];
+        let workspace = workspace(dir.path(), &folders);
+        let control = ExecutionControl::new();
+        let stopper = control.clone();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(500));
+            stopper.request_stop(Some("Arrêt demandé".into()));
+        });
+
+        let started = Instant::now();
+        let result = execute_with(
+            &workspace,
+            serde_json::json!({
+                "name": "Corrections",
+                "version": "1.0",
+                "maxWorkers": 3,
+                "operations": [
+                    {
+                        "id": "attendre",
+                        "label": "Attendre",
+                        "kind": "exec",
+                        "command": "sleep",
+                        "args": ["10"],
+                        "shell": "default"
+                    }
+                ]
+            }),
+            control,
+        );
+        handle.join().unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(8));
+        assert!(!result.success);
+        for folder in folders {
+            assert_ne!(status_of(&result, folder), FolderRunStatus::Succeeded);
+        }
+    }
+
+    #[test]
+    fn log_files_include_the_final_warnings() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = workspace(dir.path(), &["Alice"]);
+        let control = ExecutionControl::new();
+        control.request_stop(Some("Arrêt demandé".into()));
+
+        let result = execute_with(
+            &workspace,
+            serde_json::json!({
+                "name": "Corrections",
+                "version": "1.0",
+                "operations": [
+                    { "id": "note", "label": "Note", "kind": "create-file",
+                      "target": "note.txt", "content": "ok" }
+                ]
+            }),
+            control,
+        );
+
+        let warning = "Exécution interrompue · Arrêt demandé";
+        assert!(result
+            .log_entries
+            .iter()
+            .any(|entry| entry.message == warning));
+        let text_log = fs::read_to_string(&result.log_file_path).unwrap();
+        assert!(text_log.contains(warning), "{text_log}");
+        let run_log = read_run_log(Path::new(&result.run_directory)).unwrap();
+        assert!(run_log.entries.iter().any(|entry| entry.message == warning));
+    }
+}
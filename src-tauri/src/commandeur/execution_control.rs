@@ -11,10 +11,27 @@ pub enum ExecutionStatus {
     Stopping,
 }
 
+/// Opération en cours dans un dossier, pour situer une pause ou un arrêt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionActivity {
+    pub folder: String,
+    pub operation_id: String,
+    pub operation_label: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionStatusReport {
+    pub status: ExecutionStatus,
+    pub activities: Vec<ExecutionActivity>,
+}
+
 #[derive(Debug)]
 struct ExecutionState {
     status: ExecutionStatus,
     stop_reason: Option<String>,
+    activities: Vec<ExecutionActivity>,
 }
 
 struct ExecutionInner {
@@ -39,6 +56,7 @@ impl ExecutionControl {
                 state: Mutex::new(ExecutionState {
                     status: ExecutionStatus::Running,
                     stop_reason: None,
+                    activities: Vec::new(),
                 }),
                 notify: Condvar::new(),
             }),
@@ -50,6 +68,50 @@ impl ExecutionControl {
         guard.status
     }
 
+    pub fn report(&self) -> ExecutionStatusReport {
+        let guard = self.inner.state.lock().expect("execution status poisoned");
+        ExecutionStatusReport {
+            status: guard.status,
+            activities: guard.activities.clone(),
+        }
+    }
+
+    /// Déclare l'opération en cours pour `folder` jusqu'à la fin du garde; une opération
+    /// imbriquée remplace celle qui la contient, rétablie ensuite.
+    pub fn enter_operation(
+        &self,
+        folder: &str,
+        operation_id: &str,
+        operation_label: &str,
+    ) -> ActivityGuard<'_> {
+        let activity = ExecutionActivity {
+            folder: folder.to_string(),
+            operation_id: operation_id.to_string(),
+            operation_label: operation_label.to_string(),
+        };
+        let mut guard = self
+            .inner
+            .state
+            .lock()
+            .expect("execution activity poisoned");
+        let previous = match guard
+            .activities
+            .iter_mut()
+            .find(|current| current.folder == folder)
+        {
+            Some(current) => Some(std::mem::replace(current, activity)),
+            None => {
+                guard.activities.push(activity);
+                None
+            }
+        };
+        ActivityGuard {
+            control: self,
+            folder: folder.to_string(),
+            previous,
+        }
+    }
+
     pub fn request_pause(&self) {
         let mut guard = self.inner.state.lock().expect("execution pause poisoned");
         if guard.status == ExecutionStatus::Running {
@@ -72,6 +134,20 @@ impl ExecutionControl {
         self.inner.notify.notify_all();
     }
 
+    /// Variante non bloquante de `checkpoint`, utilisée pendant qu'un processus enfant tourne.
+    pub fn stop_requested(&self) -> Option<String> {
+        let guard = self.inner.state.lock().expect("execution stop poisoned");
+        match guard.status {
+            ExecutionStatus::Stopping => Some(
+                guard
+                    .stop_reason
+                    .clone()
+                    .unwrap_or_else(|| "Exécution interrompue".to_string()),
+            ),
+            _ => None,
+        }
+    }
+
     pub fn checkpoint(&self) -> Result<(), ExecutionInterrupt> {
         let mut guard = self
             .inner
@@ -108,6 +184,61 @@ impl ExecutionControl {
         let mut guard = self.inner.state.lock().expect("execution finish poisoned");
         guard.status = ExecutionStatus::Idle;
         guard.stop_reason = None;
+        guard.activities.clear();
         self.inner.notify.notify_all();
     }
 }
+
+pub struct ActivityGuard<'a> {
+    control: &'a ExecutionControl,
+    folder: String,
+    previous: Option<ExecutionActivity>,
+}
+
+impl Drop for ActivityGuard<'_> {
+    fn drop(&mut self) {
+        let mut guard = match self.control.inner.state.lock() {
+            Ok(guard) => guard,
+            Err(_) => return,
+        };
+        let position = guard
+            .activities
+            .iter()
+            .position(|current| current.folder == self.folder);
+        match (position, self.previous.take()) {
+            (Some(position), Some(previous)) => guard.activities[position] = previous,
+            (Some(position), None) => {
+                guard.activities.remove(position);
+            }
+            (None, _) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_operations_restore_the_enclosing_activity() {
+        let control = ExecutionControl::new();
+        let labels = |control: &ExecutionControl| -> Vec<String> {
+            control
+                .report()
+                .activities
+                .into_iter()
+                .map(|activity| format!("{}:{}", activity.folder, activity.operation_label))
+                .collect()
+        };
+        {
+            let _loop = control.enter_operation("Alice", "op-1", "Boucle");
+            let _other = control.enter_operation("Bob", "op-2", "Copie");
+            {
+                let _child = control.enter_operation("Alice", "op-3", "Remplacement");
+                assert_eq!(labels(&control), vec!["Alice:Remplacement", "Bob:Copie"]);
+            }
+            assert_eq!(labels(&control), vec!["Alice:Boucle", "Bob:Copie"]);
+        }
+        assert!(control.report().activities.is_empty());
+    }
+}
@@ -0,0 +1,583 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{anyhow, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use walkdir::WalkDir;
+
+use crate::commandeur::models::{CommandeurOperation, OperationDetails};
+use crate::commandeur::workspace::resolve_in_folder;
+
+const GLOB_CHARS: [char; 4] = ['*', '?', '[', '{'];
+
+/// Contenu des dossiers tel que les opérations le voient: le disque à l'exécution, l'état
+/// simulé pendant une simulation.
+pub trait FolderView {
+    fn exists(&self, path: &Path) -> bool;
+    fn is_file(&self, path: &Path) -> bool;
+    fn read(&self, path: &Path) -> Result<Vec<u8>>;
+    fn size(&self, path: &Path) -> Result<u64>;
+    fn modified(&self, path: &Path) -> Result<SystemTime>;
+    /// Chemins correspondant au motif, triés (voir `find_matches`).
+    fn find(&self, base_path: &Path, pattern: &str, options: GlobOptions) -> Result<Vec<PathBuf>>;
+}
+
+pub struct Disk;
+
+impl FolderView for Disk {
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>> {
+        Ok(fs::read(path)?)
+    }
+
+    fn size(&self, path: &Path) -> Result<u64> {
+        Ok(fs::metadata(path)?.len())
+    }
+
+    fn modified(&self, path: &Path) -> Result<SystemTime> {
+        Ok(fs::metadata(path)?.modified()?)
+    }
+
+    fn find(&self, base_path: &Path, pattern: &str, options: GlobOptions) -> Result<Vec<PathBuf>> {
+        find_matches(base_path, pattern, options)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlobOptions {
+    /// Un motif sans `/` est cherché à toutes les profondeurs (`*.class` vaut `**/*.class`).
+    pub recursive: bool,
+    pub case_insensitive: bool,
+}
+
+/// Motif de fichiers: plusieurs globs séparés par `;` ou des retours à la ligne, ceux
+/// commençant par `!` excluent les chemins correspondants (et le contenu des dossiers exclus).
+pub struct FileMatcher {
+    include: GlobSet,
+    exclude: GlobSet,
+    max_depth: usize,
+}
+
+impl FileMatcher {
+    pub fn new(pattern: &str, options: GlobOptions) -> Result<Self> {
+        let mut include = GlobSetBuilder::new();
+        let mut exclude = GlobSetBuilder::new();
+        let mut includes = 0usize;
+        let mut max_depth = 1usize;
+        for raw in split_patterns(pattern) {
+            let (excluded, body) = match raw.strip_prefix('!') {
+                Some(body) => (true, body.trim()),
+                None => (false, raw),
+            };
+            let body = normalize_pattern(body)?;
+            let body = if options.recursive && !body.contains('/') {
+                format!("**/{body}")
+            } else {
+                body
+            };
+            let glob = GlobBuilder::new(&body)
+                .literal_separator(true)
+                .backslash_escape(false)
+                .case_insensitive(options.case_insensitive)
+                .build()
+                .map_err(|err| anyhow!("Motif invalide \"{raw}\": {err}"))?;
+            if excluded {
+                exclude.add(glob);
+            } else {
+                includes += 1;
+                max_depth = if body.contains("**") {
+                    usize::MAX
+                } else {
+                    max_depth.max(body.split('/').count())
+                };
+                include.add(glob);
+            }
+        }
+        if includes == 0 {
+            return Err(anyhow!("Motif sans chemin à inclure: {pattern}"));
+        }
+        Ok(Self {
+            include: include.build()?,
+            exclude: exclude.build()?,
+            max_depth,
+        })
+    }
+
+    /// Chemin relatif (séparateurs `/`) inclus et non exclu.
+    pub fn is_match(&self, relative: &str) -> bool {
+        self.include.is_match(relative) && !self.exclude.is_match(relative)
+    }
+
+    /// Comme `find`: inclus, sans que le chemin ni l'un de ses dossiers parents soit exclu.
+    fn selects(&self, relative: &str) -> bool {
+        self.include.is_match(relative)
+            && !relative
+                .match_indices('/')
+                .map(|(end, _)| &relative[..end])
+                .chain([relative])
+                .any(|prefix| self.exclude.is_match(prefix))
+    }
+
+    /// Fichiers et dossiers de `base_path` correspondant au motif, triés par chemin.
+    pub fn find(&self, base_path: &Path) -> Result<Vec<PathBuf>> {
+        let mut matches = Vec::new();
+        let mut iterator = WalkDir::new(base_path)
+            .follow_links(false)
+            .max_depth(self.max_depth)
+            .sort_by_file_name()
+            .into_iter();
+        while let Some(entry) = iterator.next() {
+            let entry = entry.map_err(|err| anyhow!(err))?;
+            if entry.depth() == 0 {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(base_path)
+                .map_err(|err| anyhow!(err))?;
+            let rel_str = path_to_forward_string(rel);
+            if self.exclude.is_match(&rel_str) {
+                if entry.file_type().is_dir() {
+                    iterator.skip_current_dir();
+                }
+                continue;
+            }
+            if self.include.is_match(&rel_str) {
+                matches.push(entry.into_path());
+            }
+        }
+        Ok(matches)
+    }
+}
+
+pub fn is_glob_pattern(pattern: &str) -> bool {
+    pattern.contains(GLOB_CHARS)
+        || pattern.contains(';')
+        || split_patterns(pattern).any(|part| part.starts_with('!'))
+}
+
+/// Chemins correspondant au motif. Un chemin sans caractère générique, ou qui existe tel quel
+/// (ex. `rapport [v2].pdf`), est résolu directement, sauf s'il faut ignorer la casse et qu'il
+/// n'existe pas sous cette forme.
+pub fn find_matches(base_path: &Path, pattern: &str, options: GlobOptions) -> Result<Vec<PathBuf>> {
+    let literal = resolve_in_folder(base_path, pattern).ok();
+    if literal.as_ref().map_or(false, |path| path.exists())
+        || (!is_glob_pattern(pattern) && !options.case_insensitive)
+    {
+        let target = match literal {
+            Some(target) => target,
+            None => resolve_in_folder(base_path, pattern)?,
+        };
+        return Ok(if target.exists() {
+            vec![target]
+        } else {
+            Vec::new()
+        });
+    }
+    FileMatcher::new(pattern, options)?.find(base_path)
+}
+
+/// Comme `find_matches`, parmi les chemins `entries` (sous `base_path`) d'une vue simulée.
+pub fn select_matches(
+    view: &dyn FolderView,
+    base_path: &Path,
+    pattern: &str,
+    options: GlobOptions,
+    entries: impl IntoIterator<Item = PathBuf>,
+) -> Result<Vec<PathBuf>> {
+    let literal = resolve_in_folder(base_path, pattern).ok();
+    if let Some(literal) = literal.filter(|path| view.exists(path)) {
+        return Ok(vec![literal]);
+    }
+    if !is_glob_pattern(pattern) && !options.case_insensitive {
+        resolve_in_folder(base_path, pattern)?;
+        return Ok(Vec::new());
+    }
+    let matcher = FileMatcher::new(pattern, options)?;
+    let mut matches: Vec<PathBuf> = entries
+        .into_iter()
+        .filter(|path| {
+            path.strip_prefix(base_path).map_or(false, |relative| {
+                !relative.as_os_str().is_empty()
+                    && matcher.selects(&path_to_forward_string(relative))
+            })
+        })
+        .collect();
+    matches.sort();
+    matches.dedup();
+    Ok(matches)
+}
+
+fn split_patterns(pattern: &str) -> impl Iterator<Item = &str> {
+    pattern
+        .split([';', '\n'])
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+}
+
+fn normalize_pattern(pattern: &str) -> Result<String> {
+    let normalized = pattern.replace('\\', "/");
+    let trimmed = normalized.trim_start_matches("./");
+    if trimmed.starts_with('/') || trimmed.split('/').any(|segment| segment == "..") {
+        return Err(anyhow!(
+            "Le motif doit rester dans le dossier étudiant: {pattern}"
+        ));
+    }
+    Ok(trimmed.trim_end_matches('/').to_string())
+}
+
+/// Segments de tête du premier motif inclus qui ne contiennent aucun caractère générique,
+/// ex. `src` pour `src/**/*.java`.
+pub fn literal_prefix(pattern: &str) -> PathBuf {
+    let first = split_patterns(pattern)
+        .find(|part| !part.starts_with('!'))
+        .unwrap_or_default()
+        .replace('\\', "/");
+    first
+        .split('/')
+        .take_while(|segment| !segment.contains(GLOB_CHARS) && !segment.is_empty())
+        .collect()
+}
+
+pub fn path_to_forward_string(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Chemin visé par une opération acceptant un motif: la cible, ou la source d'une copie ou
+/// d'un déplacement.
+pub fn operation_path(operation: &CommandeurOperation) -> Option<&str> {
+    match &operation.details {
+        OperationDetails::DeleteFile { target, .. }
+        | OperationDetails::ReplaceInFile { target, .. }
+        | OperationDetails::Rename { target, .. } => Some(target),
+        OperationDetails::Copy { source, .. } | OperationDetails::Move { source, .. } => {
+            Some(source)
+        }
+        _ => None,
+    }
+}
+
+/// Motif complet de l'opération (chemin visé, inclusions et exclusions supplémentaires)
+/// lorsqu'elle doit être appliquée chemin par chemin.
+pub fn glob_target(operation: &CommandeurOperation) -> Option<String> {
+    let path = operation_path(operation)?;
+    let matching = operation
+        .meta
+        .matching
+        .as_ref()
+        .filter(|matching| !matching.is_empty());
+    if matching.is_none() && !is_glob_pattern(path) {
+        return None;
+    }
+    let mut parts = vec![path.trim().to_string()];
+    if let Some(matching) = matching {
+        let trimmed = |pattern: &String| pattern.trim().to_string();
+        parts.extend(matching.include.iter().map(trimmed));
+        parts.extend(
+            matching
+                .exclude
+                .iter()
+                .map(trimmed)
+                .filter(|pattern| !pattern.is_empty())
+                .map(|pattern| format!("!{pattern}")),
+        );
+    }
+    parts.retain(|part| !part.is_empty());
+    Some(parts.join(";"))
+}
+
+/// Options de recherche des chemins ciblés par l'opération.
+pub fn glob_options(operation: &CommandeurOperation) -> GlobOptions {
+    GlobOptions {
+        recursive: false,
+        case_insensitive: operation
+            .meta
+            .matching
+            .as_ref()
+            .map_or(false, |matching| matching.case_insensitive),
+    }
+}
+
+/// Vérifie le nombre de chemins correspondants attendu par l'opération (`minMatches`,
+/// `maxMatches`).
+pub fn check_match_count(operation: &CommandeurOperation, count: usize) -> Result<()> {
+    let matching = match &operation.meta.matching {
+        Some(matching) => matching,
+        None => return Ok(()),
+    };
+    if let Some(min) = matching.min_matches {
+        if count < min {
+            return Err(anyhow!(
+                "{count} chemin(s) correspondant(s), au moins {min} attendu(s)"
+            ));
+        }
+    }
+    if let Some(max) = matching.max_matches {
+        if count > max {
+            return Err(anyhow!(
+                "{count} chemin(s) correspondant(s), au plus {max} attendu(s)"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Remplace une opération à cible générique par une opération par chemin correspondant.
+/// Les chemins contenus dans un dossier déjà retenu sont ignorés, et `ReplaceInFile` ne
+/// garde que les fichiers. Une copie ou un déplacement conserve l'arborescence sous le
+/// préfixe fixe du motif; une source littérale (bornée par `minMatches`/`maxMatches`)
+/// garde sa destination telle quelle.
+pub fn expand_glob_targets(
+    operation: &CommandeurOperation,
+    base_path: &Path,
+    view: &dyn FolderView,
+) -> Result<Option<Vec<CommandeurOperation>>> {
+    let pattern = match glob_target(operation) {
+        Some(pattern) => pattern,
+        None => return Ok(None),
+    };
+    let files_only = matches!(operation.details, OperationDetails::ReplaceInFile { .. });
+    let mut selected: Vec<PathBuf> = Vec::new();
+    for path in view.find(base_path, &pattern, glob_options(operation))? {
+        if files_only && !view.is_file(&path) {
+            continue;
+        }
+        if selected.iter().any(|parent| path.starts_with(parent)) {
+            continue;
+        }
+        selected.push(path);
+    }
+
+    let prefix = base_path.join(literal_prefix(&pattern));
+    let literal_source = operation_path(operation).map_or(false, |path| !is_glob_pattern(path))
+        && operation.meta.matching.as_ref().map_or(true, |matching| {
+            matching
+                .include
+                .iter()
+                .all(|include| include.trim().is_empty())
+        });
+    let mut expanded = Vec::with_capacity(selected.len());
+    for path in selected {
+        let relative = path_to_forward_string(path.strip_prefix(base_path)?);
+        let mut concrete = operation.clone();
+        concrete.meta.matching = None;
+        match &mut concrete.details {
+            OperationDetails::DeleteFile { target, .. }
+            | OperationDetails::ReplaceInFile { target, .. }
+            | OperationDetails::Rename { target, .. } => *target = relative,
+            OperationDetails::Copy {
+                source,
+                destination,
+                ..
+            }
+            | OperationDetails::Move {
+                source,
+                destination,
+                ..
+            } if !literal_source => {
+                let below_prefix = path
+                    .strip_prefix(&prefix)
+                    .ok()
+                    .filter(|rest| !rest.as_os_str().is_empty())
+                    .map(path_to_forward_string)
+                    .unwrap_or_else(|| relative.clone());
+                *destination = format!("{}/{}", destination.trim_end_matches('/'), below_prefix);
+                *source = relative;
+            }
+            OperationDetails::Copy { source, .. } | OperationDetails::Move { source, .. } => {
+                *source = relative;
+            }
+            _ => {}
+        }
+        expanded.push(concrete);
+    }
+    Ok(Some(expanded))
+}
+
+/// Éléments d'une boucle: la liste fixe, puis les chemins relatifs correspondant au motif.
+pub fn loop_items(
+    view: &dyn FolderView,
+    base_path: &Path,
+    pattern: Option<&str>,
+    items: &[String],
+    options: GlobOptions,
+) -> Result<Vec<String>> {
+    let mut values: Vec<String> = items
+        .iter()
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect();
+    if let Some(pattern) = pattern.map(str::trim).filter(|pattern| !pattern.is_empty()) {
+        for path in view.find(base_path, pattern, options)? {
+            values.push(path_to_forward_string(path.strip_prefix(base_path)?));
+        }
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn matcher_supports_alternation_classes_and_exclusions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/app")).unwrap();
+        fs::create_dir_all(root.join("node_modules/lib")).unwrap();
+        for file in [
+            "Main.java",
+            "src/app/App.java",
+            "src/app/App.class",
+            "src/app/Test1.kt",
+            "node_modules/lib/Lib.java",
+        ] {
+            fs::write(root.join(file), "").unwrap();
+        }
+
+        let relative = |pattern: &str, options: GlobOptions| -> Vec<String> {
+            find_matches(root, pattern, options)
+                .unwrap()
+                .iter()
+                .map(|path| path_to_forward_string(path.strip_prefix(root).unwrap()))
+                .collect()
+        };
+
+        assert_eq!(
+            relative("**/*.{java,kt}; !node_modules", GlobOptions::default()),
+            vec!["Main.java", "src/app/App.java", "src/app/Test1.kt"]
+        );
+        assert_eq!(
+            relative("*.java", GlobOptions::default()),
+            vec!["Main.java"]
+        );
+        assert_eq!(
+            relative(
+                "test[0-9].KT",
+                GlobOptions {
+                    recursive: true,
+                    case_insensitive: true
+                }
+            ),
+            vec!["src/app/Test1.kt"]
+        );
+        assert!(find_matches(root, "../*.java", GlobOptions::default()).is_err());
+        assert_eq!(
+            literal_prefix("src/app/**/*.java"),
+            PathBuf::from("src/app")
+        );
+    }
+
+    #[test]
+    fn move_expands_with_exclusions_and_checks_bounds() {
+        use crate::commandeur::models::{OperationMeta, TargetMatching};
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("out/classes/app")).unwrap();
+        for file in [
+            "out/classes/app/A.class",
+            "out/classes/app/B.class",
+            "out/keep.class",
+        ] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        let operation = CommandeurOperation {
+            meta: OperationMeta {
+                id: "move".into(),
+                label: "Déplacer".into(),
+                comment: None,
+                enabled: true,
+                continue_on_error: false,
+                matching: Some(TargetMatching {
+                    include: Vec::new(),
+                    exclude: vec!["out/keep.class".into()],
+                    min_matches: Some(1),
+                    max_matches: Some(2),
+                    case_insensitive: false,
+                }),
+            },
+            details: OperationDetails::Move {
+                source: "out/**/*.class".into(),
+                destination: "archive".into(),
+                overwrite: false,
+            },
+        };
+
+        let expanded = expand_glob_targets(&operation, root, &Disk)
+            .unwrap()
+            .unwrap();
+        let moves: Vec<(String, String)> = expanded
+            .iter()
+            .map(|concrete| match &concrete.details {
+                OperationDetails::Move {
+                    source,
+                    destination,
+                    ..
+                } => (source.clone(), destination.clone()),
+                other => panic!("opération inattendue: {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            moves,
+            vec![
+                (
+                    "out/classes/app/A.class".to_string(),
+                    "archive/classes/app/A.class".to_string()
+                ),
+                (
+                    "out/classes/app/B.class".to_string(),
+                    "archive/classes/app/B.class".to_string()
+                ),
+            ]
+        );
+        assert!(expanded
+            .iter()
+            .all(|concrete| concrete.meta.matching.is_none()));
+        assert!(check_match_count(&operation, expanded.len()).is_ok());
+        assert!(check_match_count(&operation, 0).is_err());
+        assert!(check_match_count(&operation, 3).is_err());
+
+        fs::write(root.join("a.txt"), "").unwrap();
+        let literal = CommandeurOperation {
+            meta: OperationMeta {
+                matching: Some(TargetMatching {
+                    include: Vec::new(),
+                    exclude: Vec::new(),
+                    min_matches: Some(1),
+                    max_matches: None,
+                    case_insensitive: true,
+                }),
+                ..operation.meta.clone()
+            },
+            details: OperationDetails::Copy {
+                source: "A.TXT".into(),
+                destination: "b.txt".into(),
+                overwrite: false,
+            },
+        };
+        let expanded = expand_glob_targets(&literal, root, &Disk).unwrap().unwrap();
+        assert_eq!(expanded.len(), 1);
+        match &expanded[0].details {
+            OperationDetails::Copy {
+                source,
+                destination,
+                ..
+            } => assert_eq!((source.as_str(), destination.as_str()), ("a.txt", "b.txt")),
+            other => panic!("opération inattendue: {other:?}"),
+        }
+    }
+}
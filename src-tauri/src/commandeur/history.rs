@@ -0,0 +1,274 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, FixedOffset};
+
+use crate::commandeur::models::{
+    CommandeurExecutionResult, FolderRunStatus, RunHistoryEntry, RunHistoryFilter,
+};
+use crate::commandeur::storage::Storage;
+
+const HISTORY_FILE: &str = "history.json";
+const RESULT_FILE: &str = "result.json";
+
+/// Enregistre le résultat complet dans le dossier de l'exécution et l'ajoute à l'index.
+pub fn record_run(
+    storage: &Storage,
+    entry: &RunHistoryEntry,
+    result: &CommandeurExecutionResult,
+) -> Result<()> {
+    fs::write(
+        Path::new(&entry.run_directory).join(RESULT_FILE),
+        serde_json::to_vec_pretty(result)?,
+    )?;
+    record_history_entry(storage, entry)
+}
+
+/// Ajoute ou remplace l'entrée dans l'index. Une exécution est indexée dès son démarrage
+/// comme interrompue, pour rester reprenable si l'application s'arrête en cours de route.
+pub fn record_history_entry(storage: &Storage, entry: &RunHistoryEntry) -> Result<()> {
+    let path = history_path(storage)?;
+    let mut entries = read_history(&path)?;
+    entries.retain(|existing| existing.run_id != entry.run_id);
+    entries.push(entry.clone());
+    write_history(&path, &entries)
+}
+
+pub fn run_status(success: bool, interrupted: bool) -> FolderRunStatus {
+    if interrupted {
+        FolderRunStatus::Interrupted
+    } else if success {
+        FolderRunStatus::Succeeded
+    } else {
+        FolderRunStatus::Failed
+    }
+}
+
+/// Exécutions passées correspondant au filtre, de la plus récente à la plus ancienne.
+pub fn list_runs(storage: &Storage, filter: &RunHistoryFilter) -> Result<Vec<RunHistoryEntry>> {
+    let mut entries = read_history(&history_path(storage)?)?;
+    entries.retain(|entry| matches_filter(entry, filter));
+    entries.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    Ok(entries)
+}
+
+pub fn load_run_result(storage: &Storage, run_id: &str) -> Result<CommandeurExecutionResult> {
+    let path = storage.existing_run_dir(run_id)?.join(RESULT_FILE);
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Aucun résultat enregistré pour l'exécution {run_id}"))?;
+    serde_json::from_str(&content).context("Résultat d'exécution illisible")
+}
+
+/// Supprime l'exécution de l'index ainsi que son dossier, son journal texte et son instantané.
+pub fn delete_run(storage: &Storage, run_id: &str) -> Result<()> {
+    let path = history_path(storage)?;
+    let mut entries = read_history(&path)?;
+    let position = entries
+        .iter()
+        .position(|entry| entry.run_id == run_id)
+        .ok_or_else(|| anyhow!("Exécution introuvable dans l'historique: {run_id}"))?;
+    let entry = entries.remove(position);
+
+    let logs_dir = storage.logs_dir()?;
+    let snapshots_dir = storage.snapshots_dir()?;
+    let owned_paths = [
+        Some(entry.run_directory.as_str()),
+        Some(entry.log_file_path.as_str()),
+        entry.snapshot_path.as_deref(),
+    ];
+    for owned in owned_paths.iter().flatten() {
+        let owned = Path::new(owned);
+        // Seuls les fichiers produits par Commandeur sont supprimés, jamais le workspace.
+        if !(owned.starts_with(&logs_dir) || owned.starts_with(&snapshots_dir)) {
+            continue;
+        }
+        if owned.is_dir() {
+            fs::remove_dir_all(owned)?;
+        } else if owned.is_file() {
+            fs::remove_file(owned)?;
+        }
+    }
+    write_history(&path, &entries)
+}
+
+/// Ouvre le dossier de l'exécution dans le gestionnaire de fichiers du système.
+pub fn open_run_directory(storage: &Storage, run_id: &str) -> Result<()> {
+    open_path(&storage.existing_run_dir(run_id)?)
+}
+
+/// Ouvre un fichier ou un dossier avec l'application par défaut du système.
+pub fn open_path(path: &Path) -> Result<()> {
+    #[cfg(target_os = "windows")]
+    let opener = "explorer";
+    #[cfg(target_os = "macos")]
+    let opener = "open";
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let opener = "xdg-open";
+    Command::new(opener)
+        .arg(path)
+        .spawn()
+        .with_context(|| format!("Impossible d'ouvrir {}", path.display()))?;
+    Ok(())
+}
+
+fn history_path(storage: &Storage) -> Result<PathBuf> {
+    Ok(storage.logs_dir()?.join(HISTORY_FILE))
+}
+
+fn read_history(path: &Path) -> Result<Vec<RunHistoryEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content).context("Historique des exécutions illisible")
+}
+
+fn write_history(path: &Path, entries: &[RunHistoryEntry]) -> Result<()> {
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, serde_json::to_vec_pretty(entries)?)?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+fn matches_filter(entry: &RunHistoryEntry, filter: &RunHistoryFilter) -> bool {
+    if let Some(query) = non_empty(&filter.query) {
+        let query = query.to_lowercase();
+        if !entry.workflow_name.to_lowercase().contains(&query)
+            && !entry.source_path.to_lowercase().contains(&query)
+            && !entry.run_id.contains(&query)
+        {
+            return false;
+        }
+    }
+    if let Some(workflow_id) = non_empty(&filter.workflow_id) {
+        if entry.workflow_id.as_deref() != Some(workflow_id) {
+            return false;
+        }
+    }
+    if let Some(source_path) = non_empty(&filter.source_path) {
+        if Path::new(&entry.source_path) != Path::new(source_path) {
+            return false;
+        }
+    }
+    if let Some(status) = filter.status {
+        if entry.status != status {
+            return false;
+        }
+    }
+    let started = parse_date(&entry.started_at);
+    if let (Some(since), Some(started)) = (non_empty(&filter.since).and_then(parse_date), started) {
+        if started < since {
+            return false;
+        }
+    }
+    if let (Some(until), Some(started)) = (non_empty(&filter.until).and_then(parse_date), started) {
+        if started > until {
+            return false;
+        }
+    }
+    true
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_date(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(
+        run_id: &str,
+        workflow: &str,
+        started_at: &str,
+        status: FolderRunStatus,
+    ) -> RunHistoryEntry {
+        RunHistoryEntry {
+            run_id: run_id.into(),
+            resumed_from: None,
+            workflow_id: Some(format!("wf-{workflow}")),
+            workflow_name: workflow.into(),
+            workflow_version: None,
+            workflow_hash: "0".into(),
+            source_path: "/rendus/TP1".into(),
+            started_at: started_at.into(),
+            finished_at: started_at.into(),
+            status,
+            operations_run: 0,
+            folder_count: 0,
+            failed_folder_count: 0,
+            warning_count: 0,
+            error_count: 0,
+            log_file_path: String::new(),
+            run_log_path: None,
+            run_directory: String::new(),
+            output_archive_path: None,
+            snapshot_path: None,
+        }
+    }
+
+    #[test]
+    fn filter_combines_query_status_and_date_range() {
+        let entries = [
+            entry(
+                "a",
+                "Corrections",
+                "2024-03-01T10:00:00+00:00",
+                FolderRunStatus::Succeeded,
+            ),
+            entry(
+                "b",
+                "Corrections",
+                "2024-03-05T10:00:00+00:00",
+                FolderRunStatus::Failed,
+            ),
+            entry(
+                "c",
+                "Nettoyage",
+                "2024-03-06T10:00:00+00:00",
+                FolderRunStatus::Failed,
+            ),
+        ];
+        let select = |filter: RunHistoryFilter| -> Vec<&str> {
+            entries
+                .iter()
+                .filter(|entry| matches_filter(entry, &filter))
+                .map(|entry| entry.run_id.as_str())
+                .collect()
+        };
+
+        assert_eq!(
+            select(RunHistoryFilter {
+                query: Some("correc".into()),
+                ..Default::default()
+            }),
+            vec!["a", "b"]
+        );
+        assert_eq!(
+            select(RunHistoryFilter {
+                status: Some(FolderRunStatus::Failed),
+                since: Some("2024-03-02T00:00:00+00:00".into()),
+                until: Some("2024-03-05T23:59:59+00:00".into()),
+                ..Default::default()
+            }),
+            vec!["b"]
+        );
+        assert_eq!(
+            select(RunHistoryFilter {
+                workflow_id: Some("wf-Nettoyage".into()),
+                source_path: Some("/rendus/TP1".into()),
+                ..Default::default()
+            }),
+            vec!["c"]
+        );
+    }
+}
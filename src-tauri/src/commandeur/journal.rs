@@ -0,0 +1,298 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::commandeur::models::{CommandeurExecutionOptions, CommandeurWorkflow, FolderRunStatus};
+use crate::commandeur::storage::Storage;
+use crate::commandeur::workspace::{WorkspaceHandle, WorkspaceMode};
+
+const JOURNAL_FILE: &str = "journal.json";
+
+/// Avancement persisté d'une exécution, réécrit après chaque opération terminée pour
+/// permettre une reprise après un arrêt ou un plantage.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionJournal {
+    pub run_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resumed_from: Option<String>,
+    /// Options de lancement, réappliquées telles quelles à la reprise.
+    #[serde(default)]
+    pub options: CommandeurExecutionOptions,
+    pub source_path: String,
+    pub root_path: String,
+    pub workflow: CommandeurWorkflow,
+    pub folders: Vec<JournalFolder>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalFolder {
+    pub folder: String,
+    pub index: usize,
+    #[serde(default)]
+    pub completed_operations: Vec<String>,
+    #[serde(default)]
+    pub captured: BTreeMap<String, String>,
+    /// Résultat des conditions évaluées, par opération: une branche interrompue reprend
+    /// sans réexécuter une commande de condition qui a pu modifier le dossier.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub conditions: BTreeMap<String, JournalCondition>,
+    /// Renseigné une fois toutes les opérations du dossier passées.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<FolderRunStatus>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalCondition {
+    pub result: bool,
+    pub summary: String,
+}
+
+impl ExecutionJournal {
+    pub fn new(
+        run_id: &str,
+        workspace: &WorkspaceHandle,
+        workflow: &CommandeurWorkflow,
+        options: &CommandeurExecutionOptions,
+        folders: &[(usize, String)],
+    ) -> Self {
+        Self {
+            run_id: run_id.to_string(),
+            resumed_from: None,
+            options: options.clone(),
+            source_path: workspace.source_path.to_string_lossy().to_string(),
+            root_path: workspace.root_path.to_string_lossy().to_string(),
+            workflow: workflow.clone(),
+            folders: folders
+                .iter()
+                .map(|(index, folder)| JournalFolder {
+                    folder: folder.clone(),
+                    index: *index,
+                    completed_operations: Vec::new(),
+                    captured: BTreeMap::new(),
+                    conditions: BTreeMap::new(),
+                    status: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Copie du journal pour une nouvelle exécution qui reprend celle-ci.
+    pub fn resumed(&self, run_id: &str) -> Self {
+        Self {
+            run_id: run_id.to_string(),
+            resumed_from: Some(self.run_id.clone()),
+            ..self.clone()
+        }
+    }
+
+    pub fn folder(&self, folder: &str) -> Option<&JournalFolder> {
+        self.folders.iter().find(|entry| entry.folder == folder)
+    }
+
+    pub fn pending_folders(&self) -> impl Iterator<Item = &JournalFolder> {
+        self.folders.iter().filter(|entry| entry.status.is_none())
+    }
+
+    /// Refuse la reprise sur un autre workspace, ou sur une archive réextraite depuis
+    /// (les modifications déjà faites seraient perdues).
+    pub fn check_workspace(&self, workspace: &WorkspaceHandle) -> Result<()> {
+        if Path::new(&self.source_path) != workspace.source_path {
+            return Err(anyhow!(
+                "L'exécution {} porte sur un autre workspace ({})",
+                self.run_id,
+                self.source_path
+            ));
+        }
+        if matches!(workspace.mode, WorkspaceMode::Zip)
+            && Path::new(&self.root_path) != workspace.root_path
+        {
+            return Err(anyhow!(
+                "L'archive a été réextraite depuis l'exécution {}: relancez l'exécution complète",
+                self.run_id
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Journal partagé entre les workers, écrit sur disque à chaque mise à jour.
+pub struct JournalWriter {
+    path: PathBuf,
+    journal: Mutex<ExecutionJournal>,
+}
+
+impl JournalWriter {
+    pub fn create(run_dir: &Path, journal: ExecutionJournal) -> Result<Self> {
+        let writer = Self {
+            path: run_dir.join(JOURNAL_FILE),
+            journal: Mutex::new(journal),
+        };
+        writer.update(|_| {})?;
+        Ok(writer)
+    }
+
+    pub fn folder(&self, folder: &str) -> Option<JournalFolder> {
+        self.journal
+            .lock()
+            .ok()
+            .and_then(|journal| journal.folder(folder).cloned())
+    }
+
+    pub fn complete_operation(
+        &self,
+        folder: &str,
+        operation_id: &str,
+        captured: &HashMap<String, String>,
+    ) -> Result<()> {
+        self.update_folder(folder, |entry| {
+            if !entry
+                .completed_operations
+                .iter()
+                .any(|id| id == operation_id)
+            {
+                entry.completed_operations.push(operation_id.to_string());
+            }
+            entry.captured.extend(
+                captured
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone())),
+            );
+        })
+    }
+
+    pub fn record_condition(
+        &self,
+        folder: &str,
+        operation_id: &str,
+        condition: JournalCondition,
+    ) -> Result<()> {
+        self.update_folder(folder, |entry| {
+            entry.conditions.insert(operation_id.to_string(), condition);
+        })
+    }
+
+    pub fn finish_folder(&self, folder: &str, status: FolderRunStatus) -> Result<()> {
+        self.update_folder(folder, |entry| entry.status = Some(status))
+    }
+
+    fn update_folder(&self, folder: &str, apply: impl FnOnce(&mut JournalFolder)) -> Result<()> {
+        self.update(|journal| {
+            if let Some(entry) = journal
+                .folders
+                .iter_mut()
+                .find(|entry| entry.folder == folder)
+            {
+                apply(entry);
+            }
+        })
+    }
+
+    fn update(&self, apply: impl FnOnce(&mut ExecutionJournal)) -> Result<()> {
+        let mut journal = self
+            .journal
+            .lock()
+            .map_err(|_| anyhow!("Accès concurrent au journal d'exécution"))?;
+        apply(&mut journal);
+        // Écriture atomique: un plantage pendant l'écriture laisse le journal précédent.
+        let temp_path = self.path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_vec_pretty(&*journal)?)?;
+        fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+pub fn load_journal(storage: &Storage, run_id: &str) -> Result<ExecutionJournal> {
+    read_journal(&storage.existing_run_dir(run_id)?.join(JOURNAL_FILE))
+        .with_context(|| format!("Aucun journal de reprise pour l'exécution {run_id}"))
+}
+
+fn read_journal(path: &Path) -> Result<ExecutionJournal> {
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn journal_tracks_progress_and_resumes_pending_folders() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = WorkspaceHandle {
+            id: "ws".into(),
+            mode: WorkspaceMode::Directory,
+            source_path: dir.path().to_path_buf(),
+            root_path: dir.path().to_path_buf(),
+            extracted: None,
+            created_at: Utc::now(),
+            sub_folders: vec!["Alice".into(), "Bob".into()],
+            expanded_archives: Vec::new(),
+        };
+        let workflow: CommandeurWorkflow = serde_json::from_value(serde_json::json!({
+            "name": "Corrections",
+            "version": null,
+            "operations": []
+        }))
+        .unwrap();
+        let folders = vec![(0, "Alice".to_string()), (1, "Bob".to_string())];
+        let writer = JournalWriter::create(
+            dir.path(),
+            ExecutionJournal::new(
+                "run-1",
+                &workspace,
+                &workflow,
+                &CommandeurExecutionOptions {
+                    snapshot: true,
+                    selection: None,
+                    workflow_id: Some("wf-1".into()),
+                },
+                &folders,
+            ),
+        )
+        .unwrap();
+
+        let captured: HashMap<String, String> = [("note".to_string(), "12".to_string())]
+            .into_iter()
+            .collect();
+        writer
+            .complete_operation("Alice", "op-1", &captured)
+            .unwrap();
+        writer
+            .complete_operation("Alice", "op-2", &captured)
+            .unwrap();
+        writer
+            .finish_folder("Alice", FolderRunStatus::Succeeded)
+            .unwrap();
+        writer
+            .complete_operation("Bob", "op-1", &HashMap::new())
+            .unwrap();
+
+        let journal = read_journal(&dir.path().join(JOURNAL_FILE)).unwrap();
+        journal.check_workspace(&workspace).unwrap();
+        let resumed = journal.resumed("run-2");
+        assert_eq!(resumed.resumed_from.as_deref(), Some("run-1"));
+        assert!(resumed.options.snapshot);
+        assert_eq!(resumed.options.workflow_id.as_deref(), Some("wf-1"));
+        let pending: Vec<&JournalFolder> = resumed.pending_folders().collect();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].folder, "Bob");
+        assert_eq!(pending[0].completed_operations, vec!["op-1".to_string()]);
+        let alice = resumed.folder("Alice").unwrap();
+        assert_eq!(alice.completed_operations, vec!["op-1", "op-2"]);
+        assert_eq!(alice.captured.get("note").map(String::as_str), Some("12"));
+
+        let other = WorkspaceHandle {
+            source_path: dir.path().join("autre"),
+            ..workspace
+        };
+        assert!(resumed.check_workspace(&other).is_err());
+    }
+}
@@ -1,29 +1,50 @@
+mod artifacts;
 mod conditions;
+mod dry_run;
 mod errors;
 mod execution;
 mod execution_control;
+mod globs;
+mod history;
+mod journal;
 mod models;
+mod process;
 mod python;
+mod report;
 mod reporting;
+mod run_log;
 mod saved_workflows;
+mod selection;
+mod snapshots;
 mod storage;
+mod summary;
+mod templating;
 mod utils;
 mod validation;
+mod workflow_files;
 mod workspace;
 
 pub use workspace::AppState;
 
-use execution::execute_workflow;
-use execution_control::ExecutionStatus;
+use dry_run::dry_run_workflow;
+use execution::{execute_workflow, resume_execution};
+use execution_control::ExecutionStatusReport;
+use history::{delete_run, list_runs, load_run_result, open_path, open_run_directory};
 use models::{
-    CommandeurExecutionResult, CommandeurValidationMessage, CommandeurWorkflow,
-    SavedWorkflowSummary,
+    CommandeurDryRunResult, CommandeurExecutionOptions, CommandeurExecutionResult,
+    CommandeurSnapshotRestoreResult, CommandeurValidationMessage, CommandeurWorkflow,
+    FolderSelection, RunHistoryEntry, RunHistoryFilter, SavedWorkflowSummary,
 };
+use report::regenerate_report;
 use saved_workflows::{
     delete_workflow, duplicate_workflow, list_workflows, load_workflow, save_workflow,
 };
+use selection::preview_selection;
+use snapshots::restore_snapshot;
+use summary::export_run_summary;
 use validation::validate_workflow;
-use workspace::{prepare_workspace, CommandeurWorkspaceSummary};
+use workflow_files::{export_workflow, import_workflow, WORKFLOW_SCHEMA_VERSION};
+use workspace::{prepare_workspace, CommandeurWorkspaceSummary, WorkspaceOptions};
 
 use tauri::{async_runtime::spawn_blocking, State, Window};
 
@@ -31,8 +52,10 @@ use tauri::{async_runtime::spawn_blocking, State, Window};
 pub fn commandeur_prepare_workspace(
     state: State<AppState>,
     path: String,
+    options: Option<WorkspaceOptions>,
 ) -> Result<CommandeurWorkspaceSummary, String> {
-    prepare_workspace(&state, path.as_str()).map_err(|err| err.to_string())
+    let options = options.unwrap_or_default();
+    prepare_workspace(&state, path.as_str(), &options).map_err(|err| err.to_string())
 }
 
 #[tauri::command]
@@ -44,14 +67,29 @@ pub fn commandeur_validate_workflow(
     validate_workflow(&state, workspace_id.as_str(), &workflow).map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+pub async fn commandeur_dry_run_workflow(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    workflow: CommandeurWorkflow,
+) -> Result<CommandeurDryRunResult, String> {
+    let state_clone = state.inner().clone();
+    spawn_blocking(move || dry_run_workflow(&state_clone, workspace_id.as_str(), &workflow))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 pub async fn commandeur_execute_workflow(
     window: Window,
     state: State<'_, AppState>,
     workspace_id: String,
     workflow: CommandeurWorkflow,
+    options: Option<CommandeurExecutionOptions>,
 ) -> Result<CommandeurExecutionResult, String> {
     let control = state.register_execution().map_err(|err| err.to_string())?;
+    let options = options.unwrap_or_default();
     let state_clone = state.inner().clone();
     let state_for_execution = state_clone.clone();
     let workflow_clone = workflow.clone();
@@ -65,6 +103,7 @@ pub async fn commandeur_execute_workflow(
             Some(&window_clone),
             workspace_id_clone.as_str(),
             &workflow_clone,
+            &options,
             control_for_execution,
         )
     })
@@ -83,61 +122,193 @@ pub async fn commandeur_execute_workflow(
 }
 
 #[tauri::command]
-pub fn commandeur_execution_pause(state: State<AppState>) -> Result<ExecutionStatus, String> {
+pub async fn commandeur_resume_execution(
+    window: Window,
+    state: State<'_, AppState>,
+    workspace_id: String,
+    run_id: String,
+) -> Result<CommandeurExecutionResult, String> {
+    let control = state.register_execution().map_err(|err| err.to_string())?;
+    let state_clone = state.inner().clone();
+    let state_for_execution = state_clone.clone();
+    let window_clone = window.clone();
+    let control_for_execution = control.clone();
+
+    let result = spawn_blocking(move || {
+        resume_execution(
+            &state_for_execution,
+            Some(&window_clone),
+            workspace_id.as_str(),
+            run_id.as_str(),
+            control_for_execution,
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(|err| err.to_string());
+
+    if let Err(err) = state_clone.clear_execution() {
+        eprintln!(
+            "[commandeur_resume_execution] unable to clear execution: {}",
+            err
+        );
+    }
+
+    result
+}
+
+#[tauri::command]
+pub fn commandeur_preview_folder_selection(
+    state: State<AppState>,
+    workspace_id: String,
+    selection: FolderSelection,
+) -> Result<Vec<String>, String> {
+    preview_selection(&state, workspace_id.as_str(), &selection).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn commandeur_restore_snapshot(
+    state: State<AppState>,
+    workspace_id: String,
+    run_id: String,
+    folders: Option<Vec<String>>,
+) -> Result<CommandeurSnapshotRestoreResult, String> {
+    restore_snapshot(&state, workspace_id.as_str(), run_id.as_str(), folders)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn commandeur_execution_pause(state: State<AppState>) -> Result<ExecutionStatusReport, String> {
     state
         .with_execution(|session| {
             session.control.request_pause();
-            Ok(session.control.status())
+            Ok(session.control.report())
         })
         .map_err(|err| err.to_string())
 }
 
 #[tauri::command]
-pub fn commandeur_execution_resume(state: State<AppState>) -> Result<ExecutionStatus, String> {
+pub fn commandeur_execution_resume(
+    state: State<AppState>,
+) -> Result<ExecutionStatusReport, String> {
     state
         .with_execution(|session| {
             session.control.request_resume();
-            Ok(session.control.status())
+            Ok(session.control.report())
         })
         .map_err(|err| err.to_string())
 }
 
 #[tauri::command]
-pub fn commandeur_execution_stop(state: State<AppState>) -> Result<ExecutionStatus, String> {
+pub fn commandeur_execution_stop(state: State<AppState>) -> Result<ExecutionStatusReport, String> {
     state
         .with_execution(|session| {
             session
                 .control
                 .request_stop(Some("Arrêt manuel demandé".to_string()));
-            Ok(session.control.status())
+            Ok(session.control.report())
         })
         .map_err(|err| err.to_string())
 }
 
 #[tauri::command]
-pub fn commandeur_list_saved_workflows() -> Result<Vec<SavedWorkflowSummary>, String> {
-    list_workflows().map_err(|err| err.to_string())
+pub fn commandeur_list_saved_workflows(
+    state: State<AppState>,
+) -> Result<Vec<SavedWorkflowSummary>, String> {
+    list_workflows(state.storage()).map_err(|err| err.to_string())
 }
 
 #[tauri::command]
 pub fn commandeur_save_workflow(
+    state: State<AppState>,
     workflow: CommandeurWorkflow,
     existing_id: Option<String>,
 ) -> Result<SavedWorkflowSummary, String> {
-    save_workflow(&workflow, existing_id).map_err(|err| err.to_string())
+    save_workflow(state.storage(), &workflow, existing_id).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn commandeur_load_saved_workflow(
+    state: State<AppState>,
+    id: String,
+) -> Result<CommandeurWorkflow, String> {
+    load_workflow(state.storage(), &id).map_err(|err| err.to_string())
 }
 
 #[tauri::command]
-pub fn commandeur_load_saved_workflow(id: String) -> Result<CommandeurWorkflow, String> {
-    load_workflow(&id).map_err(|err| err.to_string())
+pub fn commandeur_delete_saved_workflow(state: State<AppState>, id: String) -> Result<(), String> {
+    delete_workflow(state.storage(), &id).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn commandeur_duplicate_saved_workflow(
+    state: State<AppState>,
+    id: String,
+) -> Result<SavedWorkflowSummary, String> {
+    duplicate_workflow(state.storage(), &id).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn commandeur_import_workflow(path: String) -> Result<CommandeurWorkflow, String> {
+    import_workflow(std::path::Path::new(&path)).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn commandeur_export_workflow(
+    workflow: CommandeurWorkflow,
+    path: String,
+) -> Result<(), String> {
+    export_workflow(&workflow, std::path::Path::new(&path)).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn commandeur_workflow_schema_version() -> u64 {
+    WORKFLOW_SCHEMA_VERSION
+}
+
+#[tauri::command]
+pub fn commandeur_list_runs(
+    state: State<AppState>,
+    filter: Option<RunHistoryFilter>,
+) -> Result<Vec<RunHistoryEntry>, String> {
+    list_runs(state.storage(), &filter.unwrap_or_default()).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn commandeur_get_run_result(
+    state: State<AppState>,
+    run_id: String,
+) -> Result<CommandeurExecutionResult, String> {
+    load_run_result(state.storage(), run_id.as_str()).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn commandeur_export_run_summary(
+    state: State<AppState>,
+    run_id: String,
+    destination: String,
+) -> Result<(), String> {
+    export_run_summary(state.storage(), run_id.as_str(), destination.as_str())
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn commandeur_open_run_report(
+    state: State<AppState>,
+    run_id: String,
+) -> Result<String, String> {
+    let path =
+        regenerate_report(state.storage(), run_id.as_str()).map_err(|err| err.to_string())?;
+    open_path(&path).map_err(|err| err.to_string())?;
+    Ok(path.to_string_lossy().to_string())
 }
 
 #[tauri::command]
-pub fn commandeur_delete_saved_workflow(id: String) -> Result<(), String> {
-    delete_workflow(&id).map_err(|err| err.to_string())
+pub fn commandeur_open_run(state: State<AppState>, run_id: String) -> Result<(), String> {
+    open_run_directory(state.storage(), run_id.as_str()).map_err(|err| err.to_string())
 }
 
 #[tauri::command]
-pub fn commandeur_duplicate_saved_workflow(id: String) -> Result<SavedWorkflowSummary, String> {
-    duplicate_workflow(&id).map_err(|err| err.to_string())
+pub fn commandeur_delete_run(state: State<AppState>, run_id: String) -> Result<(), String> {
+    delete_run(state.storage(), run_id.as_str()).map_err(|err| err.to_string())
 }
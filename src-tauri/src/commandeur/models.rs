@@ -16,6 +16,12 @@ pub struct CommandeurWorkflow {
     pub name: String,
     pub version: Option<String>,
     pub operations: Vec<CommandeurOperation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_workers: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_timeout_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -28,6 +34,34 @@ pub struct OperationMeta {
     pub enabled: bool,
     #[serde(default)]
     pub continue_on_error: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matching: Option<TargetMatching>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetMatching {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_matches: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_matches: Option<usize>,
+    /// `Rapport.PDF` correspond aussi à `rapport.pdf`.
+    #[serde(default)]
+    pub case_insensitive: bool,
+}
+
+impl TargetMatching {
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty()
+            && self.exclude.is_empty()
+            && self.min_matches.is_none()
+            && self.max_matches.is_none()
+            && !self.case_insensitive
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -61,6 +95,10 @@ pub enum OperationDetails {
         shell: ShellKind,
         cwd: Option<String>,
         env: Option<HashMap<String, String>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_seconds: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        capture: Option<OutputCapture>,
     },
     #[serde(rename_all = "camelCase")]
     ReplaceInFile {
@@ -98,6 +136,10 @@ pub enum OperationDetails {
         inline_script: Option<String>,
         script_path: Option<String>,
         entry: PythonEntry,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_seconds: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        capture: Option<OutputCapture>,
     },
     #[serde(rename_all = "camelCase")]
     If {
@@ -107,6 +149,39 @@ pub enum OperationDetails {
         #[serde(rename = "else")]
         else_branch: Option<Vec<CommandeurOperation>>,
     },
+    #[serde(rename_all = "camelCase")]
+    ForEach {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pattern: Option<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        items: Vec<String>,
+        #[serde(default)]
+        operations: Vec<CommandeurOperation>,
+    },
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputCapture {
+    pub name: String,
+    #[serde(default)]
+    pub source: CaptureSource,
+    pub pattern: Option<String>,
+    pub flags: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CaptureSource {
+    Stdout,
+    ExitCode,
+    Regex,
+}
+
+impl Default for CaptureSource {
+    fn default() -> Self {
+        CaptureSource::Stdout
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -148,6 +223,13 @@ pub enum ConditionSelector {
     CurrentFolderName,
     FileSearch,
     FileCount,
+    CapturedValue,
+    FileContent,
+    TotalSize,
+    NewestModified,
+    OldestModified,
+    LineCount,
+    CommandExitCode,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
@@ -155,6 +237,8 @@ pub enum ConditionSelector {
 pub enum ConditionOperator {
     Equals,
     Contains,
+    #[serde(rename = "not-contains")]
+    NotContains,
     Regex,
     Exists,
     #[serde(rename = "not-exists")]
@@ -172,6 +256,20 @@ pub enum ConditionScope {
     Recursive,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConditionRunner {
+    Command,
+    Python,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConditionQuantifier {
+    Any,
+    All,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ConditionTest {
@@ -181,6 +279,28 @@ pub struct ConditionTest {
     pub pattern: Option<String>,
     pub scope: Option<ConditionScope>,
     pub exists: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variable: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flags: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantifier: Option<ConditionQuantifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runner: Option<ConditionRunner>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<ShellKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<u64>,
+    #[serde(default)]
+    pub ignore_case: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub all: Option<Vec<ConditionTest>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub any: Option<Vec<ConditionTest>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not: Option<Box<ConditionTest>>,
     #[serde(default)]
     pub negate: bool,
 }
@@ -216,7 +336,7 @@ impl CommandeurOperation {
     }
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CommandeurValidationMessage {
     pub operation_id: String,
@@ -228,7 +348,7 @@ pub struct CommandeurValidationMessage {
     pub folders: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum ValidationLevel {
     Info,
@@ -236,14 +356,18 @@ pub enum ValidationLevel {
     Error,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CommandeurExecutionLogEntry {
     pub timestamp: String,
     pub operation_id: String,
     pub operation_label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
     pub message: String,
     pub level: ValidationLevel,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<String>,
 }
 
 impl CommandeurExecutionLogEntry {
@@ -257,11 +381,23 @@ impl CommandeurExecutionLogEntry {
             timestamp: Utc::now().to_rfc3339(),
             operation_id: operation_id.into(),
             operation_label: operation_label.into(),
+            folder: None,
             message: message.into(),
             level,
+            artifacts: Vec::new(),
         }
     }
 
+    pub fn with_folder(mut self, folder: impl Into<String>) -> Self {
+        self.folder = Some(folder.into());
+        self
+    }
+
+    pub fn with_artifacts(mut self, artifacts: Vec<String>) -> Self {
+        self.artifacts = artifacts;
+        self
+    }
+
     pub fn level_string(&self) -> &'static str {
         match self.level {
             ValidationLevel::Info => "INFO",
@@ -271,9 +407,67 @@ impl CommandeurExecutionLogEntry {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandeurExecutionOptions {
+    #[serde(default)]
+    pub snapshot: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection: Option<FolderSelection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSelection {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub folders: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_pattern: Option<String>,
+    #[serde(default)]
+    pub name_pattern_mode: NamePatternMode,
+    #[serde(default)]
+    pub case_insensitive: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<ConditionTest>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_in_run: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum NamePatternMode {
+    Glob,
+    Regex,
+}
+
+impl Default for NamePatternMode {
+    fn default() -> Self {
+        NamePatternMode::Glob
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FolderRunStatus {
+    Succeeded,
+    Failed,
+    Interrupted,
+    Skipped,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandeurFolderStatus {
+    pub folder: String,
+    pub status: FolderRunStatus,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CommandeurExecutionResult {
+    pub run_id: String,
     pub success: bool,
     pub operations_run: usize,
     pub log_file_path: String,
@@ -281,6 +475,159 @@ pub struct CommandeurExecutionResult {
     pub warnings: Vec<CommandeurValidationMessage>,
     pub errors: Vec<CommandeurValidationMessage>,
     pub output_archive_path: Option<String>,
+    pub snapshot_path: Option<String>,
+    pub timeouts: Vec<CommandeurOperationTimeout>,
+    pub run_directory: String,
+    pub artifacts: Vec<CommandeurProcessArtifact>,
+    pub folder_statuses: Vec<CommandeurFolderStatus>,
+    pub run_log_path: Option<String>,
+    #[serde(default)]
+    pub summary: CommandeurExecutionSummary,
+    #[serde(default)]
+    pub summary_csv_path: Option<String>,
+    #[serde(default)]
+    pub report_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum OperationRunStatus {
+    Ok,
+    Failed,
+    Interrupted,
+    Skipped,
+    Disabled,
+    BranchNotTaken,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandeurExecutionSummary {
+    pub columns: Vec<CommandeurSummaryColumn>,
+    pub folders: Vec<CommandeurFolderSummary>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandeurSummaryColumn {
+    pub operation_id: String,
+    pub operation_label: String,
+    pub depth: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandeurFolderSummary {
+    pub folder: String,
+    pub status: FolderRunStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    pub cells: Vec<CommandeurSummaryCell>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandeurSummaryCell {
+    pub operation_id: String,
+    pub status: OperationRunStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandeurProcessArtifact {
+    pub operation_id: String,
+    pub operation_label: String,
+    pub folder: String,
+    pub command: String,
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    pub timed_out: bool,
+    pub stdout_path: String,
+    pub stderr_path: String,
+    pub result_path: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandeurOperationTimeout {
+    pub operation_id: String,
+    pub operation_label: String,
+    pub folder: String,
+    pub timeout_seconds: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandeurSnapshotRestoreResult {
+    pub run_id: String,
+    pub restored_folders: Vec<String>,
+    pub skipped_folders: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandeurDryRunResult {
+    pub folders: Vec<CommandeurFolderPlan>,
+    pub planned_changes: usize,
+    pub failing_folders: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandeurFolderPlan {
+    pub folder: String,
+    pub steps: Vec<CommandeurPlannedStep>,
+    pub halts_execution: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandeurPlannedStep {
+    pub operation_id: String,
+    pub operation_label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_operation_id: Option<String>,
+    pub status: PlannedStepStatus,
+    pub message: String,
+    pub changes: Vec<CommandeurPlannedChange>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PlannedStepStatus {
+    Planned,
+    NoChange,
+    Disabled,
+    WouldFail,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandeurPlannedChange {
+    pub kind: PlannedChangeKind,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PlannedChangeKind {
+    CreateFile,
+    OverwriteFile,
+    ModifyFile,
+    Delete,
+    Copy,
+    Move,
+    Rename,
+    CreateDirectory,
+    RunCommand,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -290,3 +637,51 @@ pub struct SavedWorkflowSummary {
     pub name: String,
     pub saved_at: String,
 }
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RunHistoryEntry {
+    pub run_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resumed_from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow_id: Option<String>,
+    pub workflow_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow_version: Option<String>,
+    pub workflow_hash: String,
+    pub source_path: String,
+    pub started_at: String,
+    pub finished_at: String,
+    pub status: FolderRunStatus,
+    pub operations_run: usize,
+    pub folder_count: usize,
+    pub failed_folder_count: usize,
+    pub warning_count: usize,
+    pub error_count: usize,
+    pub log_file_path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_log_path: Option<String>,
+    pub run_directory: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_archive_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RunHistoryFilter {
+    #[serde(default)]
+    pub query: Option<String>,
+    #[serde(default)]
+    pub workflow_id: Option<String>,
+    #[serde(default)]
+    pub source_path: Option<String>,
+    #[serde(default)]
+    pub status: Option<FolderRunStatus>,
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub until: Option<String>,
+}
@@ -0,0 +1,512 @@
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::commandeur::execution_control::{ExecutionControl, ExecutionStatus};
+use crate::commandeur::models::ShellKind;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(25);
+const OUTPUT_FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+const MAX_LINES_PER_BATCH: usize = 200;
+const MAX_LINE_LENGTH: usize = 2000;
+/// Attente maximale des sorties une fois l'enfant terminé: un petit-enfant détaché peut garder
+/// les tubes ouverts indéfiniment.
+const READER_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OutputLine {
+    pub stream: OutputStream,
+    pub text: String,
+}
+
+/// Lignes produites depuis le dernier envoi. Au-delà de `MAX_LINES_PER_BATCH` lignes par
+/// intervalle, les lignes ne sont plus transmises en direct (elles restent dans les sorties
+/// complètes) et seul leur nombre est remonté.
+pub struct OutputBatch {
+    pub lines: Vec<OutputLine>,
+    pub dropped_lines: usize,
+}
+
+struct OutputThrottle {
+    receiver: Receiver<OutputLine>,
+    pending: Vec<OutputLine>,
+    dropped_lines: usize,
+    last_flush: Instant,
+}
+
+impl OutputThrottle {
+    fn new(receiver: Receiver<OutputLine>) -> Self {
+        Self {
+            receiver,
+            pending: Vec::new(),
+            dropped_lines: 0,
+            last_flush: Instant::now(),
+        }
+    }
+
+    fn drain(&mut self) {
+        while let Ok(line) = self.receiver.try_recv() {
+            if self.pending.len() < MAX_LINES_PER_BATCH {
+                self.pending.push(line);
+            } else {
+                self.dropped_lines += 1;
+            }
+        }
+    }
+
+    fn flush(&mut self, on_output: &mut dyn FnMut(OutputBatch), force: bool) {
+        self.drain();
+        if !force && self.last_flush.elapsed() < OUTPUT_FLUSH_INTERVAL {
+            return;
+        }
+        if self.pending.is_empty() && self.dropped_lines == 0 {
+            return;
+        }
+        on_output(OutputBatch {
+            lines: std::mem::take(&mut self.pending),
+            dropped_lines: self.dropped_lines,
+        });
+        self.dropped_lines = 0;
+        self.last_flush = Instant::now();
+    }
+}
+
+pub struct ProcessOutput {
+    pub status: Option<ExitStatus>,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    pub duration: Duration,
+}
+
+pub enum ProcessOutcome {
+    Completed(ProcessOutput),
+    TimedOut(ProcessOutput),
+    Interrupted { reason: String },
+}
+
+/// Lance `cmd` et attend sa fin en surveillant le délai et les demandes de pause ou d'arrêt.
+/// En cas de dépassement ou d'arrêt, tout l'arbre de processus de l'enfant est tué; une pause
+/// le suspend (Unix uniquement) et n'est pas décomptée du délai.
+/// Les sorties sont transmises par lots à `on_output` pendant l'exécution.
+pub fn run_process(
+    mut cmd: Command,
+    timeout: Option<Duration>,
+    control: &ExecutionControl,
+    on_output: &mut dyn FnMut(OutputBatch),
+) -> Result<ProcessOutcome> {
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    isolate_process_group(&mut cmd);
+
+    let started = Instant::now();
+    let mut child = cmd.spawn()?;
+    let (sender, receiver) = mpsc::channel();
+    let stdout_reader = spawn_reader(child.stdout.take(), OutputStream::Stdout, sender.clone());
+    let stderr_reader = spawn_reader(child.stderr.take(), OutputStream::Stderr, sender);
+    let mut throttle = OutputThrottle::new(receiver);
+
+    let mut timed_out = false;
+    let mut interrupted: Option<String> = None;
+    let mut suspended_since: Option<Instant> = None;
+    let mut paused_for = Duration::ZERO;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if let Some(reason) = control.stop_requested() {
+            kill_process_tree(&mut child);
+            interrupted = Some(reason);
+            break child.wait().ok();
+        }
+        let paused = control.status() == ExecutionStatus::Paused;
+        match suspended_since {
+            None if paused => {
+                signal_process_tree(&child, ProcessSignal::Suspend);
+                suspended_since = Some(Instant::now());
+            }
+            Some(since) if !paused => {
+                signal_process_tree(&child, ProcessSignal::Continue);
+                paused_for += since.elapsed();
+                suspended_since = None;
+            }
+            _ => {}
+        }
+        if suspended_since.is_none()
+            && timeout.map_or(false, |limit| {
+                started.elapsed().saturating_sub(paused_for) >= limit
+            })
+        {
+            kill_process_tree(&mut child);
+            timed_out = true;
+            break child.wait().ok();
+        }
+        throttle.flush(on_output, false);
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let grace_deadline = Instant::now() + READER_GRACE_PERIOD;
+    while [&stdout_reader, &stderr_reader]
+        .into_iter()
+        .flatten()
+        .any(|reader| !reader.finished.load(Ordering::SeqCst))
+        && Instant::now() < grace_deadline
+    {
+        throttle.flush(on_output, false);
+        thread::sleep(POLL_INTERVAL);
+    }
+    let output = ProcessOutput {
+        status,
+        stdout: reader_output(stdout_reader),
+        stderr: reader_output(stderr_reader),
+        duration: started.elapsed(),
+    };
+    throttle.flush(on_output, true);
+
+    if let Some(reason) = interrupted {
+        Ok(ProcessOutcome::Interrupted { reason })
+    } else if timed_out {
+        Ok(ProcessOutcome::TimedOut(output))
+    } else {
+        Ok(ProcessOutcome::Completed(output))
+    }
+}
+
+/// Lecteur d'un flux de sortie; `buffer` reste consultable même si le fil ne se termine pas.
+struct StreamReader {
+    finished: Arc<AtomicBool>,
+    buffer: Arc<Mutex<Vec<u8>>>,
+}
+
+fn spawn_reader<R: Read + Send + 'static>(
+    source: Option<R>,
+    stream: OutputStream,
+    sender: Sender<OutputLine>,
+) -> Option<StreamReader> {
+    source.map(|source| {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let finished = Arc::new(AtomicBool::new(false));
+        let shared = Arc::clone(&buffer);
+        let done = Arc::clone(&finished);
+        thread::spawn(move || {
+            let mut reader = BufReader::new(source);
+            let mut line = Vec::new();
+            loop {
+                line.clear();
+                match reader.read_until(b'\n', &mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        if let Ok(mut buffer) = shared.lock() {
+                            buffer.extend_from_slice(&line);
+                        }
+                        let text = String::from_utf8_lossy(&line);
+                        let text = text.trim_end_matches(['\n', '\r']);
+                        let text = match text.char_indices().nth(MAX_LINE_LENGTH) {
+                            Some((index, _)) => format!("{}…", &text[..index]),
+                            None => text.to_string(),
+                        };
+                        let _ = sender.send(OutputLine { stream, text });
+                    }
+                }
+            }
+            done.store(true, Ordering::SeqCst);
+        });
+        StreamReader { finished, buffer }
+    })
+}
+
+/// Sorties lues jusqu'ici; un lecteur encore bloqué est abandonné et se terminera à la
+/// fermeture du tube.
+fn reader_output(reader: Option<StreamReader>) -> Vec<u8> {
+    reader
+        .and_then(|reader| {
+            reader
+                .buffer
+                .lock()
+                .ok()
+                .map(|mut buffer| std::mem::take(&mut *buffer))
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(unix)]
+fn isolate_process_group(cmd: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    // Le processus enfant devient chef de son propre groupe pour pouvoir tuer ses descendants.
+    unsafe {
+        cmd.pre_exec(|| {
+            if libc::setpgid(0, 0) == 0 {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error())
+            }
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn isolate_process_group(_cmd: &mut Command) {}
+
+enum ProcessSignal {
+    Suspend,
+    Continue,
+}
+
+#[cfg(unix)]
+fn signal_process_tree(child: &Child, signal: ProcessSignal) {
+    let pgid = child.id() as libc::pid_t;
+    let signal = match signal {
+        ProcessSignal::Suspend => libc::SIGSTOP,
+        ProcessSignal::Continue => libc::SIGCONT,
+    };
+    unsafe {
+        libc::kill(-pgid, signal);
+    }
+}
+
+/// Sans équivalent simple de SIGSTOP, le processus continue pendant la pause.
+#[cfg(not(unix))]
+fn signal_process_tree(_child: &Child, _signal: ProcessSignal) {}
+
+#[cfg(unix)]
+fn kill_process_tree(child: &mut Child) {
+    let pgid = child.id() as libc::pid_t;
+    unsafe {
+        libc::kill(-pgid, libc::SIGKILL);
+    }
+    let _ = child.kill();
+}
+
+#[cfg(windows)]
+fn kill_process_tree(child: &mut Child) {
+    let _ = Command::new("taskkill")
+        .args(["/PID", &child.id().to_string(), "/T", "/F"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    let _ = child.kill();
+}
+
+#[cfg(not(any(unix, windows)))]
+fn kill_process_tree(child: &mut Child) {
+    let _ = child.kill();
+}
+
+pub fn build_command(shell: &ShellKind, command: &str, args: &[String]) -> Result<Command> {
+    match shell {
+        ShellKind::Default => {
+            let mut cmd = Command::new(command);
+            cmd.args(args);
+            Ok(cmd)
+        }
+        ShellKind::Powershell => {
+            #[cfg(target_os = "windows")]
+            let shell_name = "powershell";
+            #[cfg(not(target_os = "windows"))]
+            let shell_name = "pwsh";
+            let mut cmd = Command::new(shell_name);
+            let script = join_powershell(command, args);
+            cmd.arg("-NoProfile").arg("-Command").arg(script);
+            Ok(cmd)
+        }
+        ShellKind::Bash => Ok(posix_shell("bash", command, args)),
+        ShellKind::Zsh => Ok(posix_shell("zsh", command, args)),
+        ShellKind::Fish => Ok(posix_shell("fish", command, args)),
+    }
+}
+
+/// Ligne de commande interprétée par le shell du système (`sh -c`, `cmd /C` sous Windows),
+/// pour que jokers, tubes et redirections fonctionnent comme dans un terminal.
+pub fn system_shell(command: &str) -> Command {
+    #[cfg(target_os = "windows")]
+    let (shell_name, flag) = ("cmd", "/C");
+    #[cfg(not(target_os = "windows"))]
+    let (shell_name, flag) = ("sh", "-c");
+    let mut cmd = Command::new(shell_name);
+    cmd.arg(flag).arg(command);
+    cmd
+}
+
+/// Citation d'une valeur insérée dans un script de shell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellQuoting {
+    Posix,
+    PowerShell,
+    Cmd,
+}
+
+impl ShellQuoting {
+    /// Citation d'une commande lancée avec `shell`; `None` sans shell, le programme recevant
+    /// ses arguments tels quels.
+    pub fn for_shell(shell: &ShellKind) -> Option<Self> {
+        match shell {
+            ShellKind::Default => None,
+            ShellKind::Powershell => Some(Self::PowerShell),
+            ShellKind::Bash | ShellKind::Zsh | ShellKind::Fish => Some(Self::Posix),
+        }
+    }
+
+    /// Citation du shell de `system_shell`.
+    pub fn system() -> Self {
+        if cfg!(target_os = "windows") {
+            Self::Cmd
+        } else {
+            Self::Posix
+        }
+    }
+
+    pub fn quote(self, value: &str) -> String {
+        if !value.is_empty()
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_./".contains(c))
+        {
+            return value.to_string();
+        }
+        match self {
+            Self::Posix => format!("'{}'", value.replace('\'', "'\\''")),
+            Self::PowerShell => format!("'{}'", value.replace('\'', "''")),
+            Self::Cmd => format!("\"{}\"", value.replace('"', "\"\"")),
+        }
+    }
+}
+
+fn posix_shell(shell_name: &str, command: &str, args: &[String]) -> Command {
+    let mut cmd = Command::new(shell_name);
+    let script = if args.is_empty() {
+        command.to_string()
+    } else {
+        format!("{} {}", command, join_shell_args(args))
+    };
+    cmd.arg("-lc").arg(script);
+    cmd
+}
+
+fn join_shell_args(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| ShellQuoting::Posix.quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn join_powershell(command: &str, args: &[String]) -> String {
+    let mut parts = Vec::with_capacity(args.len() + 1);
+    parts.push(command.to_string());
+    parts.extend(args.iter().map(|arg| ShellQuoting::PowerShell.quote(arg)));
+    parts.join(" ")
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str) -> Command {
+        build_command(&ShellKind::Default, "sh", &["-c".into(), script.into()]).unwrap()
+    }
+
+    #[test]
+    fn timeout_kills_the_whole_process_tree() {
+        let control = ExecutionControl::new();
+        let cmd = shell("sleep 30 & sleep 30; echo fini");
+        let started = Instant::now();
+        let outcome =
+            run_process(cmd, Some(Duration::from_millis(200)), &control, &mut |_| {}).unwrap();
+        assert!(matches!(outcome, ProcessOutcome::TimedOut(_)));
+        // Les lecteurs de sortie ne se terminent que si le petit-enfant `sleep` a aussi été tué.
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn detached_grandchild_does_not_block_completion() {
+        let control = ExecutionControl::new();
+        // Le petit-enfant hérite des tubes de sortie et survit au shell.
+        let cmd = shell("sleep 30 & echo fini");
+        let started = Instant::now();
+        let outcome = run_process(cmd, None, &control, &mut |_| {}).unwrap();
+        assert!(started.elapsed() < Duration::from_secs(10));
+        match outcome {
+            ProcessOutcome::Completed(output) => assert_eq!(output.stdout, b"fini\n"),
+            _ => panic!("le processus aurait dû se terminer normalement"),
+        }
+    }
+
+    #[test]
+    fn stop_request_interrupts_running_child() {
+        let control = ExecutionControl::new();
+        control.request_stop(Some("Arrêt demandé".into()));
+        let cmd = shell("sleep 30");
+        match run_process(cmd, None, &control, &mut |_| {}).unwrap() {
+            ProcessOutcome::Interrupted { reason } => assert_eq!(reason, "Arrêt demandé"),
+            _ => panic!("le processus aurait dû être interrompu"),
+        }
+    }
+
+    #[test]
+    fn pause_suspends_child_without_consuming_timeout() {
+        let control = ExecutionControl::new();
+        control.request_pause();
+        let resumer = {
+            let control = control.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(1500));
+                control.request_resume();
+            })
+        };
+        let cmd = shell("sleep 0.2; echo fini");
+        let outcome =
+            run_process(cmd, Some(Duration::from_secs(1)), &control, &mut |_| {}).unwrap();
+        resumer.join().unwrap();
+        match outcome {
+            ProcessOutcome::Completed(output) => assert_eq!(output.stdout, b"fini\n"),
+            _ => panic!("la pause ne doit pas compter dans le délai"),
+        }
+    }
+
+    #[test]
+    fn output_is_streamed_in_batches() {
+        let control = ExecutionControl::new();
+        let cmd = shell("echo un; echo deux >&2");
+        let mut lines = Vec::new();
+        let outcome = run_process(cmd, None, &control, &mut |batch| {
+            lines.extend(batch.lines);
+        })
+        .unwrap();
+        assert!(matches!(outcome, ProcessOutcome::Completed(_)));
+        let stdout: Vec<_> = lines
+            .iter()
+            .filter(|line| line.stream == OutputStream::Stdout)
+            .map(|line| line.text.as_str())
+            .collect();
+        let stderr: Vec<_> = lines
+            .iter()
+            .filter(|line| line.stream == OutputStream::Stderr)
+            .map(|line| line.text.as_str())
+            .collect();
+        assert_eq!(stdout, vec!["un"]);
+        assert_eq!(stderr, vec!["deux"]);
+    }
+
+    #[test]
+    fn quoting_follows_each_shell_rules() {
+        let name = "O'Brien; \"x\"";
+        assert_eq!(ShellQuoting::Posix.quote("Alice"), "Alice");
+        assert_eq!(ShellQuoting::Posix.quote(""), "''");
+        assert_eq!(ShellQuoting::Posix.quote(name), "'O'\\''Brien; \"x\"'");
+        assert_eq!(ShellQuoting::PowerShell.quote(name), "'O''Brien; \"x\"'");
+        assert_eq!(ShellQuoting::Cmd.quote(name), "\"O'Brien; \"\"x\"\"\"");
+    }
+}
@@ -0,0 +1,473 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::commandeur::models::{
+    CommandeurProcessArtifact, FolderRunStatus, OperationRunStatus, ValidationLevel,
+};
+use crate::commandeur::run_log::{read_run_log, RunLog, RunLogFolder};
+use crate::commandeur::storage::Storage;
+use crate::commandeur::summary::{folder_status_label, operation_status_label};
+use crate::commandeur::workspace::WorkspaceMode;
+
+const REPORT_FILE: &str = "report.html";
+/// Au-delà, la sortie d'un processus est tronquée dans le rapport; le fichier reste lié.
+const MAX_EMBEDDED_OUTPUT: usize = 64 * 1024;
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem auto;max-width:1100px;\
+color:#1f2937;padding:0 1rem}h1{margin-bottom:.25rem}h2{margin:0;font-size:1.1rem}\
+table{border-collapse:collapse;width:100%;font-size:.85rem;margin:.5rem 0}\
+th,td{text-align:left;padding:.3rem .5rem;border-bottom:1px solid #e5e7eb;vertical-align:top}\
+th{background:#f3f4f6}section{border:1px solid #e5e7eb;border-radius:.6rem;padding:1rem;\
+margin:1rem 0}pre{background:#f9fafb;padding:.6rem;overflow:auto;max-height:24rem;\
+font-size:.78rem}.badge{display:inline-block;border-radius:999px;padding:.1rem .55rem;\
+font-size:.75rem;font-weight:600;background:#f3f4f6}.ok{background:#dcfce7;color:#065f46}\
+.warn{background:#fef3c7;color:#92400e}.ko{background:#fee2e2;color:#991b1b}\
+.muted{color:#6b7280}summary{cursor:pointer}";
+
+/// Rapport HTML autonome (styles intégrés, sans script) écrit dans le dossier de l'exécution.
+pub fn write_html_report(run_dir: &Path, log: &RunLog) -> Result<PathBuf> {
+    let path = run_dir.join(REPORT_FILE);
+    fs::write(&path, render_html_report(run_dir, log))?;
+    Ok(path)
+}
+
+/// Régénère le rapport d'une exécution passée à partir de son journal structuré.
+pub fn regenerate_report(storage: &Storage, run_id: &str) -> Result<PathBuf> {
+    let run_dir = storage.existing_run_dir(run_id)?;
+    let log = read_run_log(&run_dir)?;
+    write_html_report(&run_dir, &log)
+}
+
+pub fn render_html_report(run_dir: &Path, log: &RunLog) -> String {
+    let mut html = String::new();
+    let title = format!("Rapport Commandeur · {}", log.workflow.name);
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"fr\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n",
+        escape(&title)
+    );
+    render_header(&mut html, log);
+    render_messages(&mut html, log);
+    for folder in &log.folders {
+        render_folder(&mut html, run_dir, log, folder);
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn render_header(html: &mut String, log: &RunLog) {
+    let succeeded = log
+        .folders
+        .iter()
+        .filter(|folder| folder.status == FolderRunStatus::Succeeded)
+        .count();
+    let (status_class, status_label) = match (&log.interrupted_reason, log.success) {
+        (Some(_), _) => ("warn", "Interrompue"),
+        (None, true) => ("ok", "Réussie"),
+        (None, false) => ("ko", "En échec"),
+    };
+    let _ = write!(
+        html,
+        "<h1>{}</h1>\n<p><span class=\"badge {status_class}\">{status_label}</span> \
+         <span class=\"muted\">Exécution {}</span></p>\n<table>\n",
+        escape(&log.workflow.name),
+        escape(&log.run_id)
+    );
+    let mut rows = vec![
+        (
+            "Version du workflow",
+            log.workflow.version.clone().unwrap_or_else(|| "-".into()),
+        ),
+        ("Empreinte du workflow", log.workflow.hash.clone()),
+        ("Début", log.started_at.clone()),
+        ("Fin", log.finished_at.clone()),
+        (
+            "Workspace",
+            format!(
+                "{} ({})",
+                log.workspace.source_path,
+                match log.workspace.mode {
+                    WorkspaceMode::Zip => "ZIP",
+                    WorkspaceMode::Directory => "Dossier",
+                }
+            ),
+        ),
+        (
+            "Dossiers réussis",
+            format!("{succeeded}/{}", log.folders.len()),
+        ),
+        (
+            "Avertissements / erreurs",
+            format!("{} / {}", log.warnings.len(), log.errors.len()),
+        ),
+    ];
+    if let Some(previous) = &log.resumed_from {
+        rows.push(("Reprise de", previous.clone()));
+    }
+    if let Some(reason) = &log.interrupted_reason {
+        rows.push(("Interruption", reason.clone()));
+    }
+    for (label, value) in rows {
+        let _ = writeln!(html, "<tr><th>{label}</th><td>{}</td></tr>", escape(&value));
+    }
+    html.push_str("</table>\n");
+}
+
+/// Messages qui ne concernent aucun dossier en particulier.
+fn render_messages(html: &mut String, log: &RunLog) {
+    let general: Vec<_> = log
+        .errors
+        .iter()
+        .chain(log.warnings.iter())
+        .filter(|message| {
+            message
+                .folders
+                .as_ref()
+                .map_or(true, |folders| folders.is_empty())
+        })
+        .collect();
+    if general.is_empty() {
+        return;
+    }
+    html.push_str("<section>\n<h2>Messages généraux</h2>\n<ul>\n");
+    for message in general {
+        let _ = writeln!(
+            html,
+            "<li><span class=\"badge {}\">{}</span> {}{}</li>",
+            level_class(message.level),
+            level_label(message.level),
+            escape(&message.message),
+            message
+                .details
+                .as_deref()
+                .map(|details| format!(" <span class=\"muted\">{}</span>", escape(details)))
+                .unwrap_or_default()
+        );
+    }
+    html.push_str("</ul>\n</section>\n");
+}
+
+fn render_folder(html: &mut String, run_dir: &Path, log: &RunLog, folder: &RunLogFolder) {
+    let status_class = match folder.status {
+        FolderRunStatus::Succeeded => "ok",
+        FolderRunStatus::Failed => "ko",
+        FolderRunStatus::Interrupted | FolderRunStatus::Skipped => "warn",
+    };
+    let _ = write!(
+        html,
+        "<section>\n<h2>{} <span class=\"badge {status_class}\">{}</span>",
+        escape(&folder.folder),
+        folder_status_label(folder.status)
+    );
+    if let Some(duration) = folder.duration_ms {
+        let _ = write!(html, " <span class=\"muted\">{duration} ms</span>");
+    }
+    html.push_str("</h2>\n");
+
+    render_operations(html, log, folder);
+
+    if !folder.conditions.is_empty() {
+        html.push_str("<h3>Conditions</h3>\n<ul>\n");
+        for condition in &folder.conditions {
+            let _ = writeln!(
+                html,
+                "<li>{} : {} → <strong>{}</strong></li>",
+                escape(&condition.operation_label),
+                escape(&condition.summary),
+                if condition.result { "then" } else { "else" }
+            );
+        }
+        html.push_str("</ul>\n");
+    }
+
+    if !folder.captured.is_empty() {
+        html.push_str("<h3>Valeurs capturées</h3>\n<table>\n");
+        for (name, value) in &folder.captured {
+            let _ = writeln!(
+                html,
+                "<tr><th>{}</th><td><code>{}</code></td></tr>",
+                escape(name),
+                escape(value)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    let artifacts: Vec<&CommandeurProcessArtifact> = log
+        .artifacts
+        .iter()
+        .filter(|artifact| artifact.folder == folder.folder)
+        .collect();
+    if !artifacts.is_empty() {
+        html.push_str("<h3>Processus</h3>\n");
+        for artifact in artifacts {
+            render_artifact(html, run_dir, artifact);
+        }
+    }
+
+    let entries: Vec<_> = log
+        .entries
+        .iter()
+        .filter(|entry| entry.folder.as_deref() == Some(folder.folder.as_str()))
+        .collect();
+    if !entries.is_empty() {
+        let _ = writeln!(
+            html,
+            "<details>\n<summary>Journal ({} entrée(s))</summary>\n<pre>",
+            entries.len()
+        );
+        for entry in entries {
+            let _ = writeln!(
+                html,
+                "{} [{}] {} · {}",
+                escape(&entry.timestamp),
+                level_label(entry.level),
+                escape(&entry.operation_label),
+                escape(&entry.message)
+            );
+        }
+        html.push_str("</pre>\n</details>\n");
+    }
+    html.push_str("</section>\n");
+}
+
+/// Tableau des opérations: bilan complet s'il est présent, opérations de premier niveau sinon
+/// (journaux au format 1).
+fn render_operations(html: &mut String, log: &RunLog, folder: &RunLogFolder) {
+    let row = log
+        .summary
+        .folders
+        .iter()
+        .find(|row| row.folder == folder.folder);
+    html.push_str(
+        "<table>\n<tr><th>Opération</th><th>Statut</th><th>Durée</th><th>Erreur</th></tr>\n",
+    );
+    match row {
+        Some(row) => {
+            for column in &log.summary.columns {
+                let cell = match row
+                    .cells
+                    .iter()
+                    .find(|cell| cell.operation_id == column.operation_id)
+                {
+                    Some(cell) => cell,
+                    None => continue,
+                };
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}{}</td><td><span class=\"badge {}\">{}</span></td>\
+                     <td>{}</td><td>{}</td></tr>",
+                    "&nbsp;&nbsp;".repeat(column.depth * 2),
+                    escape(&column.operation_label),
+                    operation_class(cell.status),
+                    operation_status_label(cell.status),
+                    cell.duration_ms
+                        .map(|duration| format!("{duration} ms"))
+                        .unwrap_or_default(),
+                    escape(cell.error.as_deref().unwrap_or_default())
+                );
+            }
+        }
+        None => {
+            for operation in &folder.operations {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td><span class=\"badge\">{}</span></td>\
+                     <td>{} ms</td><td></td></tr>",
+                    escape(&operation.operation_label),
+                    folder_status_label(operation.status),
+                    operation.duration_ms
+                );
+            }
+        }
+    }
+    html.push_str("</table>\n");
+}
+
+fn render_artifact(html: &mut String, run_dir: &Path, artifact: &CommandeurProcessArtifact) {
+    let status = if artifact.timed_out {
+        "délai dépassé".to_string()
+    } else {
+        artifact
+            .exit_code
+            .map(|code| format!("code {code}"))
+            .unwrap_or_else(|| "sans code".into())
+    };
+    let _ = writeln!(
+        html,
+        "<p><strong>{}</strong> · <code>{}</code> · {} · {} ms · \
+         <a href=\"{}\">stdout</a> · <a href=\"{}\">stderr</a> · <a href=\"{}\">résultat</a></p>",
+        escape(&artifact.operation_label),
+        escape(&artifact.command),
+        status,
+        artifact.duration_ms,
+        escape(&link(run_dir, &artifact.stdout_path)),
+        escape(&link(run_dir, &artifact.stderr_path)),
+        escape(&link(run_dir, &artifact.result_path))
+    );
+    for (label, path) in [
+        ("Sortie standard", &artifact.stdout_path),
+        ("Sortie d'erreur", &artifact.stderr_path),
+    ] {
+        let content = match embedded_output(path) {
+            Some(content) => content,
+            None => continue,
+        };
+        let _ = writeln!(
+            html,
+            "<details>\n<summary>{label}</summary>\n<pre>{}</pre>\n</details>",
+            escape(&content)
+        );
+    }
+}
+
+/// Contenu d'un fichier de sortie non vide, tronqué à `MAX_EMBEDDED_OUTPUT` octets.
+fn embedded_output(path: &str) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        return None;
+    }
+    if bytes.len() <= MAX_EMBEDDED_OUTPUT {
+        return Some(String::from_utf8_lossy(&bytes).to_string());
+    }
+    let mut content = String::from_utf8_lossy(&bytes[..MAX_EMBEDDED_OUTPUT]).to_string();
+    content.push_str("\n… (sortie tronquée, voir le fichier)");
+    Some(content)
+}
+
+/// Lien relatif au rapport pour les fichiers du dossier de l'exécution, absolu sinon.
+fn link(run_dir: &Path, path: &str) -> String {
+    match Path::new(path).strip_prefix(run_dir) {
+        Ok(relative) => relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => format!("file://{}", path.replace('\\', "/")),
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn operation_class(status: OperationRunStatus) -> &'static str {
+    match status {
+        OperationRunStatus::Ok => "ok",
+        OperationRunStatus::Failed => "ko",
+        OperationRunStatus::Interrupted => "warn",
+        OperationRunStatus::Skipped
+        | OperationRunStatus::Disabled
+        | OperationRunStatus::BranchNotTaken => "",
+    }
+}
+
+fn level_class(level: ValidationLevel) -> &'static str {
+    match level {
+        ValidationLevel::Info => "",
+        ValidationLevel::Warning => "warn",
+        ValidationLevel::Error => "ko",
+    }
+}
+
+fn level_label(level: ValidationLevel) -> &'static str {
+    match level {
+        ValidationLevel::Info => "info",
+        ValidationLevel::Warning => "avertissement",
+        ValidationLevel::Error => "erreur",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commandeur::run_log::{RunLogCondition, RunLogWorkflow, RunLogWorkspace};
+
+    #[test]
+    fn report_embeds_outcomes_conditions_and_artifact_output() {
+        let run_dir = tempfile::tempdir().unwrap();
+        let artifact_dir = run_dir.path().join("folders").join("Alice").join("001-run");
+        fs::create_dir_all(&artifact_dir).unwrap();
+        let stdout_path = artifact_dir.join("stdout.txt");
+        fs::write(&stdout_path, "12 < 20").unwrap();
+        let path_string = |name: &str| artifact_dir.join(name).to_string_lossy().to_string();
+
+        let log = RunLog {
+            format_version: 2,
+            run_id: "run-1".into(),
+            resumed_from: None,
+            started_at: "2024-03-01T10:00:00+00:00".into(),
+            finished_at: "2024-03-01T10:01:00+00:00".into(),
+            success: true,
+            interrupted_reason: None,
+            workflow: RunLogWorkflow {
+                name: "Corrections <TP1>".into(),
+                version: None,
+                hash: "0123456789abcdef".into(),
+            },
+            workspace: RunLogWorkspace {
+                source_path: "/rendus".into(),
+                root_path: "/rendus".into(),
+                mode: WorkspaceMode::Directory,
+                sub_folders: vec!["Alice".into()],
+            },
+            folders: vec![RunLogFolder {
+                folder: "Alice".into(),
+                status: FolderRunStatus::Succeeded,
+                duration_ms: Some(40),
+                operations: Vec::new(),
+                captured: [("note".to_string(), "12".to_string())]
+                    .into_iter()
+                    .collect(),
+                conditions: vec![RunLogCondition {
+                    operation_id: "check".into(),
+                    operation_label: "Vérifier".into(),
+                    summary: "main.c existe".into(),
+                    result: true,
+                }],
+            }],
+            entries: Vec::new(),
+            warnings: Vec::new(),
+            errors: Vec::new(),
+            timeouts: Vec::new(),
+            artifacts: vec![CommandeurProcessArtifact {
+                operation_id: "run".into(),
+                operation_label: "Lancer".into(),
+                folder: "Alice".into(),
+                command: "./main".into(),
+                exit_code: Some(0),
+                duration_ms: 8,
+                timed_out: false,
+                stdout_path: path_string("stdout.txt"),
+                stderr_path: path_string("stderr.txt"),
+                result_path: path_string("result.json"),
+            }],
+            summary: Default::default(),
+        };
+
+        let html = render_html_report(run_dir.path(), &log);
+        assert!(html.contains("<title>Rapport Commandeur · Corrections &lt;TP1&gt;</title>"));
+        assert!(html.contains("Vérifier : main.c existe → <strong>then</strong>"));
+        assert!(html.contains("<th>note</th><td><code>12</code></td>"));
+        assert!(html.contains("<a href=\"folders/Alice/001-run/stdout.txt\">stdout</a>"));
+        assert!(html.contains("<summary>Sortie standard</summary>\n<pre>12 &lt; 20</pre>"));
+        assert!(!html.contains("Sortie d'erreur"));
+        assert!(!html.contains("<script"));
+        assert_eq!(html, render_html_report(run_dir.path(), &log));
+    }
+}
@@ -44,13 +44,15 @@ pub fn push_folder_validation(
     entry
 }
 
-pub fn push_log(
+pub fn push_folder_log(
     entries: &mut Vec<CommandeurExecutionLogEntry>,
     operation: &CommandeurOperation,
+    folder: &str,
     level: ValidationLevel,
     message: impl Into<String>,
 ) -> CommandeurExecutionLogEntry {
-    let entry = CommandeurExecutionLogEntry::new(operation.id(), operation.label(), level, message);
+    let entry = CommandeurExecutionLogEntry::new(operation.id(), operation.label(), level, message)
+        .with_folder(folder);
     entries.push(entry.clone());
     entry
 }
@@ -59,10 +61,12 @@ pub fn push_log_with_meta(
     entries: &mut Vec<CommandeurExecutionLogEntry>,
     operation_id: &str,
     operation_label: &str,
+    folder: Option<&str>,
     level: ValidationLevel,
     message: impl Into<String>,
 ) -> CommandeurExecutionLogEntry {
-    let entry = CommandeurExecutionLogEntry::new(operation_id, operation_label, level, message);
+    let mut entry = CommandeurExecutionLogEntry::new(operation_id, operation_label, level, message);
+    entry.folder = folder.map(str::to_string);
     entries.push(entry.clone());
     entry
 }
@@ -76,8 +80,10 @@ pub fn push_workspace_log(
         timestamp: Utc::now().to_rfc3339(),
         operation_id: "__workspace__".into(),
         operation_label: "Workspace".into(),
+        folder: None,
         message: message.into(),
         level,
+        artifacts: Vec::new(),
     };
     entries.push(entry.clone());
     entry
@@ -0,0 +1,199 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::commandeur::models::{
+    CommandeurExecutionLogEntry, CommandeurExecutionSummary, CommandeurOperationTimeout,
+    CommandeurProcessArtifact, CommandeurValidationMessage, CommandeurWorkflow, FolderRunStatus,
+};
+use crate::commandeur::workspace::{WorkspaceHandle, WorkspaceMode};
+
+const RUN_LOG_FILE: &str = "run-log.json";
+pub const RUN_LOG_FORMAT_VERSION: u32 = 2;
+
+/// Journal structuré d'une exécution, écrit dans son dossier à côté du journal texte pour
+/// être agrégé par des scripts.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RunLog {
+    pub format_version: u32,
+    pub run_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resumed_from: Option<String>,
+    pub started_at: String,
+    pub finished_at: String,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interrupted_reason: Option<String>,
+    pub workflow: RunLogWorkflow,
+    pub workspace: RunLogWorkspace,
+    pub folders: Vec<RunLogFolder>,
+    pub entries: Vec<CommandeurExecutionLogEntry>,
+    pub warnings: Vec<CommandeurValidationMessage>,
+    pub errors: Vec<CommandeurValidationMessage>,
+    pub timeouts: Vec<CommandeurOperationTimeout>,
+    pub artifacts: Vec<CommandeurProcessArtifact>,
+    /// Absent des journaux au format 1.
+    #[serde(default)]
+    pub summary: CommandeurExecutionSummary,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RunLogWorkflow {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub hash: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RunLogWorkspace {
+    pub source_path: String,
+    pub root_path: String,
+    pub mode: WorkspaceMode,
+    pub sub_folders: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RunLogFolder {
+    pub folder: String,
+    pub status: FolderRunStatus,
+    /// Absent pour un dossier terminé lors d'une exécution précédente (reprise).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    pub operations: Vec<RunLogOperation>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub captured: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<RunLogCondition>,
+}
+
+/// Opération de premier niveau; une boucle ou une condition compte pour une opération.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RunLogOperation {
+    pub operation_id: String,
+    pub operation_label: String,
+    pub status: FolderRunStatus,
+    pub duration_ms: u64,
+}
+
+/// Décision d'une condition `if`, une par évaluation (plusieurs dans une boucle).
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RunLogCondition {
+    pub operation_id: String,
+    pub operation_label: String,
+    pub summary: String,
+    pub result: bool,
+}
+
+impl RunLogWorkflow {
+    pub fn new(workflow: &CommandeurWorkflow) -> Self {
+        Self {
+            name: workflow.name.clone(),
+            version: workflow.version.clone(),
+            hash: workflow_hash(workflow),
+        }
+    }
+}
+
+impl RunLogWorkspace {
+    pub fn new(workspace: &WorkspaceHandle) -> Self {
+        Self {
+            source_path: workspace.source_path.to_string_lossy().to_string(),
+            root_path: workspace.root_path.to_string_lossy().to_string(),
+            mode: workspace.mode.clone(),
+            sub_folders: workspace.sub_folders.clone(),
+        }
+    }
+}
+
+pub fn write_run_log(run_dir: &Path, log: &RunLog) -> Result<PathBuf> {
+    let path = run_dir.join(RUN_LOG_FILE);
+    fs::write(&path, serde_json::to_vec_pretty(log)?)?;
+    Ok(path)
+}
+
+pub fn read_run_log(run_dir: &Path) -> Result<RunLog> {
+    let content = fs::read_to_string(run_dir.join(RUN_LOG_FILE))
+        .context("Aucun journal structuré pour cette exécution")?;
+    serde_json::from_str(&content).context("Journal structuré illisible")
+}
+
+/// Empreinte FNV-1a (64 bits) du workflow sérialisé, clés triées: deux exécutions d'un
+/// même workflow partagent la même empreinte. Ce n'est pas une empreinte cryptographique.
+pub fn workflow_hash(workflow: &CommandeurWorkflow) -> String {
+    let mut canonical = String::new();
+    match serde_json::to_value(workflow) {
+        Ok(value) => write_canonical(&value, &mut canonical),
+        Err(_) => canonical.push_str(&workflow.name),
+    }
+    let hash = canonical
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (position, key) in keys.into_iter().enumerate() {
+                if position > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(&map[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (position, item) in items.iter().enumerate() {
+                if position > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workflow_hash_ignores_variable_order_but_not_content() {
+        let workflow = |variables: Value| -> CommandeurWorkflow {
+            serde_json::from_value(serde_json::json!({
+                "name": "Corrections",
+                "version": "1",
+                "operations": [],
+                "variables": variables
+            }))
+            .unwrap()
+        };
+        let first = workflow(serde_json::json!({ "a": "1", "b": "2", "c": "3" }));
+        let second = workflow(serde_json::json!({ "c": "3", "b": "2", "a": "1" }));
+        let changed = workflow(serde_json::json!({ "a": "1", "b": "2", "c": "4" }));
+        assert_eq!(workflow_hash(&first), workflow_hash(&second));
+        assert_ne!(workflow_hash(&first), workflow_hash(&changed));
+        assert_eq!(workflow_hash(&first).len(), 16);
+    }
+}
@@ -6,7 +6,7 @@ use anyhow::{Context, Result};
 use chrono::Utc;
 
 use crate::commandeur::models::{CommandeurWorkflow, SavedWorkflowSummary};
-use crate::commandeur::storage;
+use crate::commandeur::storage::Storage;
 
 #[derive(serde::Serialize, serde::Deserialize)]
 struct StoredWorkflow {
@@ -37,12 +37,13 @@ impl StoredWorkflow {
     }
 }
 
-fn workflow_file_path(id: &str) -> Result<PathBuf> {
-    let dir = storage::workflows_dir()?;
+fn workflow_file_path(storage: &Storage, id: &str) -> Result<PathBuf> {
+    let dir = storage.workflows_dir()?;
     Ok(dir.join(format!("{}.json", id)))
 }
 
 pub fn save_workflow(
+    storage: &Storage,
     workflow: &CommandeurWorkflow,
     existing_id: Option<String>,
 ) -> Result<SavedWorkflowSummary> {
@@ -53,7 +54,7 @@ pub fn save_workflow(
 
     let name = workflow.name.clone();
     let stored = StoredWorkflow::new(id.clone(), name, workflow.clone());
-    let file_path = workflow_file_path(&id)?;
+    let file_path = workflow_file_path(storage, &id)?;
     if let Some(parent) = file_path.parent() {
         fs::create_dir_all(parent)?;
     }
@@ -64,8 +65,8 @@ pub fn save_workflow(
     Ok(stored.summary())
 }
 
-fn read_stored_workflow(id: &str) -> Result<StoredWorkflow> {
-    let file_path = workflow_file_path(id)?;
+fn read_stored_workflow(storage: &Storage, id: &str) -> Result<StoredWorkflow> {
+    let file_path = workflow_file_path(storage, id)?;
     let content = fs::read_to_string(&file_path)
         .with_context(|| format!("Impossible d'ouvrir le workflow sauvegardé {}", id))?;
     let stored: StoredWorkflow = serde_json::from_str(&content)
@@ -73,8 +74,8 @@ fn read_stored_workflow(id: &str) -> Result<StoredWorkflow> {
     Ok(stored)
 }
 
-pub fn list_workflows() -> Result<Vec<SavedWorkflowSummary>> {
-    let dir = storage::workflows_dir()?;
+pub fn list_workflows(storage: &Storage) -> Result<Vec<SavedWorkflowSummary>> {
+    let dir = storage.workflows_dir()?;
     let mut summaries = Vec::new();
     if dir.exists() {
         for entry in fs::read_dir(&dir)? {
@@ -98,25 +99,25 @@ pub fn list_workflows() -> Result<Vec<SavedWorkflowSummary>> {
     Ok(summaries)
 }
 
-pub fn load_workflow(id: &str) -> Result<CommandeurWorkflow> {
-    let stored = read_stored_workflow(id)?;
+pub fn load_workflow(storage: &Storage, id: &str) -> Result<CommandeurWorkflow> {
+    let stored = read_stored_workflow(storage, id)?;
     Ok(stored.workflow)
 }
 
-pub fn delete_workflow(id: &str) -> Result<()> {
-    let file_path = workflow_file_path(id)?;
+pub fn delete_workflow(storage: &Storage, id: &str) -> Result<()> {
+    let file_path = workflow_file_path(storage, id)?;
     if file_path.exists() {
         fs::remove_file(&file_path)?;
     }
     Ok(())
 }
 
-pub fn duplicate_workflow(id: &str) -> Result<SavedWorkflowSummary> {
-    let stored = read_stored_workflow(id)?;
+pub fn duplicate_workflow(storage: &Storage, id: &str) -> Result<SavedWorkflowSummary> {
+    let stored = read_stored_workflow(storage, id)?;
     let mut workflow = stored.workflow.clone();
     let name = workflow.name.trim();
     if !name.is_empty() {
         workflow.name = format!("{} (copie)", name);
     }
-    save_workflow(&workflow, None)
+    save_workflow(storage, &workflow, None)
 }
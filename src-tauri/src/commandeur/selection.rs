@@ -0,0 +1,228 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::commandeur::conditions::{commands_unavailable, evaluate_condition_for_folder};
+use crate::commandeur::globs::{FileMatcher, GlobOptions};
+use crate::commandeur::models::{
+    CommandeurFolderStatus, FolderRunStatus, FolderSelection, NamePatternMode,
+};
+use crate::commandeur::storage::Storage;
+use crate::commandeur::utils::build_regex;
+use crate::commandeur::workspace::{AppState, WorkspaceHandle};
+
+const FOLDER_STATUS_FILE: &str = "folders.json";
+
+/// Sous-dossiers retenus par la sélection, avec leur rang dans le workspace (pour `{{index}}`).
+/// Les critères renseignés se cumulent.
+pub fn select_folders(
+    storage: &Storage,
+    workspace: &WorkspaceHandle,
+    selection: &FolderSelection,
+) -> Result<Vec<(usize, String)>> {
+    let explicit: Vec<&str> = selection
+        .folders
+        .iter()
+        .map(|folder| folder.trim())
+        .filter(|folder| !folder.is_empty())
+        .collect();
+    if let Some(unknown) = explicit
+        .iter()
+        .find(|folder| !workspace.sub_folders.iter().any(|known| known == *folder))
+    {
+        return Err(anyhow!("Dossier inconnu dans la sélection: {unknown}"));
+    }
+
+    let pattern = selection
+        .name_pattern
+        .as_deref()
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty());
+    let name_matches: Box<dyn Fn(&str) -> bool> = match (pattern, selection.name_pattern_mode) {
+        (None, _) => Box::new(|_| true),
+        (Some(pattern), NamePatternMode::Glob) => {
+            let options = GlobOptions {
+                recursive: false,
+                case_insensitive: selection.case_insensitive,
+            };
+            let matcher = FileMatcher::new(pattern, options)?;
+            Box::new(move |name| matcher.is_match(name))
+        }
+        (Some(pattern), NamePatternMode::Regex) => {
+            let flags = if selection.case_insensitive {
+                Some("i")
+            } else {
+                None
+            };
+            let regex = build_regex(pattern, flags)?;
+            Box::new(move |name| regex.is_match(name))
+        }
+    };
+
+    let retry = match selection
+        .failed_in_run
+        .as_deref()
+        .map(str::trim)
+        .filter(|run_id| !run_id.is_empty())
+    {
+        Some(run_id) => Some(folders_to_retry(storage, run_id)?),
+        None => None,
+    };
+
+    let mut selected = Vec::new();
+    for (index, folder) in workspace.sub_folders.iter().enumerate() {
+        if !explicit.is_empty() && !explicit.contains(&folder.as_str()) {
+            continue;
+        }
+        if !name_matches(folder) {
+            continue;
+        }
+        if let Some(retry) = &retry {
+            if !retry.contains(folder) {
+                continue;
+            }
+        }
+        if let Some(condition) = &selection.condition {
+            let evaluation = evaluate_condition_for_folder(
+                &workspace.folder_absolute_path(folder),
+                folder,
+                condition,
+                &Default::default(),
+                &mut commands_unavailable,
+            )
+            .map_err(|err| anyhow!("Condition de sélection pour {folder}: {err}"))?;
+            if !evaluation.result {
+                continue;
+            }
+        }
+        selected.push((index, folder.clone()));
+    }
+    Ok(selected)
+}
+
+/// Aperçu des dossiers retenus, pour l'écran d'exécution.
+pub fn preview_selection(
+    state: &AppState,
+    workspace_id: &str,
+    selection: &FolderSelection,
+) -> Result<Vec<String>> {
+    let workspace_arc = state.get_workspace(workspace_id)?;
+    let workspace = workspace_arc
+        .lock()
+        .map_err(|_| anyhow!("Accès concurrent au workspace"))?;
+    Ok(select_folders(state.storage(), &workspace, selection)?
+        .into_iter()
+        .map(|(_, folder)| folder)
+        .collect())
+}
+
+pub fn write_folder_statuses(run_dir: &Path, statuses: &[CommandeurFolderStatus]) -> Result<()> {
+    fs::write(
+        run_dir.join(FOLDER_STATUS_FILE),
+        serde_json::to_vec_pretty(statuses)?,
+    )?;
+    Ok(())
+}
+
+/// Dossiers à relancer après l'exécution `run_id`: tous ceux qui n'ont pas réussi
+/// (échec, interruption ou non traités).
+fn folders_to_retry(storage: &Storage, run_id: &str) -> Result<Vec<String>> {
+    let path = storage.existing_run_dir(run_id)?.join(FOLDER_STATUS_FILE);
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Aucun bilan par dossier pour l'exécution {run_id}"))?;
+    let statuses: Vec<CommandeurFolderStatus> =
+        serde_json::from_str(&content).context("Bilan par dossier illisible")?;
+    Ok(statuses
+        .into_iter()
+        .filter(|status| status.status != FolderRunStatus::Succeeded)
+        .map(|status| status.folder)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commandeur::workspace::WorkspaceMode;
+    use chrono::Utc;
+
+    #[test]
+    fn selection_criteria_are_combined_and_keep_workspace_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let sub_folders: Vec<String> = ["Alice_TP1", "Bob_TP1", "Carla_TP2", "David_TP1"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        for folder in &sub_folders {
+            fs::create_dir_all(dir.path().join(folder)).unwrap();
+        }
+        fs::write(dir.path().join("Bob_TP1/rapport.pdf"), "").unwrap();
+        fs::write(dir.path().join("David_TP1/rapport.pdf"), "").unwrap();
+        let workspace = WorkspaceHandle {
+            id: "ws".into(),
+            mode: WorkspaceMode::Directory,
+            source_path: dir.path().to_path_buf(),
+            root_path: dir.path().to_path_buf(),
+            extracted: None,
+            created_at: Utc::now(),
+            sub_folders,
+            expanded_archives: Vec::new(),
+        };
+        let storage = Storage::at(dir.path().join("donnees"));
+
+        let by_glob = FolderSelection {
+            name_pattern: Some("*_TP1".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            select_folders(&storage, &workspace, &by_glob).unwrap(),
+            vec![
+                (0, "Alice_TP1".to_string()),
+                (1, "Bob_TP1".to_string()),
+                (3, "David_TP1".to_string())
+            ]
+        );
+
+        let combined: FolderSelection = serde_json::from_value(serde_json::json!({
+            "namePattern": "^(Bob|Carla|David)_",
+            "namePatternMode": "regex",
+            "folders": ["Bob_TP1", "Carla_TP2", "David_TP1"],
+            "condition": {
+                "selector": "file-search",
+                "operator": "exists",
+                "pattern": "rapport.pdf"
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            select_folders(&storage, &workspace, &combined).unwrap(),
+            vec![(1, "Bob_TP1".to_string()), (3, "David_TP1".to_string())]
+        );
+
+        let ignoring_case = FolderSelection {
+            name_pattern: Some("alice*; carla*".into()),
+            case_insensitive: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            select_folders(&storage, &workspace, &ignoring_case).unwrap(),
+            vec![(0, "Alice_TP1".to_string()), (2, "Carla_TP2".to_string())]
+        );
+        let regex_ignoring_case = FolderSelection {
+            name_pattern: Some("_tp2$".into()),
+            name_pattern_mode: NamePatternMode::Regex,
+            case_insensitive: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            select_folders(&storage, &workspace, &regex_ignoring_case).unwrap(),
+            vec![(2, "Carla_TP2".to_string())]
+        );
+
+        let unknown = FolderSelection {
+            folders: vec!["Zoé".into()],
+            ..Default::default()
+        };
+        assert!(select_folders(&storage, &workspace, &unknown).is_err());
+    }
+}
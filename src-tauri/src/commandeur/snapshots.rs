@@ -0,0 +1,228 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::commandeur::models::CommandeurSnapshotRestoreResult;
+use crate::commandeur::storage::Storage;
+use crate::commandeur::utils::copy_dir_recursive;
+use crate::commandeur::workspace::{AppState, WorkspaceHandle};
+
+const MANIFEST_FILE: &str = "manifest.json";
+const FOLDERS_DIR: &str = "folders";
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnapshotManifest {
+    version: u32,
+    run_id: String,
+    workspace_source: String,
+    root_path: String,
+    created_at: String,
+    folders: Vec<String>,
+}
+
+fn snapshot_dir(storage: &Storage, run_id: &str) -> Result<PathBuf> {
+    if run_id.trim().is_empty()
+        || run_id
+            .chars()
+            .any(|c| !(c.is_ascii_alphanumeric() || c == '-'))
+    {
+        return Err(anyhow!("Identifiant d'exécution invalide: {}", run_id));
+    }
+    Ok(storage.snapshots_dir()?.join(run_id))
+}
+
+/// Copie les sous-dossiers qui vont être traités avant le démarrage d'une exécution.
+pub fn create_snapshot(
+    storage: &Storage,
+    run_id: &str,
+    workspace: &WorkspaceHandle,
+    folders: &[String],
+) -> Result<PathBuf> {
+    let dir = snapshot_dir(storage, run_id)?;
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    let folders_root = dir.join(FOLDERS_DIR);
+    fs::create_dir_all(&folders_root)?;
+
+    let mut copied = Vec::new();
+    for folder in folders {
+        let source = workspace.folder_absolute_path(folder);
+        if !source.is_dir() {
+            continue;
+        }
+        copy_dir_recursive(&source, &folders_root.join(folder))
+            .with_context(|| format!("Impossible de copier le dossier {}", folder))?;
+        copied.push(folder.clone());
+    }
+
+    let manifest = SnapshotManifest {
+        version: 1,
+        run_id: run_id.to_string(),
+        workspace_source: workspace.source_path.to_string_lossy().to_string(),
+        root_path: workspace.root_path.to_string_lossy().to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        folders: copied,
+    };
+    fs::write(
+        dir.join(MANIFEST_FILE),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    Ok(dir)
+}
+
+fn read_manifest(dir: &Path) -> Result<SnapshotManifest> {
+    let content = fs::read_to_string(dir.join(MANIFEST_FILE))
+        .with_context(|| format!("Aucun instantané trouvé dans {}", dir.display()))?;
+    serde_json::from_str(&content).context("Manifeste d'instantané illisible")
+}
+
+/// Restaure le workspace (ou seulement `folders`) dans l'état capturé avant l'exécution `run_id`.
+pub fn restore_snapshot(
+    state: &AppState,
+    workspace_id: &str,
+    run_id: &str,
+    folders: Option<Vec<String>>,
+) -> Result<CommandeurSnapshotRestoreResult> {
+    if state.has_active_execution()? {
+        return Err(anyhow!(
+            "Impossible de restaurer pendant qu'une exécution est en cours"
+        ));
+    }
+    let dir = snapshot_dir(state.storage(), run_id)?;
+    let manifest = read_manifest(&dir)?;
+
+    let workspace_arc = state.get_workspace(workspace_id)?;
+    let workspace = workspace_arc
+        .lock()
+        .map_err(|_| anyhow!("Accès concurrent au workspace"))?;
+    if workspace.source_path.to_string_lossy() != manifest.workspace_source {
+        return Err(anyhow!(
+            "Cet instantané a été pris sur un autre workspace ({})",
+            manifest.workspace_source
+        ));
+    }
+
+    let requested = folders.unwrap_or_else(|| manifest.folders.clone());
+    let mut restored_folders = Vec::new();
+    let mut skipped_folders = Vec::new();
+    for folder in requested {
+        if !manifest.folders.contains(&folder) {
+            skipped_folders.push(folder);
+            continue;
+        }
+        let saved = dir.join(FOLDERS_DIR).join(&folder);
+        let target = workspace.folder_absolute_path(&folder);
+        restore_folder(&saved, &target)
+            .with_context(|| format!("Impossible de restaurer le dossier {}", folder))?;
+        restored_folders.push(folder);
+    }
+
+    Ok(CommandeurSnapshotRestoreResult {
+        run_id: manifest.run_id,
+        restored_folders,
+        skipped_folders,
+    })
+}
+
+/// Copie l'instantané à côté du dossier puis l'échange avec lui: un échec en cours de copie
+/// laisse le dossier du workspace intact.
+fn restore_folder(saved: &Path, target: &Path) -> Result<()> {
+    let name = target
+        .file_name()
+        .ok_or_else(|| anyhow!("Dossier invalide: {}", target.display()))?
+        .to_string_lossy()
+        .to_string();
+    let staging = target.with_file_name(format!(".{name}.raisin-restore"));
+    let previous = target.with_file_name(format!(".{name}.raisin-previous"));
+    for leftover in [&staging, &previous] {
+        if fs::symlink_metadata(leftover).is_ok() {
+            fs::remove_dir_all(leftover)?;
+        }
+    }
+    if let Err(err) = copy_dir_recursive(saved, &staging) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(err);
+    }
+    let had_target = fs::symlink_metadata(target).is_ok();
+    if had_target {
+        fs::rename(target, &previous)?;
+    }
+    if let Err(err) = fs::rename(&staging, target) {
+        if had_target {
+            let _ = fs::rename(&previous, target);
+        }
+        let _ = fs::remove_dir_all(&staging);
+        return Err(err.into());
+    }
+    if had_target {
+        fs::remove_dir_all(&previous)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commandeur::workspace::{new_id, WorkspaceMode};
+
+    #[cfg(unix)]
+    #[test]
+    fn snapshot_keeps_symlinks_and_restore_replaces_the_folder() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("rendus");
+        let alice = root.join("Alice");
+        fs::create_dir_all(alice.join("node_modules/outil/bin")).unwrap();
+        fs::write(alice.join("node_modules/outil/bin/cli.js"), "cli").unwrap();
+        fs::create_dir_all(alice.join("node_modules/.bin")).unwrap();
+        symlink("../outil/bin", alice.join("node_modules/.bin/outil")).unwrap();
+        symlink("absent.txt", alice.join("orphelin")).unwrap();
+        fs::write(alice.join("main.c"), "avant").unwrap();
+        let workspace = WorkspaceHandle {
+            id: "ws".into(),
+            mode: WorkspaceMode::Directory,
+            source_path: root.clone(),
+            root_path: root.clone(),
+            extracted: None,
+            created_at: Utc::now(),
+            sub_folders: vec!["Alice".into()],
+            expanded_archives: Vec::new(),
+        };
+
+        let storage = Storage::at(dir.path().join("donnees"));
+        let snapshot =
+            create_snapshot(&storage, &new_id(), &workspace, &["Alice".to_string()]).unwrap();
+        let saved = snapshot.join(FOLDERS_DIR).join("Alice");
+        assert_eq!(
+            fs::read_link(saved.join("node_modules/.bin/outil")).unwrap(),
+            Path::new("../outil/bin")
+        );
+        assert_eq!(
+            fs::read_link(saved.join("orphelin")).unwrap(),
+            Path::new("absent.txt")
+        );
+
+        fs::write(alice.join("main.c"), "après").unwrap();
+        fs::write(alice.join("a.out"), "binaire").unwrap();
+        restore_folder(&saved, &alice).unwrap();
+        assert_eq!(fs::read_to_string(alice.join("main.c")).unwrap(), "avant");
+        assert!(!alice.join("a.out").exists());
+        assert!(alice.join("node_modules/.bin/outil/cli.js").is_file());
+        assert!(fs::symlink_metadata(alice.join("orphelin"))
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        let leftovers: Vec<_> = fs::read_dir(&root).unwrap().collect();
+        assert_eq!(leftovers.len(), 1);
+
+        // Une copie impossible laisse le dossier du workspace tel quel.
+        assert!(restore_folder(&snapshot.join("absent"), &alice).is_err());
+        assert_eq!(fs::read_to_string(alice.join("main.c")).unwrap(), "avant");
+    }
+}
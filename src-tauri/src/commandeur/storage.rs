@@ -2,33 +2,75 @@ use std::env;
 use std::fs;
 use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use tauri::api::path::config_dir;
 
 const APP_FOLDER: &str = "Raisin";
 const MODULE_FOLDER: &str = "commandeur";
 const LOGS_FOLDER: &str = "logs";
+const RUNS_FOLDER: &str = "runs";
+const SNAPSHOTS_FOLDER: &str = "snapshots";
 const WORKFLOWS_FOLDER: &str = "workflows";
 
-fn base_dir() -> Result<PathBuf> {
-    let mut dir =
-        config_dir().unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-    dir.push(APP_FOLDER);
-    dir.push(MODULE_FOLDER);
-    fs::create_dir_all(&dir)?;
-    Ok(dir)
+/// Dossier où le module range journaux, exécutions, instantanés et workflows enregistrés.
+#[derive(Debug, Clone)]
+pub struct Storage {
+    root: PathBuf,
 }
 
-pub fn logs_dir() -> Result<PathBuf> {
-    let mut dir = base_dir()?;
-    dir.push(LOGS_FOLDER);
-    fs::create_dir_all(&dir)?;
-    Ok(dir)
-}
+impl Storage {
+    /// Dossier de configuration de l'utilisateur.
+    pub fn user() -> Self {
+        let mut root = config_dir()
+            .unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+        root.push(APP_FOLDER);
+        root.push(MODULE_FOLDER);
+        Self::at(root)
+    }
+
+    pub fn at(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn subdir(&self, name: &str) -> Result<PathBuf> {
+        let dir = self.root.join(name);
+        fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    pub fn logs_dir(&self) -> Result<PathBuf> {
+        self.subdir(LOGS_FOLDER)
+    }
+
+    pub fn run_dir(&self, run_id: &str) -> Result<PathBuf> {
+        let mut dir = self.logs_dir()?;
+        dir.push(RUNS_FOLDER);
+        dir.push(run_id);
+        fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// Dossier d'une exécution passée; erreur si l'identifiant est invalide ou inconnu.
+    pub fn existing_run_dir(&self, run_id: &str) -> Result<PathBuf> {
+        if run_id.trim().is_empty()
+            || run_id
+                .chars()
+                .any(|c| !(c.is_ascii_alphanumeric() || c == '-'))
+        {
+            return Err(anyhow!("Identifiant d'exécution invalide: {run_id}"));
+        }
+        let dir = self.logs_dir()?.join(RUNS_FOLDER).join(run_id);
+        if !dir.is_dir() {
+            return Err(anyhow!("Exécution introuvable: {run_id}"));
+        }
+        Ok(dir)
+    }
+
+    pub fn workflows_dir(&self) -> Result<PathBuf> {
+        self.subdir(WORKFLOWS_FOLDER)
+    }
 
-pub fn workflows_dir() -> Result<PathBuf> {
-    let mut dir = base_dir()?;
-    dir.push(WORKFLOWS_FOLDER);
-    fs::create_dir_all(&dir)?;
-    Ok(dir)
+    pub fn snapshots_dir(&self) -> Result<PathBuf> {
+        self.subdir(SNAPSHOTS_FOLDER)
+    }
 }
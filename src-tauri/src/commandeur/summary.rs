@@ -0,0 +1,398 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::commandeur::history::load_run_result;
+use crate::commandeur::models::{
+    CommandeurExecutionSummary, CommandeurFolderSummary, CommandeurOperation,
+    CommandeurSummaryCell, CommandeurSummaryColumn, FolderRunStatus, OperationDetails,
+    OperationRunStatus,
+};
+use crate::commandeur::storage::Storage;
+
+const SUMMARY_CSV_FILE: &str = "summary.csv";
+const CSV_SEPARATOR: char = ';';
+
+/// Statuts des opérations d'un dossier, consignés au fil de l'exécution.
+#[derive(Debug, Default)]
+pub struct FolderCells {
+    cells: HashMap<String, CommandeurSummaryCell>,
+}
+
+impl FolderCells {
+    /// Une opération rejouée (dans une boucle) cumule ses durées et garde le statut le plus
+    /// grave ainsi que la première erreur.
+    pub fn record(
+        &mut self,
+        operation_id: &str,
+        status: OperationRunStatus,
+        duration_ms: Option<u64>,
+        error: Option<String>,
+    ) {
+        match self.cells.get_mut(operation_id) {
+            Some(cell) => {
+                if severity(status) > severity(cell.status) {
+                    cell.status = status;
+                }
+                cell.duration_ms = match (cell.duration_ms, duration_ms) {
+                    (Some(total), Some(duration)) => Some(total + duration),
+                    (total, duration) => total.or(duration),
+                };
+                if cell.error.is_none() {
+                    cell.error = error;
+                }
+            }
+            None => {
+                self.cells.insert(
+                    operation_id.to_string(),
+                    CommandeurSummaryCell {
+                        operation_id: operation_id.to_string(),
+                        status,
+                        duration_ms,
+                        error,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Marque les opérations de la branche écartée par une condition, enfants compris.
+    pub fn branch_not_taken(&mut self, operations: &[CommandeurOperation]) {
+        for operation in operations.iter().filter(|operation| operation.enabled()) {
+            self.record(
+                operation.id(),
+                OperationRunStatus::BranchNotTaken,
+                None,
+                None,
+            );
+            for children in child_operations(operation) {
+                self.branch_not_taken(children);
+            }
+        }
+    }
+
+    /// Reprend d'une exécution précédente le bilan d'une opération déjà effectuée.
+    pub fn restore(
+        &mut self,
+        previous: Option<&CommandeurFolderSummary>,
+        operation: &CommandeurOperation,
+    ) {
+        let cell = previous.and_then(|previous| {
+            previous
+                .cells
+                .iter()
+                .find(|cell| cell.operation_id == operation.id())
+        });
+        match cell {
+            Some(cell) => {
+                self.cells.insert(cell.operation_id.clone(), cell.clone());
+            }
+            None => self.record(operation.id(), OperationRunStatus::Skipped, None, None),
+        }
+        for children in child_operations(operation) {
+            for child in children {
+                self.restore(previous, child);
+            }
+        }
+    }
+}
+
+/// Colonnes du tableau: toutes les opérations du workflow, imbriquées comprises, dans l'ordre.
+pub fn summary_columns(operations: &[CommandeurOperation]) -> Vec<CommandeurSummaryColumn> {
+    let mut columns = Vec::new();
+    push_columns(operations, 0, &mut columns);
+    columns
+}
+
+/// Ligne d'un dossier: une cellule par opération, `skipped` pour celles jamais atteintes.
+pub fn folder_summary(
+    operations: &[CommandeurOperation],
+    folder: &str,
+    status: FolderRunStatus,
+    duration_ms: Option<u64>,
+    mut cells: FolderCells,
+) -> CommandeurFolderSummary {
+    let mut row = Vec::new();
+    push_cells(operations, false, &mut cells, &mut row);
+    CommandeurFolderSummary {
+        folder: folder.to_string(),
+        status,
+        duration_ms,
+        cells: row,
+    }
+}
+
+pub fn write_summary_csv(run_dir: &Path, summary: &CommandeurExecutionSummary) -> Result<PathBuf> {
+    let path = run_dir.join(SUMMARY_CSV_FILE);
+    fs::write(&path, summary_csv(summary))?;
+    Ok(path)
+}
+
+/// Enregistre le tableau d'une exécution passée à l'emplacement choisi.
+pub fn export_run_summary(storage: &Storage, run_id: &str, destination: &str) -> Result<()> {
+    let result = load_run_result(storage, run_id)?;
+    fs::write(destination, summary_csv(&result.summary))
+        .with_context(|| format!("Impossible d'écrire {destination}"))
+}
+
+/// Tableau d'une ligne par dossier, séparé par des points-virgules et précédé d'un BOM pour
+/// qu'un tableur français l'ouvre directement.
+pub fn summary_csv(summary: &CommandeurExecutionSummary) -> String {
+    let mut header = vec![
+        "Dossier".to_string(),
+        "Statut".to_string(),
+        "Durée (ms)".to_string(),
+    ];
+    for column in &summary.columns {
+        header.push(column.operation_label.clone());
+        header.push(format!("{} - durée (ms)", column.operation_label));
+        header.push(format!("{} - erreur", column.operation_label));
+    }
+
+    let mut csv = String::from('\u{feff}');
+    push_record(&mut csv, &header);
+    for folder in &summary.folders {
+        let mut record = vec![
+            folder.folder.clone(),
+            folder_status_label(folder.status).to_string(),
+            folder
+                .duration_ms
+                .map(|duration| duration.to_string())
+                .unwrap_or_default(),
+        ];
+        for column in &summary.columns {
+            let cell = folder
+                .cells
+                .iter()
+                .find(|cell| cell.operation_id == column.operation_id);
+            record.push(
+                cell.map(|cell| operation_status_label(cell.status))
+                    .unwrap_or_default()
+                    .to_string(),
+            );
+            record.push(
+                cell.and_then(|cell| cell.duration_ms)
+                    .map(|duration| duration.to_string())
+                    .unwrap_or_default(),
+            );
+            record.push(cell.and_then(|cell| cell.error.clone()).unwrap_or_default());
+        }
+        push_record(&mut csv, &record);
+    }
+    csv
+}
+
+fn push_columns(
+    operations: &[CommandeurOperation],
+    depth: usize,
+    columns: &mut Vec<CommandeurSummaryColumn>,
+) {
+    for operation in operations {
+        columns.push(CommandeurSummaryColumn {
+            operation_id: operation.id().to_string(),
+            operation_label: operation.label().to_string(),
+            depth,
+        });
+        for children in child_operations(operation) {
+            push_columns(children, depth + 1, columns);
+        }
+    }
+}
+
+fn push_cells(
+    operations: &[CommandeurOperation],
+    parent_disabled: bool,
+    cells: &mut FolderCells,
+    row: &mut Vec<CommandeurSummaryCell>,
+) {
+    for operation in operations {
+        let disabled = parent_disabled || !operation.enabled();
+        let cell = if disabled {
+            None
+        } else {
+            cells.cells.remove(operation.id())
+        };
+        row.push(cell.unwrap_or_else(|| CommandeurSummaryCell {
+            operation_id: operation.id().to_string(),
+            status: if disabled {
+                OperationRunStatus::Disabled
+            } else {
+                OperationRunStatus::Skipped
+            },
+            duration_ms: None,
+            error: None,
+        }));
+        for children in child_operations(operation) {
+            push_cells(children, disabled, cells, row);
+        }
+    }
+}
+
+fn child_operations(operation: &CommandeurOperation) -> Vec<&[CommandeurOperation]> {
+    match &operation.details {
+        OperationDetails::If {
+            then, else_branch, ..
+        } => {
+            let mut children = vec![then.as_slice()];
+            if let Some(else_branch) = else_branch {
+                children.push(else_branch.as_slice());
+            }
+            children
+        }
+        OperationDetails::ForEach { operations, .. } => vec![operations.as_slice()],
+        _ => Vec::new(),
+    }
+}
+
+fn severity(status: OperationRunStatus) -> u8 {
+    match status {
+        OperationRunStatus::Disabled => 0,
+        OperationRunStatus::BranchNotTaken => 1,
+        OperationRunStatus::Skipped => 2,
+        OperationRunStatus::Ok => 3,
+        OperationRunStatus::Interrupted => 4,
+        OperationRunStatus::Failed => 5,
+    }
+}
+
+pub fn folder_status_label(status: FolderRunStatus) -> &'static str {
+    match status {
+        FolderRunStatus::Succeeded => "réussi",
+        FolderRunStatus::Failed => "échec",
+        FolderRunStatus::Interrupted => "interrompu",
+        FolderRunStatus::Skipped => "non traité",
+    }
+}
+
+pub fn operation_status_label(status: OperationRunStatus) -> &'static str {
+    match status {
+        OperationRunStatus::Ok => "ok",
+        OperationRunStatus::Failed => "échec",
+        OperationRunStatus::Interrupted => "interrompue",
+        OperationRunStatus::Skipped => "ignorée",
+        OperationRunStatus::Disabled => "désactivée",
+        OperationRunStatus::BranchNotTaken => "branche non prise",
+    }
+}
+
+fn push_record(csv: &mut String, fields: &[String]) {
+    let escaped: Vec<String> = fields.iter().map(|field| escape_field(field)).collect();
+    csv.push_str(&escaped.join(&CSV_SEPARATOR.to_string()));
+    csv.push_str("\r\n");
+}
+
+fn escape_field(field: &str) -> String {
+    if field.contains(&[CSV_SEPARATOR, '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operations() -> Vec<CommandeurOperation> {
+        serde_json::from_value(serde_json::json!([
+            {
+                "id": "compile",
+                "label": "Compiler",
+                "kind": "exec",
+                "command": "make",
+                "shell": "default"
+            },
+            {
+                "id": "check",
+                "label": "Vérifier",
+                "kind": "if",
+                "test": { "exists": "main.c" },
+                "then": [
+                    {
+                        "id": "run",
+                        "label": "Lancer; tester",
+                        "kind": "exec",
+                        "command": "./main",
+                        "shell": "default"
+                    }
+                ],
+                "else": [
+                    {
+                        "id": "note",
+                        "label": "Noter",
+                        "kind": "create-file",
+                        "target": "absent.txt",
+                        "content": ""
+                    }
+                ]
+            },
+            {
+                "id": "clean",
+                "label": "Nettoyer",
+                "kind": "delete-file",
+                "target": "a.out",
+                "enabled": false
+            }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn matrix_covers_nested_operations_and_exports_csv() {
+        let operations = operations();
+        let columns = summary_columns(&operations);
+        let ids: Vec<&str> = columns.iter().map(|c| c.operation_id.as_str()).collect();
+        assert_eq!(ids, vec!["compile", "check", "run", "note", "clean"]);
+        assert_eq!(columns[2].depth, 1);
+
+        let mut cells = FolderCells::default();
+        cells.record("compile", OperationRunStatus::Ok, Some(10), None);
+        cells.record("run", OperationRunStatus::Ok, Some(5), None);
+        cells.record(
+            "run",
+            OperationRunStatus::Failed,
+            Some(7),
+            Some("code 1".into()),
+        );
+        cells.record("check", OperationRunStatus::Failed, Some(12), None);
+        if let OperationDetails::If {
+            else_branch: Some(else_branch),
+            ..
+        } = &operations[1].details
+        {
+            cells.branch_not_taken(else_branch);
+        }
+        let row = folder_summary(
+            &operations,
+            "Alice",
+            FolderRunStatus::Failed,
+            Some(30),
+            cells,
+        );
+        let statuses: Vec<OperationRunStatus> = row.cells.iter().map(|c| c.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                OperationRunStatus::Ok,
+                OperationRunStatus::Failed,
+                OperationRunStatus::Failed,
+                OperationRunStatus::BranchNotTaken,
+                OperationRunStatus::Disabled,
+            ]
+        );
+        assert_eq!(row.cells[2].duration_ms, Some(12));
+        assert_eq!(row.cells[2].error.as_deref(), Some("code 1"));
+
+        let csv = summary_csv(&CommandeurExecutionSummary {
+            columns,
+            folders: vec![row],
+        });
+        let lines: Vec<&str> = csv.trim_start_matches('\u{feff}').lines().collect();
+        assert!(lines[0].starts_with("Dossier;Statut;Durée (ms);Compiler;"));
+        assert!(lines[0].contains(";\"Lancer; tester\";"));
+        assert!(lines[1].starts_with("Alice;échec;30;ok;10;;échec;12;"));
+        assert!(lines[1].ends_with(";branche non prise;;;désactivée;;"));
+    }
+}
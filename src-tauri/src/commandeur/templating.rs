@@ -0,0 +1,421 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use chrono::Local;
+
+use crate::commandeur::models::{
+    CommandeurOperation, ConditionRunner, ConditionTest, OperationDetails, ShellKind,
+};
+use crate::commandeur::process::ShellQuoting;
+
+pub const BUILTIN_VARIABLES: &[&str] = &["folder", "index", "workspaceRoot", "date", "runId"];
+
+/// Variables liées à l'élément courant d'une boucle `for-each`.
+pub const ITEM_VARIABLES: &[&str] = &["item", "item.name", "item.stem", "item.dir"];
+
+/// Valeurs disponibles pour l'interpolation `{{nom}}` dans un dossier donné.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    values: HashMap<String, String>,
+}
+
+impl TemplateContext {
+    /// Construit le contexte d'un dossier: variables du workflow puis variables intégrées.
+    /// Les valeurs des variables du workflow peuvent elles-mêmes utiliser les variables intégrées.
+    pub fn for_folder(
+        variables: &HashMap<String, String>,
+        folder: &str,
+        index: usize,
+        workspace_root: &Path,
+        run_id: &str,
+    ) -> Result<Self> {
+        let mut builtins = TemplateContext::default();
+        builtins.set("folder", folder);
+        builtins.set("index", (index + 1).to_string());
+        builtins.set("workspaceRoot", workspace_root.to_string_lossy());
+        builtins.set("date", Local::now().format("%Y-%m-%d").to_string());
+        builtins.set("runId", run_id);
+
+        let mut context = builtins.clone();
+        for (name, value) in variables {
+            if BUILTIN_VARIABLES.contains(&name.as_str()) {
+                continue;
+            }
+            let rendered = builtins
+                .render(value)
+                .map_err(|err| anyhow!("Variable {name}: {err}"))?;
+            context.set(name, rendered);
+        }
+        Ok(context)
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.values.insert(name.into(), value.into());
+    }
+
+    /// Lie `{{item}}` et ses dérivés, l'élément étant lu comme un chemin relatif
+    /// (`{{item.dir}}` vaut `.` à la racine du dossier).
+    pub fn bind_item(&mut self, item: &str) {
+        let path = Path::new(item);
+        let part = |value: Option<&std::ffi::OsStr>| {
+            value
+                .map(|value| value.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        };
+        let dir = path
+            .parent()
+            .map(|parent| parent.to_string_lossy().replace('\\', "/"))
+            .filter(|parent| !parent.is_empty())
+            .unwrap_or_else(|| ".".to_string());
+        self.set("item", item);
+        self.set("item.name", part(path.file_name()));
+        self.set("item.stem", part(path.file_stem()));
+        self.set("item.dir", dir);
+    }
+
+    /// Valeurs des variables d'élément, à rendre avec `restore_item` en sortie de boucle.
+    pub fn saved_item(&self) -> Vec<Option<String>> {
+        ITEM_VARIABLES
+            .iter()
+            .map(|name| self.values.get(*name).cloned())
+            .collect()
+    }
+
+    pub fn restore_item(&mut self, saved: Vec<Option<String>>) {
+        for (name, value) in ITEM_VARIABLES.iter().zip(saved) {
+            match value {
+                Some(value) => self.set(*name, value),
+                None => {
+                    self.values.remove(*name);
+                }
+            }
+        }
+    }
+
+    pub fn render(&self, input: &str) -> Result<String> {
+        self.render_quoted(input, None)
+    }
+
+    /// Comme `render`, en citant chaque valeur pour le shell qui exécutera le script: un nom
+    /// de dossier comme `O'Brien; rm -rf ~` reste un seul argument inerte.
+    pub fn render_quoted(&self, input: &str, quoting: Option<ShellQuoting>) -> Result<String> {
+        let mut output = String::with_capacity(input.len());
+        let mut missing = Vec::new();
+        for segment in parse_segments(input) {
+            match segment {
+                Segment::Text(text) => output.push_str(text),
+                Segment::Variable { name, raw } => match self.values.get(name) {
+                    Some(value) => match quoting {
+                        Some(quoting) => output.push_str(&quoting.quote(value)),
+                        None => output.push_str(value),
+                    },
+                    None => {
+                        missing.push(name.to_string());
+                        output.push_str(raw);
+                    }
+                },
+            }
+        }
+        if missing.is_empty() {
+            Ok(output)
+        } else {
+            Err(anyhow!(
+                "Variable(s) non définie(s): {}",
+                missing.join(", ")
+            ))
+        }
+    }
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Variable { name: &'a str, raw: &'a str },
+}
+
+pub fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// Découpe `input` en texte littéral et références `{{nom}}`. Une accolade non fermée
+/// ou un contenu qui n'est pas un nom de variable reste du texte, et `{{{{` s'écrit `{{`
+/// sans être interprété (gabarits Handlebars ou Vue d'un projet étudiant). La barre
+/// oblique inverse reste un caractère ordinaire pour ne pas gêner les chemins Windows.
+fn parse_segments(input: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = input;
+    while let Some(start) = rest.find("{{") {
+        if rest[start..].starts_with("{{{{") {
+            segments.push(Segment::Text(&rest[..start + 2]));
+            rest = &rest[start + 4..];
+            continue;
+        }
+        let after_open = &rest[start + 2..];
+        let end = match after_open.find("}}") {
+            Some(end) => end,
+            None => break,
+        };
+        let name = after_open[..end].trim();
+        let raw_len = start + 2 + end + 2;
+        if is_variable_name(name) {
+            if start > 0 {
+                segments.push(Segment::Text(&rest[..start]));
+            }
+            segments.push(Segment::Variable {
+                name,
+                raw: &rest[start..raw_len],
+            });
+        } else {
+            segments.push(Segment::Text(&rest[..raw_len]));
+        }
+        rest = &rest[raw_len..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    segments
+}
+
+pub fn referenced_variables(input: &str) -> Vec<String> {
+    parse_segments(input)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Variable { name, .. } => Some(name.to_string()),
+            Segment::Text(_) => None,
+        })
+        .collect()
+}
+
+/// Applique `visit` à chaque champ interpolable de l'opération (hors sous-opérations), avec
+/// la citation à appliquer aux valeurs quand le champ est un script de shell.
+fn visit_template_fields(
+    details: &mut OperationDetails,
+    visit: &mut dyn FnMut(&mut String, Option<ShellQuoting>) -> Result<()>,
+) -> Result<()> {
+    match details {
+        OperationDetails::CreateFile {
+            target, content, ..
+        } => {
+            visit(target, None)?;
+            visit(content, None)?;
+        }
+        OperationDetails::DeleteFile { target, .. } | OperationDetails::Mkdir { target, .. } => {
+            visit(target, None)?;
+        }
+        OperationDetails::Copy {
+            source,
+            destination,
+            ..
+        }
+        | OperationDetails::Move {
+            source,
+            destination,
+            ..
+        } => {
+            visit(source, None)?;
+            visit(destination, None)?;
+        }
+        OperationDetails::Exec {
+            command,
+            args,
+            shell,
+            cwd,
+            env,
+            ..
+        } => {
+            visit(command, ShellQuoting::for_shell(shell))?;
+            for arg in args.iter_mut() {
+                visit(arg, None)?;
+            }
+            if let Some(cwd) = cwd {
+                visit(cwd, None)?;
+            }
+            if let Some(env) = env {
+                for value in env.values_mut() {
+                    visit(value, None)?;
+                }
+            }
+        }
+        OperationDetails::ReplaceInFile {
+            target,
+            search,
+            replace,
+            ..
+        } => {
+            visit(target, None)?;
+            visit(search, None)?;
+            visit(replace, None)?;
+        }
+        OperationDetails::Rename {
+            target,
+            value,
+            search,
+            replace,
+            ..
+        } => {
+            visit(target, None)?;
+            visit(value, None)?;
+            if let Some(search) = search {
+                visit(search, None)?;
+            }
+            if let Some(replace) = replace {
+                visit(replace, None)?;
+            }
+        }
+        OperationDetails::Python { script_path, .. } => {
+            if let Some(script_path) = script_path {
+                visit(script_path, None)?;
+            }
+        }
+        OperationDetails::If { test, .. } => visit_condition_fields(test, visit)?,
+        OperationDetails::ForEach { pattern, items, .. } => {
+            if let Some(pattern) = pattern {
+                visit(pattern, None)?;
+            }
+            for item in items.iter_mut() {
+                visit(item, None)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn visit_condition_fields(
+    test: &mut ConditionTest,
+    visit: &mut dyn FnMut(&mut String, Option<ShellQuoting>) -> Result<()>,
+) -> Result<()> {
+    let quoting = condition_quoting(test);
+    if let Some(value) = &mut test.value {
+        visit(value, None)?;
+    }
+    if let Some(pattern) = &mut test.pattern {
+        visit(pattern, None)?;
+    }
+    if let Some(command) = &mut test.command {
+        visit(command, quoting)?;
+    }
+    for child in test
+        .all
+        .iter_mut()
+        .chain(test.any.iter_mut())
+        .flatten()
+        .chain(test.not.as_deref_mut())
+    {
+        visit_condition_fields(child, visit)?;
+    }
+    Ok(())
+}
+
+/// Une commande de condition « Système » passe par `system_shell`.
+fn condition_quoting(test: &ConditionTest) -> Option<ShellQuoting> {
+    match test.runner.unwrap_or(ConditionRunner::Command) {
+        ConditionRunner::Python => None,
+        ConditionRunner::Command => match test.shell.as_ref().unwrap_or(&ShellKind::Default) {
+            ShellKind::Default => Some(ShellQuoting::system()),
+            shell => ShellQuoting::for_shell(shell),
+        },
+    }
+}
+
+pub fn interpolate_condition(
+    test: &ConditionTest,
+    context: &TemplateContext,
+) -> Result<ConditionTest> {
+    let mut resolved = test.clone();
+    visit_condition_fields(&mut resolved, &mut |field, quoting| {
+        *field = context.render_quoted(field, quoting)?;
+        Ok(())
+    })?;
+    Ok(resolved)
+}
+
+pub fn interpolate_operation(
+    operation: &CommandeurOperation,
+    context: &TemplateContext,
+) -> Result<CommandeurOperation> {
+    let mut resolved = operation.clone();
+    visit_template_fields(&mut resolved.details, &mut |field, quoting| {
+        *field = context.render_quoted(field, quoting)?;
+        Ok(())
+    })?;
+    Ok(resolved)
+}
+
+/// Variables référencées par les champs propres de l'opération, sans doublons.
+pub fn operation_variables(operation: &CommandeurOperation) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let mut details = operation.details.clone();
+    let _ = visit_template_fields(&mut details, &mut |field, _| {
+        names.extend(referenced_variables(field));
+        Ok(())
+    });
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_builtins_and_workflow_variables() {
+        let mut variables = HashMap::new();
+        variables.insert("cours".to_string(), "INF1010-{{folder}}".to_string());
+        let context =
+            TemplateContext::for_folder(&variables, "Alice", 0, Path::new("/lot"), "run-1")
+                .unwrap();
+        assert_eq!(
+            context
+                .render("{{ folder }}_{{index}}_{{cours}}_{{runId}}.txt")
+                .unwrap(),
+            "Alice_1_INF1010-Alice_run-1.txt"
+        );
+        assert_eq!(
+            context.render("{{ pas une variable }}").unwrap(),
+            "{{ pas une variable }}"
+        );
+        assert_eq!(
+            context
+                .render("<h1>{{{{titre}}</h1> {{{{ folder }} {{folder}}")
+                .unwrap(),
+            "<h1>{{titre}}</h1> {{ folder }} Alice"
+        );
+        assert!(referenced_variables("{{{{titre}}").is_empty());
+        assert_eq!(
+            context
+                .render(r"{{workspaceRoot}}\{{folder}}\out.txt")
+                .unwrap(),
+            r"/lot\Alice\out.txt"
+        );
+        assert_eq!(
+            referenced_variables(r"C:\{{folder}}\{{cours}}"),
+            vec!["folder", "cours"]
+        );
+        let err = context.render("{{inconnue}}").unwrap_err();
+        assert!(err.to_string().contains("inconnue"));
+    }
+
+    #[test]
+    fn binds_and_restores_loop_items() {
+        let mut context = TemplateContext::default();
+        let saved = context.saved_item();
+        context.bind_item("src/tp1/main.c");
+        assert_eq!(
+            context
+                .render("gcc {{item}} -o {{item.dir}}/{{item.stem}} # {{item.name}}")
+                .unwrap(),
+            "gcc src/tp1/main.c -o src/tp1/main # main.c"
+        );
+        context.bind_item("README");
+        assert_eq!(
+            context.render("{{item.dir}}/{{item.stem}}").unwrap(),
+            "./README"
+        );
+        context.restore_item(saved);
+        assert!(context.render("{{item}}").is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::{anyhow, Result};
 use regex::{Regex, RegexBuilder};
+use std::fs;
 use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
 
 use crate::commandeur::models::RenameMode;
 
@@ -89,6 +91,66 @@ pub fn compute_rename_destination(
     Ok(parent.join(new_name))
 }
 
+/// Un remplacement ne supprime un dossier de destination que pour le remplacer par un dossier:
+/// un fichier copié ou déplacé par erreur sur un dossier ne doit pas effacer son contenu.
+pub fn ensure_replaceable(destination: &str, source_is_dir: bool, dest_is_dir: bool) -> Result<()> {
+    if dest_is_dir && !source_is_dir {
+        return Err(anyhow!(
+            "La destination {destination} est un dossier: seul un dossier peut la remplacer"
+        ));
+    }
+    Ok(())
+}
+
+/// Copie récursivement `source` vers `destination`. `fs::copy` profite du clonage
+/// copy-on-write quand le système de fichiers le permet (APFS, Btrfs, XFS). Les liens
+/// symboliques sont recréés tels quels, même s'ils pointent vers un dossier ou nulle part.
+pub fn copy_dir_recursive(source: &Path, destination: &Path) -> Result<()> {
+    fs::create_dir_all(destination)?;
+    for entry in WalkDir::new(source).follow_links(false) {
+        let entry = entry?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .map_err(|err| anyhow!(err))?;
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        let target = destination.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else if entry.file_type().is_symlink() {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            copy_symlink(entry.path(), &target)?;
+        } else {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(source: &Path, target: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(source)?, target)?;
+    Ok(())
+}
+
+#[cfg(windows)]
+fn copy_symlink(source: &Path, target: &Path) -> Result<()> {
+    let link = fs::read_link(source)?;
+    if fs::metadata(source).map_or(false, |metadata| metadata.is_dir()) {
+        std::os::windows::fs::symlink_dir(link, target)?;
+    } else {
+        std::os::windows::fs::symlink_file(link, target)?;
+    }
+    Ok(())
+}
+
 fn split_name_ext(name: &str) -> Option<(String, String)> {
     if let Some(idx) = name.rfind('.') {
         if idx == 0 {
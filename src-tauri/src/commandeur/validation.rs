@@ -1,14 +1,26 @@
+use std::collections::HashMap;
 use std::fs;
 
 use anyhow::{anyhow, Result};
 
-use crate::commandeur::conditions::evaluate_condition_for_folder;
+use crate::commandeur::conditions::{
+    captured_value_dependency, command_dependency, commands_unavailable,
+    evaluate_condition_for_folder,
+};
+use crate::commandeur::globs::{
+    check_match_count, expand_glob_targets, glob_options, glob_target, loop_items, operation_path,
+    Disk,
+};
 use crate::commandeur::models::{
-    CommandeurOperation, CommandeurValidationMessage, CommandeurWorkflow, OperationDetails,
-    PythonEntry, ReplaceMode, ValidationLevel,
+    CaptureSource, CommandeurOperation, CommandeurValidationMessage, CommandeurWorkflow,
+    OperationDetails, OutputCapture, PythonEntry, ReplaceMode, ValidationLevel,
 };
 use crate::commandeur::python::detect_external_python_modules;
 use crate::commandeur::reporting::{push_folder_validation, push_validation};
+use crate::commandeur::templating::{
+    interpolate_operation, is_variable_name, operation_variables, referenced_variables,
+    TemplateContext, BUILTIN_VARIABLES, ITEM_VARIABLES,
+};
 use crate::commandeur::utils::{build_regex, compute_rename_destination};
 use crate::commandeur::workspace::{resolve_in_folder, AppState, WorkspaceHandle};
 
@@ -23,21 +35,384 @@ pub fn validate_workflow(
         .map_err(|_| anyhow!("Accès concurrent au workspace"))?;
     let mut messages = Vec::new();
     let active_folders = guard.sub_folders.clone();
+    validate_workflow_variables(workflow, &mut messages);
+    let templates: HashMap<String, TemplateContext> = active_folders
+        .iter()
+        .enumerate()
+        .filter_map(|(index, folder)| {
+            TemplateContext::for_folder(
+                &workflow.variables,
+                folder,
+                index,
+                &guard.root_path,
+                VALIDATION_RUN_ID,
+            )
+            .ok()
+            .map(|context| (folder.clone(), context))
+        })
+        .collect();
     for operation in &workflow.operations {
-        validate_operation(&guard, operation, &active_folders, &mut messages)?;
+        validate_operation(
+            &guard,
+            operation,
+            &active_folders,
+            &templates,
+            &mut messages,
+        )?;
     }
     Ok(messages)
 }
 
+const VALIDATION_RUN_ID: &str = "validation";
+
+/// Signale les références `{{nom}}` qui ne correspondent à aucune variable du workflow
+/// ni à une variable intégrée.
+fn validate_workflow_variables(
+    workflow: &CommandeurWorkflow,
+    messages: &mut Vec<CommandeurValidationMessage>,
+) {
+    let mut captured = Vec::new();
+    collect_capture_names(&workflow.operations, &mut captured);
+    let mut known: Vec<&str> = BUILTIN_VARIABLES.to_vec();
+    known.extend(workflow.variables.keys().map(String::as_str));
+    known.extend(captured.iter().map(String::as_str));
+
+    let mut names: Vec<&String> = workflow.variables.keys().collect();
+    names.sort();
+    for name in names {
+        if BUILTIN_VARIABLES.contains(&name.as_str()) {
+            messages.push(CommandeurValidationMessage {
+                operation_id: "__workflow__".into(),
+                operation_label: Some("Variables".into()),
+                level: ValidationLevel::Warning,
+                message: format!("La variable {name} est réservée et sera ignorée"),
+                details: None,
+                folders: None,
+            });
+        }
+        let undefined: Vec<String> = referenced_variables(&workflow.variables[name])
+            .into_iter()
+            .filter(|reference| !BUILTIN_VARIABLES.contains(&reference.as_str()))
+            .collect();
+        if !undefined.is_empty() {
+            messages.push(CommandeurValidationMessage {
+                operation_id: "__workflow__".into(),
+                operation_label: Some("Variables".into()),
+                level: ValidationLevel::Error,
+                message: format!("La variable {name} utilise une variable non définie"),
+                details: Some(format!(
+                    "Seules les variables intégrées sont disponibles ici: {}",
+                    undefined.join(", ")
+                )),
+                folders: None,
+            });
+        }
+    }
+
+    check_operation_variables(&workflow.operations, &known, messages);
+}
+
+fn collect_capture_names(operations: &[CommandeurOperation], names: &mut Vec<String>) {
+    for operation in operations {
+        match &operation.details {
+            OperationDetails::Exec {
+                capture: Some(capture),
+                ..
+            }
+            | OperationDetails::Python {
+                capture: Some(capture),
+                ..
+            } => names.push(capture.name.trim().to_string()),
+            OperationDetails::If {
+                then, else_branch, ..
+            } => {
+                collect_capture_names(then, names);
+                if let Some(else_branch) = else_branch {
+                    collect_capture_names(else_branch, names);
+                }
+            }
+            OperationDetails::ForEach { operations, .. } => {
+                collect_capture_names(operations, names);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn validate_capture(
+    operation: &CommandeurOperation,
+    capture: &OutputCapture,
+    messages: &mut Vec<CommandeurValidationMessage>,
+) {
+    let name = capture.name.trim();
+    if !is_variable_name(name)
+        || BUILTIN_VARIABLES.contains(&name)
+        || ITEM_VARIABLES.contains(&name)
+    {
+        push_validation(
+            messages,
+            operation,
+            ValidationLevel::Error,
+            "Nom de capture invalide",
+            Some(format!(
+                "\"{name}\" doit commencer par une lettre et ne pas reprendre une variable intégrée"
+            )),
+            None,
+        );
+    }
+    if capture.source == CaptureSource::Regex {
+        let pattern = capture.pattern.as_deref().unwrap_or_default();
+        if pattern.is_empty() {
+            push_validation(
+                messages,
+                operation,
+                ValidationLevel::Error,
+                "Regex de capture manquante",
+                None,
+                None,
+            );
+        } else if let Err(err) = build_regex(pattern, capture.flags.as_deref()) {
+            push_validation(
+                messages,
+                operation,
+                ValidationLevel::Error,
+                "Regex de capture invalide",
+                Some(err.to_string()),
+                None,
+            );
+        }
+    }
+}
+
+fn check_operation_variables(
+    operations: &[CommandeurOperation],
+    known: &[&str],
+    messages: &mut Vec<CommandeurValidationMessage>,
+) {
+    for operation in operations {
+        let undefined: Vec<String> = operation_variables(operation)
+            .into_iter()
+            .filter(|name| !known.contains(&name.as_str()))
+            .collect();
+        if !undefined.is_empty() {
+            push_validation(
+                messages,
+                operation,
+                ValidationLevel::Error,
+                "Variable non définie",
+                Some(format!(
+                    "{} (écrivez {{{{{{{{ pour garder {{{{ tel quel)",
+                    undefined
+                        .iter()
+                        .map(|name| format!("{{{{{name}}}}}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+                None,
+            );
+        }
+        match &operation.details {
+            OperationDetails::If {
+                then, else_branch, ..
+            } => {
+                check_operation_variables(then, known, messages);
+                if let Some(else_branch) = else_branch {
+                    check_operation_variables(else_branch, known, messages);
+                }
+            }
+            OperationDetails::ForEach { operations, .. } => {
+                let mut with_item = known.to_vec();
+                with_item.extend_from_slice(ITEM_VARIABLES);
+                check_operation_variables(operations, &with_item, messages);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Valide une opération pour les dossiers donnés. Si elle utilise des variables, chaque dossier
+/// est validé avec ses propres valeurs et les messages identiques sont regroupés.
 pub fn validate_operation(
     workspace: &WorkspaceHandle,
     operation: &CommandeurOperation,
     folders: &[String],
+    templates: &HashMap<String, TemplateContext>,
     messages: &mut Vec<CommandeurValidationMessage>,
 ) -> Result<()> {
     if folders.is_empty() {
         return Ok(());
     }
+    if operation_variables(operation).is_empty() {
+        return validate_operation_details(workspace, operation, folders, templates, messages);
+    }
+    let mut per_folder = Vec::new();
+    for folder in folders {
+        let resolved = match templates
+            .get(folder)
+            .map(|context| interpolate_operation(operation, context))
+        {
+            Some(Ok(resolved)) => resolved,
+            // Variables non définies (déjà signalées) ou capturées seulement à l'exécution.
+            _ => continue,
+        };
+        validate_operation_details(
+            workspace,
+            &resolved,
+            std::slice::from_ref(folder),
+            templates,
+            &mut per_folder,
+        )?;
+    }
+    merge_messages(messages, per_folder);
+    Ok(())
+}
+
+/// Ajoute des messages validés dossier par dossier en regroupant les messages identiques.
+fn merge_messages(
+    messages: &mut Vec<CommandeurValidationMessage>,
+    per_folder: Vec<CommandeurValidationMessage>,
+) {
+    for message in per_folder {
+        let existing = messages.iter_mut().find(|existing| {
+            existing.operation_id == message.operation_id
+                && existing.level == message.level
+                && existing.message == message.message
+        });
+        match existing {
+            Some(existing) => {
+                if let Some(new_folders) = message.folders {
+                    let folders = existing.folders.get_or_insert_with(Vec::new);
+                    for folder in new_folders {
+                        if !folders.contains(&folder) {
+                            folders.push(folder);
+                        }
+                    }
+                }
+            }
+            None => messages.push(message),
+        }
+    }
+}
+
+/// Une opération à cible générique est validée pour chaque chemin correspondant; les
+/// dossiers sans correspondance ou hors des bornes `minMatches`/`maxMatches` sont signalés.
+fn validate_operation_details(
+    workspace: &WorkspaceHandle,
+    operation: &CommandeurOperation,
+    folders: &[String],
+    templates: &HashMap<String, TemplateContext>,
+    messages: &mut Vec<CommandeurValidationMessage>,
+) -> Result<()> {
+    if let Some(matching) = &operation.meta.matching {
+        if operation_path(operation).is_none() && !matching.is_empty() {
+            push_validation(
+                messages,
+                operation,
+                ValidationLevel::Warning,
+                "Les motifs d'inclusion et d'exclusion sont ignorés pour ce type d'opération",
+                None,
+                None,
+            );
+        }
+        if let (Some(min), Some(max)) = (matching.min_matches, matching.max_matches) {
+            if min > max {
+                push_validation(
+                    messages,
+                    operation,
+                    ValidationLevel::Error,
+                    format!(
+                        "Bornes de correspondances incohérentes: minimum {min} > maximum {max}"
+                    ),
+                    None,
+                    None,
+                );
+                return Ok(());
+            }
+        }
+    }
+    let pattern = match glob_target(operation) {
+        Some(pattern) => pattern,
+        None => {
+            return validate_concrete_operation(workspace, operation, folders, templates, messages)
+        }
+    };
+    let mut per_path = Vec::new();
+    let mut without_match = Vec::new();
+    let mut out_of_range = Vec::new();
+    for folder in folders {
+        let folder_path = workspace.folder_absolute_path(folder);
+        let expanded = match expand_glob_targets(operation, &folder_path, &Disk) {
+            Ok(expanded) => expanded.unwrap_or_default(),
+            Err(err) => {
+                push_validation(
+                    messages,
+                    operation,
+                    ValidationLevel::Error,
+                    "Motif de chemin invalide",
+                    Some(err.to_string()),
+                    None,
+                );
+                return Ok(());
+            }
+        };
+        if let Err(err) = check_match_count(operation, expanded.len()) {
+            out_of_range.push(format!("{folder}: {err}"));
+        } else if expanded.is_empty() {
+            without_match.push(folder.clone());
+        }
+        for concrete in &expanded {
+            validate_concrete_operation(
+                workspace,
+                concrete,
+                std::slice::from_ref(folder),
+                templates,
+                &mut per_path,
+            )?;
+        }
+    }
+    merge_messages(messages, per_path);
+    if !out_of_range.is_empty() {
+        push_validation(
+            messages,
+            operation,
+            ValidationLevel::Error,
+            format!(
+                "Nombre de correspondances inattendu pour le motif \"{}\" dans {} sous-dossier(s)",
+                pattern,
+                out_of_range.len()
+            ),
+            Some(out_of_range.join("\n")),
+            None,
+        );
+    }
+    if !without_match.is_empty() {
+        let level = match operation.details {
+            OperationDetails::DeleteFile { required: true, .. } => ValidationLevel::Error,
+            _ => ValidationLevel::Warning,
+        };
+        push_validation(
+            messages,
+            operation,
+            level,
+            format!(
+                "Aucun chemin ne correspond au motif \"{}\" dans {} sous-dossier(s)",
+                pattern,
+                without_match.len()
+            ),
+            None,
+            Some(without_match),
+        );
+    }
+    Ok(())
+}
+
+fn validate_concrete_operation(
+    workspace: &WorkspaceHandle,
+    operation: &CommandeurOperation,
+    folders: &[String],
+    templates: &HashMap<String, TemplateContext>,
+    messages: &mut Vec<CommandeurValidationMessage>,
+) -> Result<()> {
     match &operation.details {
         OperationDetails::CreateFile { target, .. } => {
             let mut missing = Vec::new();
@@ -187,7 +562,15 @@ pub fn validate_operation(
                 );
             }
         }
-        OperationDetails::Exec { cwd, command, .. } => {
+        OperationDetails::Exec {
+            cwd,
+            command,
+            capture,
+            ..
+        } => {
+            if let Some(capture) = capture {
+                validate_capture(operation, capture, messages);
+            }
             if command.trim().is_empty() {
                 push_validation(
                     messages,
@@ -467,25 +850,31 @@ pub fn validate_operation(
             inline_script,
             script_path,
             entry,
-        } => match entry {
-            PythonEntry::Inline => {
-                if inline_script
-                    .as_ref()
-                    .map(|s| s.trim().is_empty())
-                    .unwrap_or(true)
-                {
-                    push_validation(
-                        messages,
-                        operation,
-                        ValidationLevel::Error,
-                        "Le script Python inline est vide",
-                        None,
-                        None,
-                    );
-                } else if let Some(script) = inline_script {
-                    if let Some(externals) = detect_external_python_modules(script) {
-                        if !externals.is_empty() {
-                            push_validation(
+            capture,
+            ..
+        } => {
+            if let Some(capture) = capture {
+                validate_capture(operation, capture, messages);
+            }
+            match entry {
+                PythonEntry::Inline => {
+                    if inline_script
+                        .as_ref()
+                        .map(|s| s.trim().is_empty())
+                        .unwrap_or(true)
+                    {
+                        push_validation(
+                            messages,
+                            operation,
+                            ValidationLevel::Error,
+                            "Le script Python inline est vide",
+                            None,
+                            None,
+                        );
+                    } else if let Some(script) = inline_script {
+                        if let Some(externals) = detect_external_python_modules(script) {
+                            if !externals.is_empty() {
+                                push_validation(
                                 messages,
                                 operation,
                                 ValidationLevel::Warning,
@@ -493,38 +882,38 @@ pub fn validate_operation(
                                 Some(externals.join(", ")),
                                 None,
                             );
+                            }
                         }
                     }
                 }
-            }
-            PythonEntry::File => {
-                if script_path
-                    .as_ref()
-                    .map(|s| s.trim().is_empty())
-                    .unwrap_or(true)
-                {
-                    push_validation(
-                        messages,
-                        operation,
-                        ValidationLevel::Error,
-                        "Le chemin du script Python est vide",
-                        None,
-                        None,
-                    );
-                } else {
-                    let mut missing = Vec::new();
-                    for folder in folders {
-                        let folder_path = workspace.folder_absolute_path(folder);
-                        match resolve_in_folder(&folder_path, script_path.as_ref().unwrap()) {
-                            Ok(path) => {
-                                if !path.exists() {
-                                    missing.push(folder.clone());
-                                } else if let Ok(content) = fs::read_to_string(&path) {
-                                    if let Some(externals) =
-                                        detect_external_python_modules(&content)
-                                    {
-                                        if !externals.is_empty() {
-                                            push_folder_validation(
+                PythonEntry::File => {
+                    if script_path
+                        .as_ref()
+                        .map(|s| s.trim().is_empty())
+                        .unwrap_or(true)
+                    {
+                        push_validation(
+                            messages,
+                            operation,
+                            ValidationLevel::Error,
+                            "Le chemin du script Python est vide",
+                            None,
+                            None,
+                        );
+                    } else {
+                        let mut missing = Vec::new();
+                        for folder in folders {
+                            let folder_path = workspace.folder_absolute_path(folder);
+                            match resolve_in_folder(&folder_path, script_path.as_ref().unwrap()) {
+                                Ok(path) => {
+                                    if !path.exists() {
+                                        missing.push(folder.clone());
+                                    } else if let Ok(content) = fs::read_to_string(&path) {
+                                        if let Some(externals) =
+                                            detect_external_python_modules(&content)
+                                        {
+                                            if !externals.is_empty() {
+                                                push_folder_validation(
                                                 messages,
                                                 operation,
                                                 folder,
@@ -532,41 +921,88 @@ pub fn validate_operation(
                                                 "Le script Python semble utiliser des modules externes non supportés",
                                                 Some(externals.join(", ")),
                                             );
+                                            }
                                         }
                                     }
                                 }
-                            }
-                            Err(err) => {
-                                push_validation(
-                                    messages,
-                                    operation,
-                                    ValidationLevel::Error,
-                                    "Chemin du script Python invalide",
-                                    Some(err.to_string()),
-                                    None,
-                                );
-                                return Ok(());
+                                Err(err) => {
+                                    push_validation(
+                                        messages,
+                                        operation,
+                                        ValidationLevel::Error,
+                                        "Chemin du script Python invalide",
+                                        Some(err.to_string()),
+                                        None,
+                                    );
+                                    return Ok(());
+                                }
                             }
                         }
-                    }
-                    if !missing.is_empty() {
-                        push_validation(
-                            messages,
-                            operation,
-                            ValidationLevel::Error,
-                            "Script Python introuvable",
-                            None,
-                            Some(missing),
-                        );
+                        if !missing.is_empty() {
+                            push_validation(
+                                messages,
+                                operation,
+                                ValidationLevel::Error,
+                                "Script Python introuvable",
+                                None,
+                                Some(missing),
+                            );
+                        }
                     }
                 }
             }
-        },
+        }
         OperationDetails::If {
             test,
             then,
             else_branch,
         } => {
+            let command = command_dependency(test);
+            if let Some(command) = &command {
+                if command.is_empty() {
+                    push_validation(
+                        messages,
+                        operation,
+                        ValidationLevel::Error,
+                        "Commande de condition manquante",
+                        None,
+                        None,
+                    );
+                } else {
+                    push_validation(
+                        messages,
+                        operation,
+                        ValidationLevel::Warning,
+                        format!(
+                            "La condition lance \"{command}\" dans chaque dossier: ses effets de bord s'appliquent à l'exécution"
+                        ),
+                        Some(
+                            "La commande n'est pas lancée pendant la validation ni la simulation."
+                                .to_string(),
+                        ),
+                        None,
+                    );
+                }
+            }
+            let captured = captured_value_dependency(test);
+            if let Some(name) = &captured {
+                push_validation(
+                    messages,
+                    operation,
+                    ValidationLevel::Info,
+                    format!(
+                        "Condition évaluée à l'exécution à partir de la valeur capturée {name}"
+                    ),
+                    None,
+                    None,
+                );
+            }
+            if command.is_some() || captured.is_some() {
+                for child in then.iter().chain(else_branch.iter().flatten()) {
+                    validate_operation(workspace, child, folders, templates, messages)?;
+                }
+                return Ok(());
+            }
             let mut matching_folders = Vec::new();
             let mut non_matching_folders = Vec::new();
             let mut sample_summary: Option<String> = None;
@@ -574,7 +1010,13 @@ pub fn validate_operation(
             let total_folders = folders.len();
             for folder in folders {
                 let base_path = workspace.folder_absolute_path(folder);
-                match evaluate_condition_for_folder(&base_path, folder, test) {
+                match evaluate_condition_for_folder(
+                    &base_path,
+                    folder,
+                    test,
+                    &HashMap::new(),
+                    &mut commands_unavailable,
+                ) {
                     Ok(evaluation) => {
                         if evaluation.result {
                             success_count += 1;
@@ -618,14 +1060,108 @@ pub fn validate_operation(
                 affected_folders,
             );
             for child in then {
-                validate_operation(workspace, child, &matching_folders, messages)?;
+                validate_operation(workspace, child, &matching_folders, templates, messages)?;
             }
             if let Some(else_branch) = else_branch {
                 for child in else_branch {
-                    validate_operation(workspace, child, &non_matching_folders, messages)?;
+                    validate_operation(
+                        workspace,
+                        child,
+                        &non_matching_folders,
+                        templates,
+                        messages,
+                    )?;
                 }
             }
         }
+        OperationDetails::ForEach {
+            pattern,
+            items,
+            operations,
+        } => {
+            let pattern = pattern
+                .as_deref()
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty());
+            if pattern.is_none() && items.iter().all(|item| item.trim().is_empty()) {
+                push_validation(
+                    messages,
+                    operation,
+                    ValidationLevel::Error,
+                    "Boucle sans éléments",
+                    Some("Indiquez un motif de fichiers ou une liste de valeurs.".to_string()),
+                    None,
+                );
+                return Ok(());
+            }
+            if operations.is_empty() {
+                push_validation(
+                    messages,
+                    operation,
+                    ValidationLevel::Warning,
+                    "Boucle sans opération",
+                    None,
+                    None,
+                );
+            }
+            // Les opérations de la boucle sont validées pour chaque élément de chaque dossier.
+            let mut per_item = Vec::new();
+            let mut without_items = Vec::new();
+            for folder in folders {
+                let folder_path = workspace.folder_absolute_path(folder);
+                let values = match loop_items(
+                    &Disk,
+                    &folder_path,
+                    pattern,
+                    items,
+                    glob_options(operation),
+                ) {
+                    Ok(values) => values,
+                    Err(err) => {
+                        push_validation(
+                            messages,
+                            operation,
+                            ValidationLevel::Error,
+                            "Motif de boucle invalide",
+                            Some(err.to_string()),
+                            None,
+                        );
+                        return Ok(());
+                    }
+                };
+                if values.is_empty() {
+                    without_items.push(folder.clone());
+                    continue;
+                }
+                let folder_context = templates.get(folder).cloned().unwrap_or_default();
+                for value in &values {
+                    let mut context = folder_context.clone();
+                    context.bind_item(value);
+                    let mut item_templates = HashMap::new();
+                    item_templates.insert(folder.clone(), context);
+                    for child in operations {
+                        validate_operation(
+                            workspace,
+                            child,
+                            std::slice::from_ref(folder),
+                            &item_templates,
+                            &mut per_item,
+                        )?;
+                    }
+                }
+            }
+            merge_messages(messages, per_item);
+            if !without_items.is_empty() {
+                push_validation(
+                    messages,
+                    operation,
+                    ValidationLevel::Info,
+                    "Aucun élément à parcourir",
+                    None,
+                    Some(without_items),
+                );
+            }
+        }
     }
     Ok(())
 }
@@ -0,0 +1,385 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::{Map, Value};
+
+use crate::commandeur::models::CommandeurWorkflow;
+
+/// Version du format des fichiers de workflow. Un fichier sans `schemaVersion` est en
+/// version 1 (exports YAML de l'éditeur, identifiants d'opération facultatifs).
+pub const WORKFLOW_SCHEMA_VERSION: u64 = 2;
+const SCHEMA_VERSION_KEY: &str = "schemaVersion";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WorkflowFileFormat {
+    Yaml,
+    Json,
+}
+
+impl WorkflowFileFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("yaml") | Some("yml") => Some(Self::Yaml),
+            Some("json") => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+pub fn import_workflow(path: &Path) -> Result<CommandeurWorkflow> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Impossible de lire {}", path.display()))?;
+    // Une extension inconnue est lue en YAML, qui accepte aussi le JSON.
+    let format = WorkflowFileFormat::from_path(path).unwrap_or(WorkflowFileFormat::Yaml);
+    parse_workflow(&content, format)
+}
+
+/// Écrit le workflow en YAML ou en JSON selon l'extension, précédé de `schemaVersion`.
+pub fn export_workflow(workflow: &CommandeurWorkflow, path: &Path) -> Result<()> {
+    let format = WorkflowFileFormat::from_path(path)
+        .ok_or_else(|| anyhow!("Extension non prise en charge: utilisez .yaml, .yml ou .json"))?;
+    let mut document = Map::new();
+    document.insert(
+        SCHEMA_VERSION_KEY.to_string(),
+        Value::from(WORKFLOW_SCHEMA_VERSION),
+    );
+    if let Value::Object(fields) = serde_json::to_value(workflow)? {
+        document.extend(fields);
+    }
+    let document = Value::Object(document);
+    let content = match format {
+        WorkflowFileFormat::Yaml => serde_yaml::to_string(&document)?,
+        WorkflowFileFormat::Json => serde_json::to_string_pretty(&document)?,
+    };
+    fs::write(path, content).with_context(|| format!("Impossible d'écrire {}", path.display()))
+}
+
+fn parse_workflow(content: &str, format: WorkflowFileFormat) -> Result<CommandeurWorkflow> {
+    let document: Value = match format {
+        WorkflowFileFormat::Yaml => {
+            serde_yaml::from_str(content).map_err(|err| anyhow!("YAML invalide: {err}"))?
+        }
+        WorkflowFileFormat::Json => {
+            serde_json::from_str(content).map_err(|err| anyhow!("JSON invalide: {err}"))?
+        }
+    };
+    let document = migrate(document)?;
+    match serde_json::from_value(document.clone()) {
+        Ok(workflow) => Ok(workflow),
+        Err(_) => Err(describe_invalid_workflow(content, &document)),
+    }
+}
+
+/// Amène le document à la version courante du schéma, étape par étape.
+fn migrate(document: Value) -> Result<Value> {
+    let mut document = match document {
+        Value::Object(document) => document,
+        _ => return Err(anyhow!("Le fichier ne contient pas de workflow")),
+    };
+    let version = match document.remove(SCHEMA_VERSION_KEY) {
+        None => 1,
+        Some(value) => value
+            .as_u64()
+            .filter(|version| *version >= 1)
+            .ok_or_else(|| anyhow!("schemaVersion invalide: {value}"))?,
+    };
+    if version > WORKFLOW_SCHEMA_VERSION {
+        return Err(anyhow!(
+            "schemaVersion {version} non pris en charge (maximum {WORKFLOW_SCHEMA_VERSION}): \
+             mettez Raisin à jour"
+        ));
+    }
+    if version < 2 {
+        migrate_v1(&mut document);
+    }
+    Ok(Value::Object(document))
+}
+
+/// Version 1 -> 2: identifiants d'opération obligatoires, version du workflow par défaut.
+fn migrate_v1(document: &mut Map<String, Value>) {
+    if document.get("version").map_or(true, Value::is_null) {
+        document.insert("version".into(), Value::from("1.0"));
+    }
+    if let Some(Value::Array(operations)) = document.get_mut("operations") {
+        assign_missing_ids(operations);
+    }
+}
+
+fn assign_missing_ids(operations: &mut [Value]) {
+    for operation in operations {
+        let operation = match operation {
+            Value::Object(operation) => operation,
+            _ => continue,
+        };
+        if operation.get("id").map_or(true, Value::is_null) {
+            operation.insert("id".into(), Value::from(uuid::Uuid::new_v4().to_string()));
+        }
+        for key in ["then", "else", "operations"] {
+            if let Some(Value::Array(children)) = operation.get_mut(key) {
+                assign_missing_ids(children);
+            }
+        }
+    }
+}
+
+/// serde_yaml indique le champ fautif en tête du message (`operations[1].command: ...`) et sa
+/// position. Le document migré donne le champ; le fichier d'origine, relu tel quel, donne la
+/// ligne quand il échoue sur le même champ (une migration peut déplacer ou ajouter des lignes).
+fn describe_invalid_workflow(content: &str, document: &Value) -> anyhow::Error {
+    let migrated = match serde_yaml::to_string(document) {
+        Ok(migrated) => migrated,
+        Err(err) => return anyhow!("Workflow invalide: {err}"),
+    };
+    let (path, message) = match serde_yaml::from_str::<CommandeurWorkflow>(&migrated) {
+        Ok(_) => return anyhow!("Workflow invalide"),
+        Err(err) => split_error(&err),
+    };
+    let path = match path {
+        Some(path) => path,
+        None => return anyhow!("Workflow invalide: {message}"),
+    };
+    let field = refine_field(document, &path, &message);
+    let line = match serde_yaml::from_str::<CommandeurWorkflow>(content) {
+        Err(err) if split_error(&err) == (Some(path.clone()), message.clone()) => err
+            .location()
+            .map(|location| line_of_key(content, location.line(), &path, &field)),
+        _ => None,
+    };
+    match line {
+        Some(line) => anyhow!("Workflow invalide, ligne {line}, champ {field}: {message}"),
+        None => anyhow!("Workflow invalide, champ {field}: {message}"),
+    }
+}
+
+/// Sépare le chemin du champ et le message d'une erreur serde_yaml, sans la position.
+fn split_error(err: &serde_yaml::Error) -> (Option<String>, String) {
+    let mut message = err.to_string();
+    if let Some(location) = err.location() {
+        let suffix = format!(" at line {} column {}", location.line(), location.column());
+        if message.ends_with(&suffix) {
+            message.truncate(message.len() - suffix.len());
+        }
+    }
+    match message.split_once(": ") {
+        Some((path, rest)) if !path.is_empty() && !path.contains(' ') => {
+            (Some(path.to_string()), rest.to_string())
+        }
+        _ => (None, message),
+    }
+}
+
+/// Les opérations (énumération étiquetée) sont signalées sur leur mapping, voire sur celui de
+/// l'opération parente: on retrouve le champ descendant qui porte la valeur citée par l'erreur
+/// (`invalid type: string "x"`).
+fn refine_field(document: &Value, field: &str, message: &str) -> String {
+    let value = message
+        .strip_prefix("invalid type: ")
+        .or_else(|| message.strip_prefix("invalid value: "))
+        .and_then(|rest| rest.split_once(&['"', '`'][..]))
+        .and_then(|(_, rest)| rest.split_once(&['"', '`'][..]))
+        .map(|(value, _)| value);
+    let value = match value {
+        Some(value) => value,
+        None => return field.to_string(),
+    };
+    value_at(document, field)
+        .and_then(|node| find_scalar(node, field, value))
+        .unwrap_or_else(|| field.to_string())
+}
+
+fn find_scalar(node: &Value, path: &str, value: &str) -> Option<String> {
+    let children: Vec<(String, &Value)> = match node {
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, child)| (format!("{path}.{key}"), child))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, child)| (format!("{path}[{index}]"), child))
+            .collect(),
+        _ => return None,
+    };
+    children.into_iter().find_map(|(child_path, child)| {
+        let matches = match child {
+            Value::String(text) => text == value,
+            Value::Number(number) => number.to_string() == value,
+            Value::Bool(flag) => flag.to_string() == value,
+            _ => false,
+        };
+        if matches {
+            Some(child_path)
+        } else {
+            find_scalar(child, &child_path, value)
+        }
+    })
+}
+
+/// Nœud du document désigné par un chemin `operations[1].then[0]`.
+fn value_at<'a>(document: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut node = document;
+    for part in path.split('.') {
+        let (key, indices) = part.split_once('[').unwrap_or((part, ""));
+        if !key.is_empty() {
+            node = node.get(key)?;
+        }
+        for index in indices.split('[').filter(|index| !index.is_empty()) {
+            node = node.get(index.trim_end_matches(']').parse::<usize>().ok()?)?;
+        }
+    }
+    Some(node)
+}
+
+/// Ligne de la clé affinée (`timeoutSeconds: x` ou `"timeoutSeconds": x`) à partir de la
+/// ligne signalée pour le mapping, ou cette ligne si la clé n'y figure pas.
+fn line_of_key(content: &str, reported: usize, path: &str, field: &str) -> usize {
+    let key = match field
+        .strip_prefix(path)
+        .and_then(|rest| rest.rsplit('.').next())
+    {
+        Some(key) if !key.is_empty() && !key.ends_with(']') => key,
+        _ => return reported,
+    };
+    let plain = format!("{key}:");
+    let quoted = format!("\"{key}\"");
+    content
+        .lines()
+        .enumerate()
+        .skip(reported.saturating_sub(1))
+        .find(|(_, line)| {
+            let line = line.trim_start().trim_start_matches(['-', ' ', '{']);
+            line.starts_with(&plain) || line.starts_with(&quoted)
+        })
+        .map_or(reported, |(index, _)| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_one_yaml_is_migrated_and_round_trips_as_json() {
+        let source = "\
+name: Corrections
+operations:
+  - label: Compiler
+    kind: exec
+    command: make
+    shell: default
+  - label: Vérifier
+    kind: if
+    test:
+      exists: main.c
+    then:
+      - label: Noter
+        kind: create-file
+        target: note.txt
+        content: ok
+";
+        let workflow = parse_workflow(source, WorkflowFileFormat::Yaml).unwrap();
+        assert_eq!(workflow.version.as_deref(), Some("1.0"));
+        assert!(!workflow.operations[0].id().is_empty());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("workflow.json");
+        export_workflow(&workflow, &path).unwrap();
+        let exported: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(exported[SCHEMA_VERSION_KEY], WORKFLOW_SCHEMA_VERSION);
+        let reimported = import_workflow(&path).unwrap();
+        assert_eq!(reimported.operations[0].id(), workflow.operations[0].id());
+
+        let future = format!("schemaVersion: 99\n{source}");
+        let err = parse_workflow(&future, WorkflowFileFormat::Yaml).unwrap_err();
+        assert!(err.to_string().contains("schemaVersion 99"));
+    }
+
+    #[test]
+    fn invalid_field_reports_line_and_path() {
+        let source = "\
+schemaVersion: 2
+name: Corrections
+version: '1'
+operations:
+  - id: a
+    label: Compiler
+    kind: exec
+    command: make
+    shell: default
+    timeoutSeconds: bientôt
+";
+        let err = parse_workflow(source, WorkflowFileFormat::Yaml)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("Workflow invalide, ligne 10, champ operations[0].timeoutSeconds: "),
+            "{err}"
+        );
+
+        let json = r#"{
+  "schemaVersion": 2,
+  "name": "Corrections",
+  "version": "1",
+  "operations": [
+    { "id": "a", "label": "Supprimer", "kind": "delete-file" }
+  ]
+}"#;
+        let err = parse_workflow(json, WorkflowFileFormat::Json)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("Workflow invalide, ligne 6, champ operations[0]"),
+            "{err}"
+        );
+        assert!(err.contains("target"), "{err}");
+    }
+
+    #[test]
+    fn nested_field_is_located_only_when_the_file_shows_it() {
+        let operations = "\
+operations:
+  - id: si
+    label: Vérifier
+    kind: if
+    test:
+      exists: main.c
+    then:
+      - id: b
+        label: Compiler
+        kind: exec
+        command: make
+        shell: default
+        timeoutSeconds: jamais
+";
+        let source = format!("schemaVersion: 2\nname: Corrections\nversion: '1'\n{operations}");
+        let err = parse_workflow(&source, WorkflowFileFormat::Yaml)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with(
+                "Workflow invalide, ligne 16, champ operations[0].then[0].timeoutSeconds: "
+            ),
+            "{err}"
+        );
+
+        // En version 1, les identifiants ajoutés par la migration décalent le document: le
+        // champ reste signalé, sans ligne.
+        let source = format!(
+            "name: Corrections\n{}",
+            operations.replace("    id: b\n", "")
+        );
+        let source = source.replace("  - id: si\n    label", "  - label");
+        let err = parse_workflow(&source, WorkflowFileFormat::Yaml)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("Workflow invalide, champ operations[0].then[0].timeoutSeconds: "),
+            "{err}"
+        );
+    }
+}
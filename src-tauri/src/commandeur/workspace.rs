@@ -11,12 +11,13 @@ use tempfile::TempDir;
 use uuid::Uuid;
 use walkdir::WalkDir;
 use zip::write::FileOptions;
-use zip::CompressionMethod;
+use zip::{CompressionMethod, ZipArchive};
 
 use crate::commandeur::errors::CommandeurError;
 use crate::commandeur::execution_control::ExecutionControl;
 use crate::commandeur::models::{CommandeurExecutionLogEntry, CommandeurValidationMessage};
-use crate::commandeur::storage;
+use crate::commandeur::storage::Storage;
+use crate::zip_utils::is_zip_like;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -26,6 +27,8 @@ pub struct CommandeurWorkspaceSummary {
     pub source_path: String,
     pub extracted_path: Option<String>,
     pub sub_folders: Vec<String>,
+    #[serde(default)]
+    pub expanded_archives: Vec<NestedArchiveExpansion>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -35,6 +38,36 @@ pub enum WorkspaceMode {
     Directory,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NestedArchiveMode {
+    Ignore,
+    Replace,
+    KeepBoth,
+}
+
+impl Default for NestedArchiveMode {
+    fn default() -> Self {
+        NestedArchiveMode::Ignore
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceOptions {
+    #[serde(default)]
+    pub nested_archives: NestedArchiveMode,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NestedArchiveExpansion {
+    pub archive: String,
+    pub directory: Option<String>,
+    pub archive_kept: bool,
+    pub error: Option<String>,
+}
+
 pub struct WorkspaceHandle {
     pub id: String,
     pub mode: WorkspaceMode,
@@ -43,6 +76,7 @@ pub struct WorkspaceHandle {
     pub extracted: Option<TempDir>,
     pub created_at: DateTime<Utc>,
     pub sub_folders: Vec<String>,
+    pub expanded_archives: Vec<NestedArchiveExpansion>,
 }
 
 impl WorkspaceHandle {
@@ -60,18 +94,28 @@ pub struct ActiveExecution {
 pub struct AppState {
     workspaces: Arc<Mutex<HashMap<String, Arc<Mutex<WorkspaceHandle>>>>>,
     execution: Arc<Mutex<Option<ActiveExecution>>>,
+    storage: Storage,
 }
 
 impl Default for AppState {
     fn default() -> Self {
+        Self::with_storage(Storage::user())
+    }
+}
+
+impl AppState {
+    pub fn with_storage(storage: Storage) -> Self {
         Self {
             workspaces: Arc::new(Mutex::new(HashMap::new())),
             execution: Arc::new(Mutex::new(None)),
+            storage,
         }
     }
-}
 
-impl AppState {
+    pub fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
     pub fn register_workspace(&self, handle: WorkspaceHandle) -> Result<()> {
         let mut guard = self
             .workspaces
@@ -110,6 +154,14 @@ impl AppState {
         f(session)
     }
 
+    pub fn has_active_execution(&self) -> Result<bool> {
+        let guard = self
+            .execution
+            .lock()
+            .map_err(|_| anyhow!("Impossible d'obtenir le verrou du state"))?;
+        Ok(guard.is_some())
+    }
+
     pub fn clear_execution(&self) -> Result<()> {
         let mut guard = self
             .execution
@@ -143,22 +195,36 @@ impl AppState {
     }
 }
 
-pub fn prepare_workspace(state: &AppState, path: &str) -> Result<CommandeurWorkspaceSummary> {
+pub fn prepare_workspace(
+    state: &AppState,
+    path: &str,
+    options: &WorkspaceOptions,
+) -> Result<CommandeurWorkspaceSummary> {
     let path_buf = PathBuf::from(path);
     if !path_buf.exists() {
         return Err(anyhow!("Le chemin fourni n'existe pas"));
     }
 
-    if path_buf.is_file() {
-        return Err(anyhow!(
-            "Seuls les dossiers sont supportés pour Commandeur."
-        ));
-    }
-
-    let root_path = auto_descend_single_directory(&path_buf)?;
-    let mode = WorkspaceMode::Directory;
-    let extracted: Option<TempDir> = None;
     let source_path = path_buf.clone();
+    let (mode, extracted, root_path) = if path_buf.is_file() {
+        let is_archive = path_buf
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(is_zip_like)
+            .unwrap_or(false);
+        if !is_archive {
+            return Err(anyhow!(
+                "Seuls les dossiers et les archives ZIP sont supportés pour Commandeur."
+            ));
+        }
+        let temp_dir = TempDir::new()?;
+        extract_zip_archive(&path_buf, temp_dir.path())?;
+        let root_path = auto_descend_single_directory(temp_dir.path())?;
+        (WorkspaceMode::Zip, Some(temp_dir), root_path)
+    } else {
+        let root_path = auto_descend_single_directory(&path_buf)?;
+        (WorkspaceMode::Directory, None, root_path)
+    };
 
     let sub_folders = collect_first_level_directories(&root_path)?;
     if sub_folders.is_empty() {
@@ -167,6 +233,11 @@ pub fn prepare_workspace(state: &AppState, path: &str) -> Result<CommandeurWorks
         ));
     }
 
+    let expanded_archives = match options.nested_archives {
+        NestedArchiveMode::Ignore => Vec::new(),
+        mode => expand_nested_archives(&root_path, &sub_folders, mode)?,
+    };
+
     let workspace_id = new_id();
     let summary = CommandeurWorkspaceSummary {
         workspace_id: workspace_id.clone(),
@@ -176,6 +247,7 @@ pub fn prepare_workspace(state: &AppState, path: &str) -> Result<CommandeurWorks
             .as_ref()
             .map(|dir| dir.path().to_string_lossy().to_string()),
         sub_folders: sub_folders.clone(),
+        expanded_archives: expanded_archives.clone(),
     };
 
     let handle = WorkspaceHandle {
@@ -186,6 +258,7 @@ pub fn prepare_workspace(state: &AppState, path: &str) -> Result<CommandeurWorks
         extracted,
         created_at: Utc::now(),
         sub_folders,
+        expanded_archives,
     };
 
     state.register_workspace(handle)?;
@@ -215,6 +288,118 @@ pub fn auto_descend_single_directory(base: impl AsRef<Path>) -> Result<PathBuf>
     }
 }
 
+pub fn extract_zip_archive(archive_path: &Path, destination: &Path) -> Result<()> {
+    let file = fs::File::open(archive_path)?;
+    let mut archive = ZipArchive::new(file).map_err(|err| {
+        anyhow!(
+            "Archive ZIP illisible ({}): {}",
+            archive_path.display(),
+            err
+        )
+    })?;
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let relative = entry
+            .enclosed_name()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| anyhow!("Entrée d'archive invalide: {}", entry.name()))?;
+        let out_path = destination.join(relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path)?;
+            continue;
+        }
+        ensure_parent_dir(&out_path)?;
+        let mut out_file = fs::File::create(&out_path)?;
+        io::copy(&mut entry, &mut out_file)?;
+    }
+    Ok(())
+}
+
+/// Extrait les archives ZIP trouvées dans les dossiers étudiants, y compris celles
+/// contenues dans une archive déjà extraite. Chaque archive est décompressée dans un
+/// dossier voisin portant son nom sans extension.
+pub fn expand_nested_archives(
+    root: &Path,
+    folders: &[String],
+    mode: NestedArchiveMode,
+) -> Result<Vec<NestedArchiveExpansion>> {
+    let keep_archive = mode == NestedArchiveMode::KeepBoth;
+    let mut expansions = Vec::new();
+    for folder in folders {
+        let mut pending = vec![root.join(folder)];
+        while let Some(dir) = pending.pop() {
+            let archives: Vec<PathBuf> = WalkDir::new(&dir)
+                .follow_links(false)
+                .into_iter()
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| {
+                    entry.file_type().is_file()
+                        && entry.file_name().to_str().map(is_zip_like).unwrap_or(false)
+                })
+                .map(|entry| entry.into_path())
+                .collect();
+            for archive in archives {
+                let destination = archive.with_extension("");
+                if keep_archive && destination.exists() {
+                    continue;
+                }
+                let destination = unique_directory_path(destination);
+                let relative_archive = relative_display(root, &archive);
+                fs::create_dir_all(&destination)?;
+                match extract_zip_archive(&archive, &destination) {
+                    Ok(()) => {
+                        if !keep_archive {
+                            fs::remove_file(&archive)?;
+                        }
+                        expansions.push(NestedArchiveExpansion {
+                            archive: relative_archive,
+                            directory: Some(relative_display(root, &destination)),
+                            archive_kept: keep_archive,
+                            error: None,
+                        });
+                        pending.push(destination);
+                    }
+                    Err(err) => {
+                        let _ = fs::remove_dir_all(&destination);
+                        expansions.push(NestedArchiveExpansion {
+                            archive: relative_archive,
+                            directory: None,
+                            archive_kept: true,
+                            error: Some(err.to_string()),
+                        });
+                    }
+                }
+            }
+        }
+    }
+    Ok(expansions)
+}
+
+fn unique_directory_path(candidate: PathBuf) -> PathBuf {
+    if !candidate.exists() {
+        return candidate;
+    }
+    let name = candidate
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "archive".to_string());
+    let mut idx = 1;
+    loop {
+        let next = candidate.with_file_name(format!("{}-{}", name, idx));
+        if !next.exists() {
+            return next;
+        }
+        idx += 1;
+    }
+}
+
+fn relative_display(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
 pub fn collect_first_level_directories(root: &Path) -> Result<Vec<String>> {
     let mut dirs = Vec::new();
     for entry in fs::read_dir(root)? {
@@ -262,16 +447,20 @@ pub fn ensure_parent_dir(path: &Path) -> io::Result<()> {
     Ok(())
 }
 
+/// Chemin du journal texte, connu avant son écriture pour être cité dans l'historique.
+pub fn execution_log_path(storage: &Storage) -> Result<PathBuf> {
+    let filename = format!("commandeur-log-{}.txt", Utc::now().format("%Y%m%d-%H%M%S"));
+    Ok(storage.logs_dir()?.join(filename))
+}
+
 pub fn write_execution_log(
+    file_path: &Path,
     workspace: &WorkspaceHandle,
     entries: &[CommandeurExecutionLogEntry],
     warnings: &[CommandeurValidationMessage],
     errors: &[CommandeurValidationMessage],
-) -> Result<PathBuf> {
-    let logs_dir = storage::logs_dir()?;
-    let filename = format!("commandeur-log-{}.txt", Utc::now().format("%Y%m%d-%H%M%S"));
-    let file_path = logs_dir.join(filename);
-    let mut file = fs::File::create(&file_path)?;
+) -> Result<()> {
+    let mut file = fs::File::create(file_path)?;
     writeln!(file, "Raisin Commandeur - Journal d'exécution")?;
     writeln!(file, "Date: {}", Utc::now().to_rfc3339())?;
     writeln!(file, "Source: {}", workspace.source_path.display())?;
@@ -296,6 +485,18 @@ pub fn write_execution_log(
         }
     )?;
     writeln!(file, "Sous-dossiers: {}", workspace.sub_folders.join(", "))?;
+    if !workspace.expanded_archives.is_empty() {
+        writeln!(file, "Archives imbriquées:")?;
+        for expansion in &workspace.expanded_archives {
+            match (&expansion.directory, &expansion.error) {
+                (Some(directory), _) => writeln!(file, "- {} -> {}", expansion.archive, directory)?,
+                (None, Some(error)) => {
+                    writeln!(file, "- {} (échec: {})", expansion.archive, error)?
+                }
+                (None, None) => writeln!(file, "- {}", expansion.archive)?,
+            }
+        }
+    }
     writeln!(file, "")?;
     writeln!(file, "== Chronologie ==")?;
     for entry in entries {
@@ -307,40 +508,41 @@ pub fn write_execution_log(
             entry.operation_label,
             entry.message
         )?;
-    }
-    if !warnings.is_empty() {
-        writeln!(file, "")?;
-        writeln!(file, "== Avertissements ==")?;
-        for warn in warnings {
-            writeln!(
-                file,
-                "- [{}] {} ({:?})",
-                warn.operation_id,
-                warn.message,
-                warn.folders.as_ref().map(|f| f.join(", "))
-            )?;
-            if let Some(details) = &warn.details {
-                writeln!(file, "    > {}", details)?;
-            }
+        for artifact in &entry.artifacts {
+            writeln!(file, "    -> {}", artifact)?;
         }
     }
-    if !errors.is_empty() {
-        writeln!(file, "")?;
-        writeln!(file, "== Erreurs ==")?;
-        for err in errors {
-            writeln!(
+    write_messages(&mut file, "Avertissements", warnings)?;
+    write_messages(&mut file, "Erreurs", errors)?;
+    Ok(())
+}
+
+fn write_messages(
+    file: &mut fs::File,
+    title: &str,
+    messages: &[CommandeurValidationMessage],
+) -> Result<()> {
+    if messages.is_empty() {
+        return Ok(());
+    }
+    writeln!(file)?;
+    writeln!(file, "== {title} ==")?;
+    for message in messages {
+        match message.folders.as_deref() {
+            Some(folders) if !folders.is_empty() => writeln!(
                 file,
-                "- [{}] {} ({:?})",
-                err.operation_id,
-                err.message,
-                err.folders.as_ref().map(|f| f.join(", "))
-            )?;
-            if let Some(details) = &err.details {
-                writeln!(file, "    > {}", details)?;
-            }
+                "- [{}] {} ({})",
+                message.operation_id,
+                message.message,
+                folders.join(", ")
+            )?,
+            _ => writeln!(file, "- [{}] {}", message.operation_id, message.message)?,
+        }
+        if let Some(details) = &message.details {
+            writeln!(file, "    > {}", details)?;
         }
     }
-    Ok(file_path)
+    Ok(())
 }
 
 pub fn repack_zip(workspace: &WorkspaceHandle) -> Result<PathBuf> {
@@ -390,3 +592,104 @@ pub fn repack_zip(workspace: &WorkspaceHandle) -> Result<PathBuf> {
 pub fn new_id() -> String {
     Uuid::new_v4().to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_zip(path: &Path, files: &[(&str, &[u8])]) {
+        let file = fs::File::create(path).expect("create zip");
+        let mut writer = zip::ZipWriter::new(file);
+        let options = FileOptions::default();
+        for (name, data) in files {
+            writer.start_file(*name, options).expect("start file");
+            writer.write_all(data).expect("write entry");
+        }
+        writer.finish().expect("finish zip");
+    }
+
+    #[test]
+    fn zip_workspace_is_extracted_and_repacked_next_to_source() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let archive = temp.path().join("Lot1030.zip");
+        write_zip(
+            &archive,
+            &[
+                ("Lot1030/Alice/Main.java", b"class Main {}"),
+                ("Lot1030/Bob/README.md", b"# Bob"),
+            ],
+        );
+        let original = fs::read(&archive).expect("read archive");
+
+        let state = AppState::with_storage(Storage::at(temp.path().join("donnees")));
+        let summary = prepare_workspace(
+            &state,
+            archive.to_str().unwrap(),
+            &WorkspaceOptions::default(),
+        )
+        .expect("prepare workspace");
+        assert!(matches!(summary.mode, WorkspaceMode::Zip));
+        assert_eq!(summary.sub_folders, vec!["Alice", "Bob"]);
+        assert!(summary.extracted_path.is_some());
+
+        let handle = state
+            .get_workspace(&summary.workspace_id)
+            .expect("workspace");
+        let guard = handle.lock().expect("lock");
+        assert!(guard
+            .folder_absolute_path("Alice")
+            .join("Main.java")
+            .is_file());
+
+        let repacked = repack_zip(&guard).expect("repack");
+        assert_eq!(repacked, temp.path().join("Lot1030-commandeur.zip"));
+        assert_eq!(fs::read(&archive).expect("read archive"), original);
+    }
+
+    #[test]
+    fn non_zip_files_are_rejected() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let file = temp.path().join("notes.txt");
+        fs::write(&file, "hello").expect("write file");
+        let state = AppState::with_storage(Storage::at(temp.path().join("donnees")));
+        assert!(
+            prepare_workspace(&state, file.to_str().unwrap(), &WorkspaceOptions::default())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn nested_student_archives_are_expanded_in_place() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = temp.path().join("lot");
+        let alice = root.join("Alice");
+        let bob = root.join("Bob");
+        fs::create_dir_all(&alice).expect("create alice");
+        fs::create_dir_all(&bob).expect("create bob");
+        write_zip(
+            &alice.join("Intra.zip"),
+            &[("Intra/pom.xml", b"<project/>")],
+        );
+        write_zip(&bob.join("Intra.zip"), &[("Intra/pom.xml", b"<project/>")]);
+
+        let replaced =
+            expand_nested_archives(&root, &["Alice".to_string()], NestedArchiveMode::Replace)
+                .expect("expand alice");
+        assert_eq!(replaced.len(), 1);
+        assert_eq!(replaced[0].directory.as_deref(), Some("Alice/Intra"));
+        assert!(alice.join("Intra/Intra/pom.xml").is_file());
+        assert!(!alice.join("Intra.zip").exists());
+
+        let kept = expand_nested_archives(&root, &["Bob".to_string()], NestedArchiveMode::KeepBoth)
+            .expect("expand bob");
+        assert_eq!(kept.len(), 1);
+        assert!(bob.join("Intra.zip").is_file());
+        assert!(bob.join("Intra/Intra/pom.xml").is_file());
+
+        let again =
+            expand_nested_archives(&root, &["Bob".to_string()], NestedArchiveMode::KeepBoth)
+                .expect("expand bob twice");
+        assert!(again.is_empty());
+    }
+}
@@ -2,6 +2,7 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod commandeur;
+mod zip_utils;
 
 use serde::{Deserialize, Serialize};
 use std::{
@@ -12,6 +13,7 @@ use std::{
 };
 use tauri::api::dialog::blocking::FileDialogBuilder;
 use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
+use zip_utils::is_zip_like;
 
 fn ensure_directory(path: &str, entries: &mut Vec<ZipEntryMeta>, added: &mut HashSet<String>) {
     if path.is_empty() {
@@ -48,12 +50,6 @@ fn ensure_parent_directories(
     }
 }
 
-fn is_zip_like(name: &str) -> bool {
-    let trimmed = name.trim();
-    let lower = trimmed.to_ascii_lowercase();
-    lower.ends_with(".zip") || lower.ends_with(".zipx")
-}
-
 fn collect_zip_entries<R: Read + std::io::Seek>(
     archive: &mut ZipArchive<R>,
     prefix: &str,
@@ -942,7 +938,11 @@ fn main() {
             list_available_shells,
             commandeur::commandeur_prepare_workspace,
             commandeur::commandeur_validate_workflow,
+            commandeur::commandeur_dry_run_workflow,
             commandeur::commandeur_execute_workflow,
+            commandeur::commandeur_resume_execution,
+            commandeur::commandeur_preview_folder_selection,
+            commandeur::commandeur_restore_snapshot,
             commandeur::commandeur_execution_pause,
             commandeur::commandeur_execution_resume,
             commandeur::commandeur_execution_stop,
@@ -951,6 +951,15 @@ fn main() {
             commandeur::commandeur_load_saved_workflow,
             commandeur::commandeur_delete_saved_workflow,
             commandeur::commandeur_duplicate_saved_workflow,
+            commandeur::commandeur_import_workflow,
+            commandeur::commandeur_export_workflow,
+            commandeur::commandeur_workflow_schema_version,
+            commandeur::commandeur_list_runs,
+            commandeur::commandeur_get_run_result,
+            commandeur::commandeur_export_run_summary,
+            commandeur::commandeur_open_run_report,
+            commandeur::commandeur_open_run,
+            commandeur::commandeur_delete_run,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -242,4 +242,26 @@ export const commandeurStyles = {
     background: "#f9fafb",
     textAlign: "center" as const,
   },
+  summaryTableWrapper: {
+    overflowX: "auto" as const,
+    border: "1px solid #e5e7eb",
+    borderRadius: ".65rem",
+  },
+  summaryTable: {
+    borderCollapse: "collapse" as const,
+    width: "100%",
+    fontSize: ".72rem",
+  },
+  summaryHeaderCell: {
+    textAlign: "left" as const,
+    padding: ".35rem .5rem",
+    background: "#f3f4f6",
+    borderBottom: "1px solid #e5e7eb",
+    whiteSpace: "nowrap" as const,
+  },
+  summaryCell: {
+    padding: ".3rem .5rem",
+    borderBottom: "1px solid #f3f4f6",
+    whiteSpace: "nowrap" as const,
+  },
 };
@@ -15,15 +15,21 @@ import ExecutionStep from "./ExecutionStep";
 import Toast, { type ToastMessage, type ToastTone } from "../Toast/Toast";
 import type {
   CommandeurExecutionLogEntry,
+  CommandeurExecutionOutput,
   CommandeurExecutionResult,
+  CommandeurExecutionActivity,
   CommandeurExecutionStatus,
+  CommandeurFolderSelection,
+  CommandeurLiveOutputLine,
   CommandeurExecutionProgress,
   CommandeurValidationMessage,
   CommandeurWorkflow,
   CommandeurWorkspaceSummary,
 } from "../../types";
 import {
+  continueCommandeurExecution,
   executeCommandeurWorkflow,
+  getCommandeurRunResult,
   pauseCommandeurExecution,
   resumeCommandeurExecution,
   stopCommandeurExecution,
@@ -39,6 +45,8 @@ type StepId = 0 | 1 | 2;
 const LOG_EVENT = "commandeur://execution-log";
 const VALIDATION_EVENT = "commandeur://execution-validation";
 const PROGRESS_EVENT = "commandeur://execution-progress";
+const OUTPUT_EVENT = "commandeur://execution-output";
+const LIVE_OUTPUT_LIMIT = 500;
 
 export const Commandeur: React.FC<CommandeurProps> = ({ onBack }) => {
   const [currentStep, setCurrentStep] = useState<StepId>(0);
@@ -50,6 +58,9 @@ export const Commandeur: React.FC<CommandeurProps> = ({ onBack }) => {
   const [workflowPath, setWorkflowPath] = useState<string | null>(null);
   const [savedWorkflowId, setSavedWorkflowId] = useState<string | null>(null);
 
+  const [liveOutput, setLiveOutput] = useState<CommandeurLiveOutputLine[]>(
+    []
+  );
   const [liveLogEntries, setLiveLogEntries] = useState<
     CommandeurExecutionLogEntry[]
   >([]);
@@ -74,6 +85,9 @@ export const Commandeur: React.FC<CommandeurProps> = ({ onBack }) => {
   const [executionError, setExecutionError] = useState<string | null>(null);
   const [executionStatus, setExecutionStatus] =
     useState<CommandeurExecutionStatus>("idle");
+  const [executionActivities, setExecutionActivities] = useState<
+    CommandeurExecutionActivity[]
+  >([]);
   const [executionProgress, setExecutionProgress] =
     useState<CommandeurExecutionProgress>({
       operationsProcessed: 0,
@@ -99,6 +113,7 @@ export const Commandeur: React.FC<CommandeurProps> = ({ onBack }) => {
     let unlistenLog: UnlistenFn | null = null;
     let unlistenValidation: UnlistenFn | null = null;
     let unlistenProgress: UnlistenFn | null = null;
+    let unlistenOutput: UnlistenFn | null = null;
     let active = true;
 
     (async () => {
@@ -128,6 +143,29 @@ export const Commandeur: React.FC<CommandeurProps> = ({ onBack }) => {
             setExecutionProgress(event.payload);
           }
         );
+        unlistenOutput = await listen<CommandeurExecutionOutput>(
+          OUTPUT_EVENT,
+          (event) => {
+            if (!active) return;
+            const { folder, operationId, lines, droppedLines } = event.payload;
+            const incoming: CommandeurLiveOutputLine[] = lines.map((line) => ({
+              ...line,
+              folder,
+              operationId,
+            }));
+            if (droppedLines > 0) {
+              incoming.push({
+                folder,
+                operationId,
+                stream: "stderr",
+                text: `… ${droppedLines} ligne(s) non affichée(s), voir les sorties enregistrées`,
+              });
+            }
+            setLiveOutput((prev) =>
+              [...prev, ...incoming].slice(-LIVE_OUTPUT_LIMIT)
+            );
+          }
+        );
       } catch (err) {
         console.warn("Impossible d'écouter les événements Commandeur", err);
       }
@@ -138,6 +176,7 @@ export const Commandeur: React.FC<CommandeurProps> = ({ onBack }) => {
       if (unlistenLog) unlistenLog();
       if (unlistenValidation) unlistenValidation();
       if (unlistenProgress) unlistenProgress();
+      if (unlistenOutput) unlistenOutput();
     };
   }, []);
 
@@ -178,6 +217,7 @@ export const Commandeur: React.FC<CommandeurProps> = ({ onBack }) => {
     setExecutionResult(null);
     setExecutionError(null);
     setLiveLogEntries([]);
+    setLiveOutput([]);
     setLiveWarnings([]);
     setLiveErrors([]);
     setSavedWorkflowId(null);
@@ -269,6 +309,7 @@ export const Commandeur: React.FC<CommandeurProps> = ({ onBack }) => {
       setExecutionResult(null);
       setExecutionError(null);
       setLiveLogEntries([]);
+      setLiveOutput([]);
       setLiveWarnings([]);
       setLiveErrors([]);
       setExecutionStatus("idle");
@@ -344,44 +385,83 @@ export const Commandeur: React.FC<CommandeurProps> = ({ onBack }) => {
     void handleValidate();
   }, [workspace, workflow, workspaceConfirmed, isValidating, handleValidate]);
 
-  const handleExecute = useCallback(async () => {
-    if (!workspace || !workflow) return;
-    if (executionStatus !== "idle") return;
-    try {
-      setExecutionResult(null);
-      setExecutionStatus("running");
-      setExecutionError(null);
-      setLiveLogEntries([]);
-      setLiveWarnings([]);
-      setLiveErrors([]);
+  const runExecution = useCallback(
+    async (
+      start: () => Promise<CommandeurExecutionResult>,
+      operationsTotal: number
+    ) => {
+      if (executionStatus !== "idle") return;
+      try {
+        setExecutionResult(null);
+        setExecutionStatus("running");
+        setExecutionError(null);
+        setLiveLogEntries([]);
+        setLiveWarnings([]);
+        setLiveErrors([]);
+        setExecutionProgress({ operationsProcessed: 0, operationsTotal });
+        const result = await start();
+        setExecutionResult(result);
+        setLiveLogEntries(result.logEntries);
+        setLiveWarnings(result.warnings);
+        setLiveErrors(result.errors);
+      } catch (err) {
+        setExecutionError(err instanceof Error ? err.message : String(err));
+        setExecutionProgress({ operationsProcessed: 0, operationsTotal: 0 });
+      } finally {
+        setExecutionStatus("idle");
+        setExecutionActivities([]);
+      }
+    },
+    [executionStatus]
+  );
+
+  const handleExecute = useCallback(
+    async (selection?: CommandeurFolderSelection) => {
+      if (!workspace || !workflow) return;
       const enabledOperations = workflow.operations.filter(
         (op) => op.enabled
       ).length;
-      const foldersCount = workspace.subFolders.length;
-      setExecutionProgress({
-        operationsProcessed: 0,
-        operationsTotal: enabledOperations * foldersCount,
-      });
-      const result = await executeCommandeurWorkflow(
-        workspace.workspaceId,
-        workflow
+      const foldersCount =
+        selection?.folders?.length ?? workspace.subFolders.length;
+      await runExecution(
+        () =>
+          executeCommandeurWorkflow(
+            workspace.workspaceId,
+            workflow,
+            { selection, workflowId: savedWorkflowId ?? undefined }
+          ),
+        enabledOperations * foldersCount
       );
-      setExecutionResult(result);
-      setLiveLogEntries(result.logEntries);
-      setLiveWarnings(result.warnings);
-      setLiveErrors(result.errors);
-    } catch (err) {
-      setExecutionError(err instanceof Error ? err.message : String(err));
-      setExecutionProgress({ operationsProcessed: 0, operationsTotal: 0 });
-    } finally {
-      setExecutionStatus("idle");
-    }
-  }, [workspace, workflow, executionStatus]);
+    },
+    [workspace, workflow, savedWorkflowId, runExecution]
+  );
+
+  const handleContinueExecution = useCallback(
+    async (runId: string) => {
+      if (!workspace) return;
+      // Le total restant est connu côté backend et arrive par l'événement de progression.
+      await runExecution(
+        () => continueCommandeurExecution(workspace.workspaceId, runId),
+        0
+      );
+    },
+    [workspace, runExecution]
+  );
+
+  const handleShowRun = useCallback(async (runId: string) => {
+    const result = await getCommandeurRunResult(runId);
+    setExecutionError(null);
+    setExecutionResult(result);
+    setLiveLogEntries(result.logEntries);
+    setLiveWarnings(result.warnings);
+    setLiveErrors(result.errors);
+  }, []);
 
   const handlePauseExecution = useCallback(async () => {
     try {
-      const status = await pauseCommandeurExecution();
-      setExecutionStatus(status);
+      const report = await pauseCommandeurExecution();
+      setExecutionStatus(report.status);
+      setExecutionActivities(report.activities);
     } catch (err) {
       pushToast({
         tone: "error",
@@ -395,8 +475,9 @@ export const Commandeur: React.FC<CommandeurProps> = ({ onBack }) => {
 
   const handleResumeExecution = useCallback(async () => {
     try {
-      const status = await resumeCommandeurExecution();
-      setExecutionStatus(status);
+      const report = await resumeCommandeurExecution();
+      setExecutionStatus(report.status);
+      setExecutionActivities(report.activities);
     } catch (err) {
       pushToast({
         tone: "error",
@@ -410,8 +491,9 @@ export const Commandeur: React.FC<CommandeurProps> = ({ onBack }) => {
 
   const handleStopExecution = useCallback(async () => {
     try {
-      const status = await stopCommandeurExecution();
-      setExecutionStatus(status);
+      const report = await stopCommandeurExecution();
+      setExecutionStatus(report.status);
+      setExecutionActivities(report.activities);
     } catch (err) {
       pushToast({
         tone: "error",
@@ -474,11 +556,15 @@ export const Commandeur: React.FC<CommandeurProps> = ({ onBack }) => {
             workflow={workflow}
             validationMessages={validationMessages}
             onExecute={handleExecute}
+            onContinue={handleContinueExecution}
+            onShowRun={handleShowRun}
             executionResult={executionResult}
             executionError={executionError}
             isExecuting={executionStatus === "running"}
             executionStatus={executionStatus}
+            executionActivities={executionActivities}
             liveLogEntries={liveLogEntries}
+            liveOutput={liveOutput}
             liveWarnings={liveWarnings}
             liveErrors={liveErrors}
             executionProgress={executionProgress}
@@ -3,24 +3,34 @@ import { commandeurStyles } from "./Commandeur.styles";
 import type {
   CommandeurExecutionLogEntry,
   CommandeurExecutionResult,
+  CommandeurExecutionActivity,
   CommandeurExecutionStatus,
   CommandeurExecutionProgress,
+  CommandeurFolderSelection,
+  CommandeurLiveOutputLine,
   CommandeurValidationMessage,
   CommandeurWorkflow,
   CommandeurWorkspaceSummary,
 } from "../../types";
 import ProgressBar from "../ProgressBar/ProgressBar";
+import FolderSelectionPanel from "./FolderSelectionPanel";
+import RunHistoryPanel from "./RunHistoryPanel";
+import ExecutionSummaryTable from "./ExecutionSummaryTable";
 
 interface ExecutionStepProps {
   workspace: CommandeurWorkspaceSummary | null;
   workflow: CommandeurWorkflow | null;
   validationMessages: CommandeurValidationMessage[];
-  onExecute: () => Promise<void>;
+  onExecute: (selection?: CommandeurFolderSelection) => Promise<void>;
+  onContinue: (runId: string) => Promise<void>;
+  onShowRun: (runId: string) => Promise<void>;
   executionResult: CommandeurExecutionResult | null;
   executionError: string | null;
   isExecuting: boolean;
   executionStatus: CommandeurExecutionStatus;
+  executionActivities: CommandeurExecutionActivity[];
   liveLogEntries: CommandeurExecutionLogEntry[];
+  liveOutput: CommandeurLiveOutputLine[];
   liveWarnings: CommandeurValidationMessage[];
   liveErrors: CommandeurValidationMessage[];
   isDesktopRuntime: boolean;
@@ -48,11 +58,15 @@ export const ExecutionStep: React.FC<ExecutionStepProps> = ({
   workflow,
   validationMessages,
   onExecute,
+  onContinue,
+  onShowRun,
   executionResult,
   executionError,
   isExecuting,
   executionStatus,
+  executionActivities,
   liveLogEntries,
+  liveOutput,
   liveWarnings,
   liveErrors,
   executionProgress,
@@ -88,6 +102,9 @@ export const ExecutionStep: React.FC<ExecutionStepProps> = ({
   const prevScrollHeight = useRef(0);
   const [isUserScrolling, setIsUserScrolling] = useState(false);
   const [autoScrollEnabled, setAutoScrollEnabled] = useState(true);
+  const [selection, setSelection] = useState<
+    CommandeurFolderSelection | undefined
+  >(undefined);
   const { operationsProcessed, operationsTotal } = executionProgress;
   const progressValue = useMemo(() => {
     if (operationsTotal > 0) {
@@ -192,16 +209,34 @@ export const ExecutionStep: React.FC<ExecutionStepProps> = ({
         </div>
       )}
 
+      {workspace && (
+        <FolderSelectionPanel
+          workspace={workspace}
+          lastRunId={executionResult?.runId ?? null}
+          disabled={isExecutionActive}
+          onChange={setSelection}
+        />
+      )}
+
       <div style={commandeurStyles.actionsRow}>
         <button
           className="btn btn-primary"
-          onClick={onExecute}
+          onClick={() => void onExecute(selection)}
           disabled={
             !workspace || !workflow || isExecutionActive || hasBlockingErrors
           }
         >
           {isExecutionActive ? "Exécution en cours…" : "Lancer l'exécution"}
         </button>
+        {executionResult && !executionResult.success && !isExecutionActive && (
+          <button
+            className="btn"
+            onClick={() => void onContinue(executionResult.runId)}
+            title="Ignore les dossiers terminés et les opérations déjà effectuées"
+          >
+            Reprendre là où elle s'est arrêtée
+          </button>
+        )}
         {executionStatus === "running" && (
           <>
             <button
@@ -230,6 +265,22 @@ export const ExecutionStep: React.FC<ExecutionStepProps> = ({
             </button>
           </>
         )}
+        {executionStatus === "paused" &&
+          (executionActivities.length > 0 ? (
+            executionActivities.map((activity) => (
+              <span
+                key={activity.folder}
+                style={commandeurStyles.badge("warning")}
+              >
+                En pause dans « {activity.operationLabel} » pour{" "}
+                {activity.folder}
+              </span>
+            ))
+          ) : (
+            <span style={commandeurStyles.badge("warning")}>
+              En pause entre deux opérations
+            </span>
+          ))}
         {executionStatus === "stopping" && (
           <span style={commandeurStyles.badge("warning")}>Arrêt en cours…</span>
         )}
@@ -296,17 +347,59 @@ export const ExecutionStep: React.FC<ExecutionStepProps> = ({
               <span style={commandeurStyles.badge("neutral")}>
                 Opérations exécutées : {executionResult.operationsRun}
               </span>
+              {executionResult.folderStatuses.some(
+                (entry) => entry.status !== "succeeded"
+              ) && (
+                <span style={commandeurStyles.badge("warning")}>
+                  Dossiers à relancer :{" "}
+                  {
+                    executionResult.folderStatuses.filter(
+                      (entry) => entry.status !== "succeeded"
+                    ).length
+                  }{" "}
+                  (exécution {executionResult.runId})
+                </span>
+              )}
               <span style={commandeurStyles.badge("neutral")}>
                 Fichier log : {executionResult.logFilePath}
               </span>
+              {executionResult.runLogPath && (
+                <span style={commandeurStyles.badge("neutral")}>
+                  Journal JSON : {executionResult.runLogPath}
+                </span>
+              )}
+              {executionResult.reportPath && (
+                <span style={commandeurStyles.badge("neutral")}>
+                  Rapport HTML : {executionResult.reportPath}
+                </span>
+              )}
+              {executionResult.summaryCsvPath && (
+                <span style={commandeurStyles.badge("neutral")}>
+                  Tableau CSV : {executionResult.summaryCsvPath}
+                </span>
+              )}
               {executionResult.outputArchivePath && (
                 <span style={commandeurStyles.badge("neutral")}>
                   Archive générée : {executionResult.outputArchivePath}
                 </span>
               )}
+              {executionResult.artifacts.length > 0 && (
+                <span style={commandeurStyles.badge("neutral")}>
+                  Sorties enregistrées ({executionResult.artifacts.length}) :{" "}
+                  {executionResult.runDirectory}
+                </span>
+              )}
             </div>
           )}
 
+          {executionResult && (
+            <ExecutionSummaryTable
+              runId={executionResult.runId}
+              summary={executionResult.summary}
+              isDesktopRuntime={isDesktopRuntime}
+            />
+          )}
+
           <div>
             <h4 style={{ margin: "0 0 .35rem" }}>Journal d'exécution</h4>
             {hasLogEntries ? (
@@ -367,6 +460,27 @@ export const ExecutionStep: React.FC<ExecutionStepProps> = ({
             )}
           </div>
 
+          {isExecutionActive && liveOutput.length > 0 && (
+            <div>
+              <h4 style={{ margin: "0 0 .35rem" }}>Sortie en direct</h4>
+              <div style={commandeurStyles.logList}>
+                {liveOutput.map((line, index) => (
+                  <div
+                    key={`${index}-${line.folder}-${line.operationId}`}
+                    style={{
+                      fontFamily: "monospace",
+                      fontSize: ".75rem",
+                      whiteSpace: "pre-wrap",
+                      color: line.stream === "stderr" ? "#b91c1c" : "#1f2937",
+                    }}
+                  >
+                    [{line.folder}] {line.text}
+                  </div>
+                ))}
+              </div>
+            </div>
+          )}
+
           <div>
             <h4 style={{ margin: "0 0 .35rem" }}>Avertissements</h4>
             {hasWarnings ? (
@@ -478,6 +592,16 @@ export const ExecutionStep: React.FC<ExecutionStepProps> = ({
           </div>
         </section>
       )}
+
+      {isDesktopRuntime && (
+        <RunHistoryPanel
+          sourcePath={workspace?.sourcePath ?? null}
+          refreshKey={executionResult?.runId ?? null}
+          disabled={isExecutionActive}
+          onShow={onShowRun}
+          onContinue={onContinue}
+        />
+      )}
     </div>
   );
 };
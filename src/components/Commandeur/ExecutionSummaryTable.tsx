@@ -0,0 +1,154 @@
+import React, { useState } from "react";
+import { save } from "@tauri-apps/api/dialog";
+import { commandeurStyles } from "./Commandeur.styles";
+import type {
+  CommandeurExecutionSummary,
+  CommandeurOperationRunStatus,
+} from "../../types";
+import {
+  exportCommandeurRunSummary,
+  openCommandeurRunReport,
+} from "../../services/commandeur/api";
+
+const statusDisplay: Record<
+  CommandeurOperationRunStatus,
+  { label: string; tone: "neutral" | "success" | "warning" | "error" }
+> = {
+  ok: { label: "OK", tone: "success" },
+  failed: { label: "Échec", tone: "error" },
+  interrupted: { label: "Interrompue", tone: "warning" },
+  skipped: { label: "Ignorée", tone: "neutral" },
+  disabled: { label: "Désactivée", tone: "neutral" },
+  "branch-not-taken": { label: "Branche non prise", tone: "neutral" },
+};
+
+interface ExecutionSummaryTableProps {
+  runId: string;
+  summary: CommandeurExecutionSummary;
+  isDesktopRuntime: boolean;
+}
+
+export const ExecutionSummaryTable: React.FC<ExecutionSummaryTableProps> = ({
+  runId,
+  summary,
+  isDesktopRuntime,
+}) => {
+  const [exportMessage, setExportMessage] = useState<{
+    tone: "success" | "error";
+    text: string;
+  } | null>(null);
+
+  if (summary.folders.length === 0 || summary.columns.length === 0) {
+    return null;
+  }
+
+  const handleExport = async () => {
+    try {
+      const destination = await save({
+        defaultPath: `bilan-${runId}.csv`,
+        filters: [{ name: "Tableau CSV", extensions: ["csv"] }],
+      });
+      if (!destination) return;
+      await exportCommandeurRunSummary(runId, destination);
+      setExportMessage({ tone: "success", text: `Exporté : ${destination}` });
+    } catch (err) {
+      setExportMessage({
+        tone: "error",
+        text: err instanceof Error ? err.message : String(err),
+      });
+    }
+  };
+
+  const handleOpenReport = async () => {
+    try {
+      await openCommandeurRunReport(runId);
+      setExportMessage(null);
+    } catch (err) {
+      setExportMessage({
+        tone: "error",
+        text: err instanceof Error ? err.message : String(err),
+      });
+    }
+  };
+
+  return (
+    <div style={{ display: "flex", flexDirection: "column", gap: ".4rem" }}>
+      <div style={commandeurStyles.badgeRow}>
+        <h4 style={{ margin: 0 }}>Bilan par dossier</h4>
+        {isDesktopRuntime && (
+          <>
+            <button className="btn" onClick={() => void handleExport()}>
+              Exporter en CSV
+            </button>
+            <button className="btn" onClick={() => void handleOpenReport()}>
+              Ouvrir le rapport HTML
+            </button>
+          </>
+        )}
+        {exportMessage && (
+          <span style={commandeurStyles.badge(exportMessage.tone)}>
+            {exportMessage.text}
+          </span>
+        )}
+      </div>
+      <div style={commandeurStyles.summaryTableWrapper}>
+        <table style={commandeurStyles.summaryTable}>
+          <thead>
+            <tr>
+              <th style={commandeurStyles.summaryHeaderCell}>Dossier</th>
+              {summary.columns.map((column) => (
+                <th
+                  key={column.operationId}
+                  style={commandeurStyles.summaryHeaderCell}
+                  title={column.operationId}
+                >
+                  {"› ".repeat(column.depth)}
+                  {column.operationLabel}
+                </th>
+              ))}
+            </tr>
+          </thead>
+          <tbody>
+            {summary.folders.map((row) => (
+              <tr key={row.folder}>
+                <td style={commandeurStyles.summaryCell}>
+                  <strong>{row.folder}</strong>
+                  {row.durationMs !== undefined && (
+                    <span style={{ color: "#6b7280" }}>
+                      {" "}
+                      · {(row.durationMs / 1000).toFixed(1)} s
+                    </span>
+                  )}
+                </td>
+                {row.cells.map((cell) => {
+                  const display = statusDisplay[cell.status];
+                  const details = [
+                    cell.durationMs !== undefined
+                      ? `${cell.durationMs} ms`
+                      : null,
+                    cell.error ?? null,
+                  ]
+                    .filter(Boolean)
+                    .join(" · ");
+                  return (
+                    <td
+                      key={cell.operationId}
+                      style={commandeurStyles.summaryCell}
+                      title={details || undefined}
+                    >
+                      <span style={commandeurStyles.badge(display.tone)}>
+                        {display.label}
+                      </span>
+                    </td>
+                  );
+                })}
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      </div>
+    </div>
+  );
+};
+
+export default ExecutionSummaryTable;
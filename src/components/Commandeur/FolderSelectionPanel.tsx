@@ -0,0 +1,203 @@
+import React, { useEffect, useState } from "react";
+import { commandeurStyles } from "./Commandeur.styles";
+import type {
+  CommandeurConditionTest,
+  CommandeurFolderSelection,
+  CommandeurNamePatternMode,
+  CommandeurWorkspaceSummary,
+} from "../../types";
+import { previewCommandeurFolderSelection } from "../../services/commandeur/api";
+import { ConditionTestEditor } from "./WorkflowEditor/WorkflowEditor";
+
+type SelectionMode = "all" | "list" | "pattern" | "condition" | "failed";
+
+const selectionModeOptions: Array<{ value: SelectionMode; label: string }> = [
+  { value: "all", label: "Tous les dossiers" },
+  { value: "list", label: "Liste de dossiers" },
+  { value: "pattern", label: "Nom correspondant à un motif" },
+  { value: "condition", label: "Dossiers vérifiant une condition" },
+  { value: "failed", label: "Dossiers en échec lors d'une exécution" },
+];
+
+interface FolderSelectionPanelProps {
+  workspace: CommandeurWorkspaceSummary;
+  lastRunId: string | null;
+  disabled: boolean;
+  onChange: (selection: CommandeurFolderSelection | undefined) => void;
+}
+
+export const FolderSelectionPanel: React.FC<FolderSelectionPanelProps> = ({
+  workspace,
+  lastRunId,
+  disabled,
+  onChange,
+}) => {
+  const [mode, setMode] = useState<SelectionMode>("all");
+  const [folders, setFolders] = useState<string[]>([]);
+  const [namePattern, setNamePattern] = useState("");
+  const [namePatternMode, setNamePatternMode] =
+    useState<CommandeurNamePatternMode>("glob");
+  const [caseInsensitive, setCaseInsensitive] = useState(false);
+  const [condition, setCondition] = useState<CommandeurConditionTest>({
+    selector: "file-search",
+    operator: "exists",
+    pattern: "",
+    scope: "current-folder",
+    negate: false,
+  });
+  const [runId, setRunId] = useState(lastRunId ?? "");
+  const [preview, setPreview] = useState<string[] | null>(null);
+  const [previewError, setPreviewError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (lastRunId) setRunId(lastRunId);
+  }, [lastRunId]);
+
+  const selection: CommandeurFolderSelection | undefined =
+    mode === "list"
+      ? { folders }
+      : mode === "pattern"
+      ? { namePattern, namePatternMode, caseInsensitive }
+      : mode === "condition"
+      ? { condition }
+      : mode === "failed"
+      ? { failedInRun: runId.trim() }
+      : undefined;
+  const selectionKey = JSON.stringify(selection ?? null);
+
+  useEffect(() => {
+    onChange(selection);
+    setPreview(null);
+    setPreviewError(null);
+    // La clé sérialisée suffit à détecter un changement de sélection.
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [selectionKey]);
+
+  const handlePreview = async () => {
+    if (!selection) return;
+    try {
+      setPreviewError(null);
+      setPreview(
+        await previewCommandeurFolderSelection(workspace.workspaceId, selection)
+      );
+    } catch (err) {
+      setPreview(null);
+      setPreviewError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const toggleFolder = (folder: string, checked: boolean) => {
+    setFolders((current) =>
+      checked
+        ? workspace.subFolders.filter(
+            (candidate) => candidate === folder || current.includes(candidate)
+          )
+        : current.filter((candidate) => candidate !== folder)
+    );
+  };
+
+  return (
+    <section style={{ display: "flex", flexDirection: "column", gap: ".6rem" }}>
+      <h4 style={{ margin: 0 }}>Dossiers à traiter</h4>
+      <select
+        value={mode}
+        disabled={disabled}
+        onChange={(event) => setMode(event.target.value as SelectionMode)}
+      >
+        {selectionModeOptions.map((option) => (
+          <option key={option.value} value={option.value}>
+            {option.label}
+          </option>
+        ))}
+      </select>
+
+      {mode === "list" && (
+        <div style={commandeurStyles.list}>
+          {workspace.subFolders.map((folder) => (
+            <label key={folder} style={commandeurStyles.listItem}>
+              <input
+                type="checkbox"
+                disabled={disabled}
+                checked={folders.includes(folder)}
+                onChange={(event) => toggleFolder(folder, event.target.checked)}
+              />{" "}
+              {folder}
+            </label>
+          ))}
+        </div>
+      )}
+
+      {mode === "pattern" && (
+        <div style={{ display: "flex", gap: ".5rem" }}>
+          <select
+            value={namePatternMode}
+            disabled={disabled}
+            onChange={(event) =>
+              setNamePatternMode(
+                event.target.value as CommandeurNamePatternMode
+              )
+            }
+          >
+            <option value="glob">Glob</option>
+            <option value="regex">Regex</option>
+          </select>
+          <input
+            style={{ flex: 1 }}
+            value={namePattern}
+            disabled={disabled}
+            onChange={(event) => setNamePattern(event.target.value)}
+            placeholder={
+              namePatternMode === "glob" ? "Ex. *_TP1; !Test*" : "Ex. ^(A|B)"
+            }
+          />
+          <label>
+            <input
+              type="checkbox"
+              checked={caseInsensitive}
+              disabled={disabled}
+              onChange={(event) => setCaseInsensitive(event.target.checked)}
+            />{" "}
+            Ignorer la casse
+          </label>
+        </div>
+      )}
+
+      {mode === "condition" && (
+        <ConditionTestEditor test={condition} onChange={setCondition} />
+      )}
+
+      {mode === "failed" && (
+        <input
+          value={runId}
+          disabled={disabled}
+          onChange={(event) => setRunId(event.target.value)}
+          placeholder="Identifiant de l'exécution"
+        />
+      )}
+
+      {selection && (
+        <div style={commandeurStyles.badgeRow}>
+          <button
+            className="btn"
+            type="button"
+            disabled={disabled}
+            onClick={() => void handlePreview()}
+          >
+            Prévisualiser la sélection
+          </button>
+          {preview && (
+            <span style={commandeurStyles.badge("neutral")}>
+              {preview.length}/{workspace.subFolders.length} dossier(s)
+              {preview.length > 0 ? ` : ${preview.join(", ")}` : ""}
+            </span>
+          )}
+          {previewError && (
+            <span style={commandeurStyles.badge("error")}>{previewError}</span>
+          )}
+        </div>
+      )}
+    </section>
+  );
+};
+
+export default FolderSelectionPanel;
@@ -0,0 +1,233 @@
+import React, { useCallback, useEffect, useState } from "react";
+import { commandeurStyles } from "./Commandeur.styles";
+import type {
+  CommandeurFolderRunStatus,
+  CommandeurRunHistoryEntry,
+} from "../../types";
+import {
+  deleteCommandeurRun,
+  listCommandeurRuns,
+  openCommandeurRun,
+  openCommandeurRunReport,
+} from "../../services/commandeur/api";
+
+const statusLabels: Record<CommandeurFolderRunStatus, string> = {
+  succeeded: "Réussie",
+  failed: "En échec",
+  interrupted: "Interrompue",
+  skipped: "Non traitée",
+};
+
+function statusTone(status: CommandeurFolderRunStatus) {
+  switch (status) {
+    case "succeeded":
+      return "success" as const;
+    case "failed":
+      return "error" as const;
+    default:
+      return "warning" as const;
+  }
+}
+
+interface RunHistoryPanelProps {
+  sourcePath: string | null;
+  refreshKey: string | null;
+  disabled: boolean;
+  onShow: (runId: string) => Promise<void>;
+  onContinue: (runId: string) => Promise<void>;
+}
+
+export const RunHistoryPanel: React.FC<RunHistoryPanelProps> = ({
+  sourcePath,
+  refreshKey,
+  disabled,
+  onShow,
+  onContinue,
+}) => {
+  const [runs, setRuns] = useState<CommandeurRunHistoryEntry[]>([]);
+  const [query, setQuery] = useState("");
+  const [status, setStatus] = useState<CommandeurFolderRunStatus | "">("");
+  const [workspaceOnly, setWorkspaceOnly] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      setError(null);
+      setRuns(
+        await listCommandeurRuns({
+          query: query || undefined,
+          status: status || undefined,
+          sourcePath: workspaceOnly && sourcePath ? sourcePath : undefined,
+        })
+      );
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  }, [query, status, workspaceOnly, sourcePath]);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh, refreshKey]);
+
+  const handleDelete = async (entry: CommandeurRunHistoryEntry) => {
+    const confirmed = window.confirm(
+      `Supprimer l'exécution du ${new Date(
+        entry.startedAt
+      ).toLocaleString()} et ses fichiers ?`
+    );
+    if (!confirmed) return;
+    try {
+      await deleteCommandeurRun(entry.runId);
+      await refresh();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const runAction = async (action: () => Promise<void>) => {
+    try {
+      setError(null);
+      await action();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  return (
+    <details>
+      <summary>Historique des exécutions</summary>
+      <div
+        style={{
+          display: "flex",
+          flexDirection: "column",
+          gap: ".6rem",
+          marginTop: ".6rem",
+        }}
+      >
+        <div style={{ display: "flex", gap: ".5rem", flexWrap: "wrap" }}>
+          <input
+            style={{ flex: 1 }}
+            value={query}
+            onChange={(event) => setQuery(event.target.value)}
+            placeholder="Workflow, chemin ou identifiant"
+          />
+          <select
+            value={status}
+            onChange={(event) =>
+              setStatus(event.target.value as CommandeurFolderRunStatus | "")
+            }
+          >
+            <option value="">Tous les statuts</option>
+            <option value="succeeded">Réussies</option>
+            <option value="failed">En échec</option>
+            <option value="interrupted">Interrompues</option>
+          </select>
+          {sourcePath && (
+            <label>
+              <input
+                type="checkbox"
+                checked={workspaceOnly}
+                onChange={(event) => setWorkspaceOnly(event.target.checked)}
+              />{" "}
+              Ce workspace uniquement
+            </label>
+          )}
+        </div>
+
+        {error && <span style={commandeurStyles.badge("error")}>{error}</span>}
+
+        {runs.length === 0 ? (
+          <div style={commandeurStyles.emptyState}>
+            Aucune exécution enregistrée.
+          </div>
+        ) : (
+          <div style={commandeurStyles.list}>
+            {runs.map((entry) => (
+              <div key={entry.runId} style={commandeurStyles.listItem}>
+                <div style={commandeurStyles.badgeRow}>
+                  <strong>{new Date(entry.startedAt).toLocaleString()}</strong>
+                  <span>
+                    {entry.workflowName}
+                    {entry.workflowVersion
+                      ? ` (v${entry.workflowVersion})`
+                      : ""}
+                  </span>
+                  <span
+                    style={commandeurStyles.badge(statusTone(entry.status))}
+                  >
+                    {statusLabels[entry.status]}
+                  </span>
+                  <span style={commandeurStyles.badge("neutral")}>
+                    {entry.operationsRun} opération(s) ·{" "}
+                    {entry.folderCount - entry.failedFolderCount}/
+                    {entry.folderCount} dossier(s) réussi(s)
+                  </span>
+                  {entry.resumedFrom && (
+                    <span style={commandeurStyles.badge("neutral")}>
+                      Reprise de {entry.resumedFrom}
+                    </span>
+                  )}
+                </div>
+                <div style={{ fontSize: ".85rem", opacity: 0.8 }}>
+                  {entry.sourcePath}
+                </div>
+                <div style={commandeurStyles.badgeRow}>
+                  <button
+                    className="btn"
+                    type="button"
+                    disabled={disabled}
+                    onClick={() => void runAction(() => onShow(entry.runId))}
+                  >
+                    Afficher
+                  </button>
+                  <button
+                    className="btn"
+                    type="button"
+                    onClick={() =>
+                      void runAction(() => openCommandeurRun(entry.runId))
+                    }
+                  >
+                    Ouvrir le dossier
+                  </button>
+                  {entry.runLogPath && (
+                    <button
+                      className="btn"
+                      type="button"
+                      onClick={() =>
+                        void runAction(async () => {
+                          await openCommandeurRunReport(entry.runId);
+                        })
+                      }
+                    >
+                      Rapport HTML
+                    </button>
+                  )}
+                  {entry.status !== "succeeded" && (
+                    <button
+                      className="btn"
+                      type="button"
+                      disabled={disabled}
+                      onClick={() => void onContinue(entry.runId)}
+                    >
+                      Reprendre
+                    </button>
+                  )}
+                  <button
+                    className="btn"
+                    type="button"
+                    disabled={disabled}
+                    onClick={() => void handleDelete(entry)}
+                  >
+                    Supprimer
+                  </button>
+                </div>
+              </div>
+            ))}
+          </div>
+        )}
+      </div>
+    </details>
+  );
+};
+
+export default RunHistoryPanel;
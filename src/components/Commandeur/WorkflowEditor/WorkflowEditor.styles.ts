@@ -146,6 +146,13 @@ export const workflowEditorStyles = {
     flexDirection: "column" as const,
     gap: ".5rem",
   },
+  conditionGroup: {
+    display: "flex",
+    flexDirection: "column" as const,
+    gap: ".5rem",
+    borderLeft: "3px solid #e5e7eb",
+    paddingLeft: ".75rem",
+  },
   formGrid: {
     display: "grid",
     gridTemplateColumns: "repeat(auto-fit, minmax(220px, 1fr))",
@@ -1,18 +1,21 @@
 import React, { useEffect, useMemo, useState } from "react";
 import type {
   CommandeurConditionOperator,
+  CommandeurConditionQuantifier,
+  CommandeurConditionRunner,
   CommandeurConditionScope,
   CommandeurConditionSelector,
   CommandeurConditionTest,
   CommandeurOperation,
   CommandeurOperationKind,
+  CommandeurOutputCapture,
+  CommandeurTargetMatching,
   CommandeurWorkflow,
 } from "../../../types";
-import type { CommandeurConditionalOperation } from "../../../types/Commandeur";
 import { workflowEditorStyles } from "./WorkflowEditor.styles";
 import { listAvailableShells } from "../../../services/commandeur/api";
 
-type OperationBranchKey = "then" | "else";
+type OperationBranchKey = "then" | "else" | "operations";
 type OperationPath = Array<number | OperationBranchKey>;
 
 const operationKindLabels: Record<CommandeurOperationKind, string> = {
@@ -26,6 +29,7 @@ const operationKindLabels: Record<CommandeurOperationKind, string> = {
   mkdir: "Créer un dossier",
   python: "Script Python",
   if: "Condition",
+  "for-each": "Boucle",
 };
 
 const operationKindOptions = Object.entries(operationKindLabels).map(
@@ -35,6 +39,8 @@ const operationKindOptions = Object.entries(operationKindLabels).map(
 type ConditionSelector = CommandeurConditionSelector;
 type ConditionOperator = CommandeurConditionOperator;
 type ConditionScope = CommandeurConditionScope;
+type ConditionQuantifier = CommandeurConditionQuantifier;
+type ConditionRunner = CommandeurConditionRunner;
 
 const defaultConditionSelector: ConditionSelector = "file-search";
 const defaultConditionScope: ConditionScope = "current-folder";
@@ -46,8 +52,38 @@ const conditionSelectorOptions: ReadonlyArray<{
   { value: "current-folder-name", label: "Nom du dossier courant" },
   { value: "file-search", label: "Recherche de fichier" },
   { value: "file-count", label: "Nombre de fichiers" },
+  { value: "captured-value", label: "Valeur capturée" },
+  { value: "file-content", label: "Contenu de fichier" },
+  { value: "total-size", label: "Taille totale" },
+  { value: "newest-modified", label: "Modification la plus récente" },
+  { value: "oldest-modified", label: "Modification la plus ancienne" },
+  { value: "line-count", label: "Nombre de lignes" },
+  { value: "command-exit-code", label: "Résultat d'une commande" },
 ];
 
+const metricConditionSelectors: ConditionSelector[] = [
+  "total-size",
+  "newest-modified",
+  "oldest-modified",
+  "line-count",
+];
+
+const metricValueHints: Partial<Record<ConditionSelector, string>> = {
+  "total-size": "Ex. 10MB, 512 Ko, 0",
+  "newest-modified": "Ex. 2025-03-01T23:59 ou {{echeance}}",
+  "oldest-modified": "Ex. 2025-03-01T23:59 ou {{echeance}}",
+  "line-count": "Ex. 500 lignes",
+};
+
+function isFileCondition(selector: ConditionSelector): boolean {
+  return (
+    selector === "file-search" ||
+    selector === "file-count" ||
+    selector === "file-content" ||
+    metricConditionSelectors.includes(selector)
+  );
+}
+
 const conditionOperatorOptions: Record<
   ConditionSelector,
   ReadonlyArray<{ value: ConditionOperator; label: string }>
@@ -66,6 +102,49 @@ const conditionOperatorOptions: Record<
     { value: "greater-than", label: "Supérieure à" },
     { value: "less-than", label: "Inférieure à" },
   ],
+  "captured-value": [
+    { value: "equals", label: "Égale à" },
+    { value: "contains", label: "Contient" },
+    { value: "not-contains", label: "Ne contient pas" },
+    { value: "regex", label: "Correspond à la regex" },
+    { value: "greater-than", label: "Supérieure à (numérique)" },
+    { value: "less-than", label: "Inférieure à (numérique)" },
+    { value: "exists", label: "A été capturée" },
+    { value: "not-exists", label: "N'a pas été capturée" },
+  ],
+  "file-content": [
+    { value: "contains", label: "Contient" },
+    { value: "not-contains", label: "Ne contient pas" },
+    { value: "regex", label: "Correspond à la regex" },
+  ],
+  "total-size": [
+    { value: "greater-than", label: "Supérieure à" },
+    { value: "less-than", label: "Inférieure à" },
+    { value: "equals", label: "Égale à" },
+  ],
+  "newest-modified": [
+    { value: "greater-than", label: "Après" },
+    { value: "less-than", label: "Avant" },
+    { value: "equals", label: "À la date" },
+  ],
+  "oldest-modified": [
+    { value: "greater-than", label: "Après" },
+    { value: "less-than", label: "Avant" },
+    { value: "equals", label: "À la date" },
+  ],
+  "line-count": [
+    { value: "greater-than", label: "Supérieur à" },
+    { value: "less-than", label: "Inférieur à" },
+    { value: "equals", label: "Égal à" },
+  ],
+  "command-exit-code": [
+    { value: "equals", label: "Code de sortie égal à" },
+    { value: "greater-than", label: "Code de sortie supérieur à" },
+    { value: "less-than", label: "Code de sortie inférieur à" },
+    { value: "contains", label: "Sortie standard contient" },
+    { value: "not-contains", label: "Sortie standard ne contient pas" },
+    { value: "regex", label: "Sortie standard correspond à la regex" },
+  ],
 };
 
 const conditionScopeOptions: ReadonlyArray<{
@@ -76,9 +155,62 @@ const conditionScopeOptions: ReadonlyArray<{
   { value: "recursive", label: "Inclure les sous-dossiers" },
 ];
 
+const conditionQuantifierOptions: Array<{
+  value: ConditionQuantifier;
+  label: string;
+}> = [
+  { value: "any", label: "Au moins un fichier" },
+  { value: "all", label: "Tous les fichiers" },
+];
+
+type ConditionMode = "test" | "all" | "any" | "not";
+
+const conditionModeOptions: Array<{ value: ConditionMode; label: string }> = [
+  { value: "test", label: "Condition simple" },
+  { value: "all", label: "Toutes les conditions (ET)" },
+  { value: "any", label: "Au moins une condition (OU)" },
+  { value: "not", label: "Négation (NON)" },
+];
+
+function conditionMode(test: CommandeurConditionTest): ConditionMode {
+  if (test.all) return "all";
+  if (test.any) return "any";
+  if (test.not) return "not";
+  return "test";
+}
+
+function createDefaultConditionTest(): CommandeurConditionTest {
+  return {
+    selector: defaultConditionSelector,
+    operator: "exists",
+    pattern: "",
+    scope: defaultConditionScope,
+    negate: false,
+  };
+}
+
 function normalizeConditionTest(
   test: CommandeurConditionTest
 ): CommandeurConditionTest {
+  if (test.all) {
+    return {
+      all: test.all.map(normalizeConditionTest),
+      negate: test.negate ?? false,
+    };
+  }
+  if (test.any) {
+    return {
+      any: test.any.map(normalizeConditionTest),
+      negate: test.negate ?? false,
+    };
+  }
+  if (test.not) {
+    return {
+      not: normalizeConditionTest(test.not),
+      negate: test.negate ?? false,
+    };
+  }
+
   const selector: ConditionSelector = test.selector ?? defaultConditionSelector;
   const operatorChoices = conditionOperatorOptions[selector];
   const operatorValues = operatorChoices.map((option) => option.value);
@@ -95,7 +227,7 @@ function normalizeConditionTest(
     negate,
   };
 
-  if (selector === "file-search" || selector === "file-count") {
+  if (isFileCondition(selector)) {
     const scope = test.scope ?? defaultConditionScope;
     normalized.scope = scope;
     const legacyExists = test.exists;
@@ -107,35 +239,81 @@ function normalizeConditionTest(
     if (legacyExists && legacyExists.trim().length > 0 && !test.pattern) {
       normalized.exists = legacyExists.trim();
     }
+    if (test.ignoreCase) {
+      normalized.ignoreCase = true;
+    }
   } else if (test.exists && test.exists.trim().length > 0) {
     normalized.exists = test.exists;
   }
 
   if (selector === "current-folder-name") {
     normalized.value = test.value ?? "";
-  } else if (selector === "file-count") {
+  } else if (
+    selector === "file-count" ||
+    selector === "captured-value" ||
+    selector === "file-content" ||
+    selector === "command-exit-code" ||
+    metricConditionSelectors.includes(selector)
+  ) {
     normalized.value = test.value ?? "";
   } else if (typeof test.value === "string" && test.value.trim().length > 0) {
     normalized.value = test.value;
   }
 
-  if (selector !== "file-search" && selector !== "file-count") {
+  if (!isFileCondition(selector)) {
     delete normalized.scope;
     delete normalized.pattern;
     delete normalized.exists;
   }
 
+  if (selector === "captured-value") {
+    normalized.variable = test.variable ?? "";
+  }
+
+  if (selector === "file-content") {
+    normalized.quantifier = test.quantifier ?? "any";
+  }
+
+  if (selector === "command-exit-code") {
+    normalized.command = test.command ?? "";
+    normalized.runner = test.runner ?? "command";
+    if (normalized.runner === "command") {
+      normalized.shell = test.shell ?? "default";
+    }
+    if (test.timeoutSeconds !== undefined) {
+      normalized.timeoutSeconds = test.timeoutSeconds;
+    }
+  }
+
+  if (
+    (selector === "file-content" ||
+      selector === "captured-value" ||
+      selector === "command-exit-code") &&
+    test.flags
+  ) {
+    normalized.flags = test.flags;
+  }
+
   if (selector === "file-search" && normalized.pattern === undefined) {
     normalized.pattern = "";
   }
 
-  if (selector === "file-count" && normalized.pattern === undefined) {
+  if (
+    (selector === "file-count" ||
+      selector === "file-content" ||
+      metricConditionSelectors.includes(selector)) &&
+    normalized.pattern === undefined
+  ) {
     normalized.pattern = "";
   }
 
   if (
     selector !== "current-folder-name" &&
     selector !== "file-count" &&
+    selector !== "captured-value" &&
+    selector !== "file-content" &&
+    selector !== "command-exit-code" &&
+    !metricConditionSelectors.includes(selector) &&
     normalized.value !== undefined &&
     normalized.value?.trim().length === 0
   ) {
@@ -179,6 +357,9 @@ function cloneOperationWithNewIds(
         return childClone;
       });
     }
+    if (op.kind === "for-each") {
+      op.operations.forEach(assignIds);
+    }
   };
   assignIds(clone);
   return clone;
@@ -278,21 +459,52 @@ function createOperation(kind: CommandeurOperationKind): CommandeurOperation {
       return {
         ...base,
         kind,
-        test: {
-          selector: defaultConditionSelector,
-          operator: "exists",
-          pattern: "",
-          scope: defaultConditionScope,
-          negate: false,
-        },
+        test: createDefaultConditionTest(),
         then: [],
         else: [],
       };
+    case "for-each":
+      return {
+        ...base,
+        kind,
+        pattern: "",
+        items: undefined,
+        operations: [],
+      };
     default:
       return base;
   }
 }
 
+function getBranchOperations(
+  operation: CommandeurOperation,
+  branch: OperationBranchKey
+): CommandeurOperation[] | null {
+  if (operation.kind === "if" && branch !== "operations") {
+    return branch === "then" ? operation.then : operation.else ?? [];
+  }
+  if (operation.kind === "for-each" && branch === "operations") {
+    return operation.operations;
+  }
+  return null;
+}
+
+function withBranchOperations(
+  operation: CommandeurOperation,
+  branch: OperationBranchKey,
+  operations: CommandeurOperation[]
+): CommandeurOperation {
+  if (operation.kind === "if") {
+    return branch === "then"
+      ? { ...operation, then: operations }
+      : { ...operation, else: operations };
+  }
+  if (operation.kind === "for-each") {
+    return { ...operation, operations };
+  }
+  return operation;
+}
+
 function getOperationAtPath(
   operations: CommandeurOperation[],
   path: OperationPath | null
@@ -306,8 +518,11 @@ function getOperationAtPath(
       currentOp = currentOps[segment];
       if (!currentOp) return null;
     } else {
-      if (!currentOp || currentOp.kind !== "if") return null;
-      currentOps = segment === "then" ? currentOp.then : currentOp.else ?? [];
+      const branchOps = currentOp
+        ? getBranchOperations(currentOp, segment)
+        : null;
+      if (!branchOps) return null;
+      currentOps = branchOps;
       currentOp = null;
     }
   }
@@ -334,17 +549,13 @@ function replaceOperationAtPath(
     return next;
   }
   const [branch, ...branchRest] = rest;
-  if (branch !== "then" && branch !== "else") return operations;
-  if (target.kind !== "if") return operations;
-  const branchOps = branch === "then" ? target.then : target.else ?? [];
+  if (typeof branch === "number") return operations;
+  const branchOps = getBranchOperations(target, branch);
+  if (!branchOps) return operations;
   const updatedBranch = replaceOperationAtPath(branchOps, branchRest, updater);
   if (updatedBranch === branchOps) return operations;
   const next = [...operations];
-  const updatedTarget: CommandeurConditionalOperation =
-    branch === "then"
-      ? { ...target, then: updatedBranch }
-      : { ...target, else: updatedBranch };
-  next[head] = updatedTarget;
+  next[head] = withBranchOperations(target, branch, updatedBranch);
   return next;
 }
 
@@ -363,18 +574,14 @@ function removeOperationAtPath(
     return next;
   }
   const [branch, ...branchRest] = rest;
-  if (branch !== "then" && branch !== "else") return operations;
+  if (typeof branch === "number") return operations;
   const target = operations[head];
-  if (!target || target.kind !== "if") return operations;
-  const branchOps = branch === "then" ? target.then : target.else ?? [];
+  const branchOps = target ? getBranchOperations(target, branch) : null;
+  if (!target || !branchOps) return operations;
   const updatedBranch = removeOperationAtPath(branchOps, branchRest);
   if (updatedBranch === branchOps) return operations;
   const next = [...operations];
-  const updatedTarget: CommandeurConditionalOperation =
-    branch === "then"
-      ? { ...target, then: updatedBranch }
-      : { ...target, else: updatedBranch };
-  next[head] = updatedTarget;
+  next[head] = withBranchOperations(target, branch, updatedBranch);
   return next;
 }
 
@@ -403,9 +610,9 @@ function insertOperationAt(
     return next;
   }
   const [branch, ...branchRest] = rest;
-  if (branch !== "then" && branch !== "else") return operations;
-  if (target.kind !== "if") return operations;
-  const branchOps = branch === "then" ? target.then : target.else ?? [];
+  if (typeof branch === "number") return operations;
+  const branchOps = getBranchOperations(target, branch);
+  if (!branchOps) return operations;
   const updatedBranch = insertOperationAt(
     branchOps,
     branchRest,
@@ -414,11 +621,7 @@ function insertOperationAt(
   );
   if (updatedBranch === branchOps) return operations;
   const next = [...operations];
-  const updatedTarget: CommandeurConditionalOperation =
-    branch === "then"
-      ? { ...target, then: updatedBranch }
-      : { ...target, else: updatedBranch };
-  next[head] = updatedTarget;
+  next[head] = withBranchOperations(target, branch, updatedBranch);
   return next;
 }
 
@@ -441,18 +644,14 @@ function moveOperation(
     return next;
   }
   const [branch, ...branchRest] = rest;
-  if (branch !== "then" && branch !== "else") return operations;
+  if (typeof branch === "number") return operations;
   const target = operations[head];
-  if (!target || target.kind !== "if") return operations;
-  const branchOps = branch === "then" ? target.then : target.else ?? [];
+  const branchOps = target ? getBranchOperations(target, branch) : null;
+  if (!target || !branchOps) return operations;
   const updatedBranch = moveOperation(branchOps, branchRest, direction);
   if (updatedBranch === branchOps) return operations;
   const next = [...operations];
-  const updatedTarget: CommandeurConditionalOperation =
-    branch === "then"
-      ? { ...target, then: updatedBranch }
-      : { ...target, else: updatedBranch };
-  next[head] = updatedTarget;
+  next[head] = withBranchOperations(target, branch, updatedBranch);
   return next;
 }
 
@@ -578,21 +777,18 @@ export const WorkflowEditor: React.FC<WorkflowEditorProps> = ({
   ) => {
     const child = createOperation(kind);
     const parentOperation = getOperationAtPath(workflow.operations, path);
-    if (!parentOperation || parentOperation.kind !== "if") return;
-    const branchOpsBefore =
-      branch === "then" ? parentOperation.then : parentOperation.else ?? [];
+    const branchOpsBefore = parentOperation
+      ? getBranchOperations(parentOperation, branch)
+      : null;
+    if (!branchOpsBefore) return;
     const newIndex = branchOpsBefore.length;
     const updated = replaceOperationAtPath(
       workflow.operations,
       path,
       (operation) => {
-        if (operation.kind !== "if") return operation;
-        const branchOps =
-          branch === "then" ? operation.then : operation.else ?? [];
-        const nextBranch = [...branchOps, child];
-        return branch === "then"
-          ? { ...operation, then: nextBranch }
-          : { ...operation, else: nextBranch };
+        const branchOps = getBranchOperations(operation, branch);
+        if (!branchOps) return operation;
+        return withBranchOperations(operation, branch, [...branchOps, child]);
       }
     );
     updateWorkflowOperations(updated);
@@ -718,6 +914,33 @@ export const WorkflowEditor: React.FC<WorkflowEditorProps> = ({
                   })}
                 </div>
               )}
+
+              {operation.kind === "for-each" && (
+                <div style={workflowEditorStyles.listSecondary}>
+                  <div
+                    style={{
+                      display: "flex",
+                      flexDirection: "column",
+                      gap: ".4rem",
+                    }}
+                  >
+                    <div style={{ fontWeight: 600, fontSize: ".8rem" }}>
+                      Pour chaque élément
+                    </div>
+                    {renderOperationList(
+                      operation.operations,
+                      [...path, "operations"],
+                      depth + 1
+                    )}
+                    <AddOperationControl
+                      label="Ajouter dans la boucle"
+                      onAdd={(kind) =>
+                        handleAddChild(path, "operations", kind)
+                      }
+                    />
+                  </div>
+                </div>
+              )}
             </div>
           );
         })}
@@ -756,6 +979,75 @@ export const WorkflowEditor: React.FC<WorkflowEditorProps> = ({
             placeholder="1.0"
           />
         </div>
+        <div style={workflowEditorStyles.metaField}>
+          <label style={workflowEditorStyles.label} htmlFor="workflow-workers">
+            Dossiers en parallèle
+          </label>
+          <input
+            id="workflow-workers"
+            type="number"
+            min={1}
+            style={workflowEditorStyles.metaInput}
+            value={workflow.maxWorkers ?? ""}
+            onChange={(event) => {
+              const value = Number.parseInt(event.target.value, 10);
+              onWorkflowChange({
+                ...workflow,
+                maxWorkers: Number.isNaN(value) || value < 1 ? undefined : value,
+              });
+            }}
+            placeholder="1"
+          />
+        </div>
+        <div style={workflowEditorStyles.metaField}>
+          <label style={workflowEditorStyles.label} htmlFor="workflow-timeout">
+            Délai par défaut (s)
+          </label>
+          <input
+            id="workflow-timeout"
+            type="number"
+            min={1}
+            style={workflowEditorStyles.metaInput}
+            value={workflow.defaultTimeoutSeconds ?? ""}
+            onChange={(event) =>
+              onWorkflowChange({
+                ...workflow,
+                defaultTimeoutSeconds: parseTimeoutSeconds(event.target.value),
+              })
+            }
+            placeholder="Aucun"
+          />
+        </div>
+        <div style={workflowEditorStyles.metaField}>
+          <label style={workflowEditorStyles.label} htmlFor="workflow-variables">
+            Variables (nom=valeur, une par ligne)
+          </label>
+          <textarea
+            id="workflow-variables"
+            style={workflowEditorStyles.textarea}
+            value={Object.entries(workflow.variables ?? {})
+              .map(([name, value]) => `${name}=${value}`)
+              .join("\n")}
+            onChange={(event) => {
+              const variables: Record<string, string> = {};
+              for (const line of event.target.value.split(/\r?\n/)) {
+                const [name, ...valueParts] = line.split("=");
+                if (!name || !name.trim()) continue;
+                variables[name.trim()] = valueParts.join("=");
+              }
+              onWorkflowChange({
+                ...workflow,
+                variables: Object.keys(variables).length ? variables : undefined,
+              });
+            }}
+            placeholder="cours=INF1010"
+          />
+          <div style={workflowEditorStyles.helperText}>
+            Utilisables via {"{{nom}}"}, ainsi que {"{{folder}}"}, {"{{index}}"},{" "}
+            {"{{workspaceRoot}}"}, {"{{date}}"} et {"{{runId}}"}. Écrivez{" "}
+            {"{{{{"} pour garder {"{{"} tel quel.
+          </div>
+        </div>
       </div>
 
       <div style={workflowEditorStyles.editorBody}>
@@ -891,6 +1183,13 @@ const OperationForm: React.FC<OperationFormProps> = ({
         availableShells={availableShells}
       />
 
+      {patternOperationKinds.includes(operation.kind) && (
+        <MatchingFields
+          matching={operation.matching}
+          onChange={(matching) => updateField("matching", matching)}
+        />
+      )}
+
       <div style={workflowEditorStyles.field}>
         <label style={workflowEditorStyles.label}>Commentaire</label>
         <textarea
@@ -1123,6 +1422,25 @@ const SpecificFields: React.FC<SpecificFieldsProps> = ({
             }
             multiline
           />
+          <Field
+            label="Délai maximal en secondes (optionnel)"
+            value={operation.timeoutSeconds?.toString() ?? ""}
+            onChange={(value) =>
+              onUpdate((current) =>
+                current.kind === "exec"
+                  ? { ...current, timeoutSeconds: parseTimeoutSeconds(value) }
+                  : current
+              )
+            }
+          />
+          <CaptureFields
+            capture={operation.capture}
+            onChange={(capture) =>
+              onUpdate((current) =>
+                current.kind === "exec" ? { ...current, capture } : current
+              )
+            }
+          />
         </div>
       );
     case "replace-in-file":
@@ -1413,98 +1731,28 @@ const SpecificFields: React.FC<SpecificFieldsProps> = ({
               )
             }
           />
+          <Field
+            label="Délai maximal en secondes (optionnel)"
+            value={operation.timeoutSeconds?.toString() ?? ""}
+            onChange={(value) =>
+              onUpdate((current) =>
+                current.kind === "python"
+                  ? { ...current, timeoutSeconds: parseTimeoutSeconds(value) }
+                  : current
+              )
+            }
+          />
+          <CaptureFields
+            capture={operation.capture}
+            onChange={(capture) =>
+              onUpdate((current) =>
+                current.kind === "python" ? { ...current, capture } : current
+              )
+            }
+          />
         </div>
       );
-    case "if": {
-      const normalizedTest = normalizeConditionTest(operation.test);
-      const selector = normalizedTest.selector ?? defaultConditionSelector;
-      const operatorChoices = conditionOperatorOptions[selector];
-      const operator =
-        normalizedTest.operator &&
-        operatorChoices.some(
-          (choice) => choice.value === normalizedTest.operator
-        )
-          ? normalizedTest.operator
-          : operatorChoices[0].value;
-      const scope = normalizedTest.scope ?? defaultConditionScope;
-      const pattern = normalizedTest.pattern ?? "";
-      const value = normalizedTest.value ?? "";
-
-      const handleSelectorChange = (nextSelector: ConditionSelector) => {
-        onUpdate((current) => {
-          if (current.kind !== "if") return current;
-          const normalized = normalizeConditionTest({
-            ...current.test,
-            selector: nextSelector,
-            operator: conditionOperatorOptions[nextSelector][0].value,
-            scope:
-              nextSelector === "file-search" || nextSelector === "file-count"
-                ? defaultConditionScope
-                : undefined,
-            pattern:
-              nextSelector === "file-search" || nextSelector === "file-count"
-                ? ""
-                : undefined,
-            value:
-              nextSelector === "file-count"
-                ? "1"
-                : nextSelector === "current-folder-name"
-                ? ""
-                : undefined,
-          });
-          return { ...current, test: normalized };
-        });
-      };
-
-      const handleOperatorChange = (nextOperator: ConditionOperator) => {
-        onUpdate((current) => {
-          if (current.kind !== "if") return current;
-          const normalized = normalizeConditionTest({
-            ...current.test,
-            operator: nextOperator,
-          });
-          return { ...current, test: normalized };
-        });
-      };
-
-      const handleScopeChange = (nextScope: ConditionScope) => {
-        onUpdate((current) => {
-          if (current.kind !== "if") return current;
-          const normalized = normalizeConditionTest({
-            ...current.test,
-            scope: nextScope,
-          });
-          return { ...current, test: normalized };
-        });
-      };
-
-      const handlePatternChange = (nextPattern: string) => {
-        onUpdate((current) => {
-          if (current.kind !== "if") return current;
-          const normalized = normalizeConditionTest({
-            ...current.test,
-            pattern: nextPattern,
-          });
-          return { ...current, test: normalized };
-        });
-      };
-
-      const handleValueChange = (nextValue: string) => {
-        onUpdate((current) => {
-          if (current.kind !== "if") return current;
-          const normalized = normalizeConditionTest({
-            ...current.test,
-            value: nextValue,
-          });
-          return { ...current, test: normalized };
-        });
-      };
-
-      const handleCountValueChange = (rawValue: string) => {
-        const sanitized = rawValue.replace(/[^0-9]/g, "");
-        handleValueChange(sanitized);
-      };
-
+    case "if":
       return (
         <div
           style={{
@@ -1513,33 +1761,446 @@ const SpecificFields: React.FC<SpecificFieldsProps> = ({
             gap: ".75rem",
           }}
         >
+          <ConditionTestEditor
+            test={operation.test}
+            onChange={(test) =>
+              onUpdate((current) =>
+                current.kind === "if" ? { ...current, test } : current
+              )
+            }
+          />
+          <div style={workflowEditorStyles.helperText}>
+            Les sous-opérations sont gérées dans le panneau de gauche.
+          </div>
+        </div>
+      );
+    case "for-each":
+      return (
+        <div style={workflowEditorStyles.formGrid}>
+          <Field
+            label="Motif de fichiers (optionnel)"
+            value={operation.pattern ?? ""}
+            onChange={(value) =>
+              onUpdate((current) =>
+                current.kind === "for-each"
+                  ? { ...current, pattern: value || undefined }
+                  : current
+              )
+            }
+          />
+          <div style={workflowEditorStyles.field}>
+            <label style={workflowEditorStyles.label}>
+              Valeurs fixes (une par ligne)
+            </label>
+            <textarea
+              style={workflowEditorStyles.textarea}
+              value={(operation.items ?? []).join("\n")}
+              onChange={(event) =>
+                onUpdate((current) =>
+                  current.kind === "for-each"
+                    ? { ...current, items: splitPatternLines(event.target.value) }
+                    : current
+                )
+              }
+              placeholder="Ex. debug"
+            />
+          </div>
+          <div style={workflowEditorStyles.helperText}>
+            Les opérations de la boucle reçoivent {"{{item}}"},{" "}
+            {"{{item.name}}"}, {"{{item.stem}}"} et {"{{item.dir}}"}. Exemple :
+            motif src/**/*.c puis gcc {"{{item}}"} -o {"{{item.dir}}"}/
+            {"{{item.stem}}"}.
+          </div>
+        </div>
+      );
+    default:
+      return null;
+  }
+};
+
+interface ConditionTestEditorProps {
+  test: CommandeurConditionTest;
+  onChange: (test: CommandeurConditionTest) => void;
+}
+
+export const ConditionTestEditor: React.FC<ConditionTestEditorProps> = ({
+  test,
+  onChange,
+}) => {
+  const mode = conditionMode(test);
+  const children =
+    mode === "all" ? test.all ?? [] : mode === "any" ? test.any ?? [] : [];
+
+  const handleModeChange = (nextMode: ConditionMode) => {
+    if (nextMode === mode) return;
+    const operands =
+      mode === "test"
+        ? [test]
+        : mode === "not"
+        ? test.not
+          ? [test.not]
+          : []
+        : children;
+    const first = operands[0] ?? createDefaultConditionTest();
+    const groupOperands = operands.length > 0 ? operands : [first];
+    const next: CommandeurConditionTest =
+      nextMode === "test"
+        ? first
+        : nextMode === "not"
+        ? { not: first, negate: false }
+        : nextMode === "all"
+        ? { all: groupOperands, negate: false }
+        : { any: groupOperands, negate: false };
+    onChange(normalizeConditionTest(next));
+  };
+
+  const updateChildren = (nextChildren: CommandeurConditionTest[]) => {
+    onChange(
+      mode === "all"
+        ? { ...test, all: nextChildren }
+        : { ...test, any: nextChildren }
+    );
+  };
+
+  return (
+    <div
+      style={{
+        display: "flex",
+        flexDirection: "column" as const,
+        gap: ".75rem",
+      }}
+    >
+      <div style={workflowEditorStyles.field}>
+        <label style={workflowEditorStyles.label}>Type de condition</label>
+        <select
+          style={workflowEditorStyles.select}
+          value={mode}
+          onChange={(event) =>
+            handleModeChange(event.target.value as ConditionMode)
+          }
+        >
+          {conditionModeOptions.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+      </div>
+
+      {mode === "test" && (
+        <ConditionLeafEditor test={test} onChange={onChange} />
+      )}
+
+      {mode === "not" && (
+        <div style={workflowEditorStyles.conditionGroup}>
+          <ConditionTestEditor
+            test={test.not ?? createDefaultConditionTest()}
+            onChange={(child) => onChange({ ...test, not: child })}
+          />
+        </div>
+      )}
+
+      {(mode === "all" || mode === "any") && (
+        <>
+          {children.map((child, index) => (
+            <div key={index} style={workflowEditorStyles.conditionGroup}>
+              <ConditionTestEditor
+                test={child}
+                onChange={(nextChild) =>
+                  updateChildren(
+                    children.map((existing, childIndex) =>
+                      childIndex === index ? nextChild : existing
+                    )
+                  )
+                }
+              />
+              <button
+                className="btn"
+                type="button"
+                onClick={() =>
+                  updateChildren(
+                    children.filter((_, childIndex) => childIndex !== index)
+                  )
+                }
+              >
+                Retirer cette condition
+              </button>
+            </div>
+          ))}
+          <button
+            className="btn"
+            type="button"
+            onClick={() =>
+              updateChildren([...children, createDefaultConditionTest()])
+            }
+          >
+            Ajouter une condition
+          </button>
+        </>
+      )}
+    </div>
+  );
+};
+
+const ConditionLeafEditor: React.FC<ConditionTestEditorProps> = ({
+  test,
+  onChange,
+}) => {
+  const normalizedTest = normalizeConditionTest(test);
+  const selector = normalizedTest.selector ?? defaultConditionSelector;
+  const operatorChoices = conditionOperatorOptions[selector];
+  const operator =
+    normalizedTest.operator &&
+    operatorChoices.some(
+      (choice) => choice.value === normalizedTest.operator
+    )
+      ? normalizedTest.operator
+      : operatorChoices[0].value;
+  const scope = normalizedTest.scope ?? defaultConditionScope;
+  const pattern = normalizedTest.pattern ?? "";
+  const value = normalizedTest.value ?? "";
+
+  const handleSelectorChange = (nextSelector: ConditionSelector) => {
+    onChange(
+      normalizeConditionTest({
+        ...test,
+        selector: nextSelector,
+        operator: conditionOperatorOptions[nextSelector][0].value,
+        scope: isFileCondition(nextSelector) ? defaultConditionScope : undefined,
+        pattern: isFileCondition(nextSelector) ? "" : undefined,
+        value:
+          nextSelector === "file-count"
+            ? "1"
+            : nextSelector === "command-exit-code"
+            ? "0"
+            : nextSelector === "current-folder-name"
+            ? ""
+            : undefined,
+      })
+    );
+  };
+
+  const handleOperatorChange = (nextOperator: ConditionOperator) => {
+    onChange(
+      normalizeConditionTest({
+        ...test,
+        operator: nextOperator,
+      })
+    );
+  };
+
+  const handleScopeChange = (nextScope: ConditionScope) => {
+    onChange(
+      normalizeConditionTest({
+        ...test,
+        scope: nextScope,
+      })
+    );
+  };
+
+  const handlePatternChange = (nextPattern: string) => {
+    onChange(
+      normalizeConditionTest({
+        ...test,
+        pattern: nextPattern,
+      })
+    );
+  };
+
+  const handleValueChange = (nextValue: string) => {
+    onChange(
+      normalizeConditionTest({
+        ...test,
+        value: nextValue,
+      })
+    );
+  };
+
+  const handleVariableChange = (nextVariable: string) => {
+    onChange(
+      normalizeConditionTest({
+        ...test,
+        variable: nextVariable,
+      })
+    );
+  };
+
+  const handleQuantifierChange = (nextQuantifier: ConditionQuantifier) => {
+    onChange(
+      normalizeConditionTest({
+        ...test,
+        quantifier: nextQuantifier,
+      })
+    );
+  };
+
+  const handleFlagsChange = (nextFlags: string) => {
+    onChange(
+      normalizeConditionTest({
+        ...test,
+        flags: nextFlags,
+      })
+    );
+  };
+
+  const handleCommandChange = (
+    patch: Pick<
+      CommandeurConditionTest,
+      "command" | "runner" | "shell" | "timeoutSeconds"
+    >
+  ) => {
+    onChange(
+      normalizeConditionTest({
+        ...test,
+        ...patch,
+      })
+    );
+  };
+
+  const handleCountValueChange = (rawValue: string) => {
+    const sanitized = rawValue.replace(/[^0-9]/g, "");
+    handleValueChange(sanitized);
+  };
+
+  return (
+    <div
+      style={{
+        display: "flex",
+        flexDirection: "column" as const,
+        gap: ".75rem",
+      }}
+    >
+      <div style={workflowEditorStyles.formGrid}>
+        <div style={workflowEditorStyles.field}>
+          <label style={workflowEditorStyles.label}>Sélecteur</label>
+          <select
+            style={workflowEditorStyles.select}
+            value={selector}
+            onChange={(event) =>
+              handleSelectorChange(event.target.value as ConditionSelector)
+            }
+          >
+            {conditionSelectorOptions.map((option) => (
+              <option key={option.value} value={option.value}>
+                {option.label}
+              </option>
+            ))}
+          </select>
+        </div>
+        <div style={workflowEditorStyles.field}>
+          <label style={workflowEditorStyles.label}>Opérateur</label>
+          <select
+            style={workflowEditorStyles.select}
+            value={operator}
+            onChange={(event) =>
+              handleOperatorChange(event.target.value as ConditionOperator)
+            }
+          >
+            {operatorChoices.map((option) => (
+              <option key={option.value} value={option.value}>
+                {option.label}
+              </option>
+            ))}
+          </select>
+        </div>
+      </div>
+
+      {selector === "current-folder-name" && (
+        <div style={workflowEditorStyles.field}>
+          <label style={workflowEditorStyles.label}>Valeur attendue</label>
+          <input
+            style={workflowEditorStyles.input}
+            value={value}
+            onChange={(event) => handleValueChange(event.target.value)}
+            placeholder="Ex. projet-final"
+          />
+          <span style={workflowEditorStyles.helperText}>
+            Comparaison appliquée au nom du dossier étudiant courant.
+          </span>
+        </div>
+      )}
+
+      {isFileCondition(selector) && (
+        <CheckboxField
+          label="Ignorer la casse des motifs"
+          checked={normalizedTest.ignoreCase === true}
+          onChange={(checked) =>
+            onChange(
+              normalizeConditionTest({
+                ...test,
+                ignoreCase: checked,
+              })
+            )
+          }
+        />
+      )}
+
+      {selector === "file-search" && (
+        <div style={workflowEditorStyles.formGrid}>
+          <div style={workflowEditorStyles.field}>
+            <label style={workflowEditorStyles.label}>
+              Motif de fichier
+            </label>
+            <input
+              style={workflowEditorStyles.input}
+              value={pattern}
+              onChange={(event) => handlePatternChange(event.target.value)}
+              placeholder="Ex. rapport.pdf ou *.yaml"
+            />
+            <span style={workflowEditorStyles.helperText}>
+              Globs : *, **, ?, [0-9], {a,b}. Séparez plusieurs motifs par ; et
+              excluez avec ! (ex. **/*.java; !build).
+            </span>
+          </div>
+          <div style={workflowEditorStyles.field}>
+            <label style={workflowEditorStyles.label}>Portée</label>
+            <select
+              style={workflowEditorStyles.select}
+              value={scope}
+              onChange={(event) =>
+                handleScopeChange(event.target.value as ConditionScope)
+              }
+            >
+              {conditionScopeOptions.map((option) => (
+                <option key={option.value} value={option.value}>
+                  {option.label}
+                </option>
+              ))}
+            </select>
+          </div>
+        </div>
+      )}
+
+      {selector === "file-count" && (
+        <>
           <div style={workflowEditorStyles.formGrid}>
             <div style={workflowEditorStyles.field}>
-              <label style={workflowEditorStyles.label}>Sélecteur</label>
-              <select
-                style={workflowEditorStyles.select}
-                value={selector}
+              <label style={workflowEditorStyles.label}>
+                Motif (optionnel)
+              </label>
+              <input
+                style={workflowEditorStyles.input}
+                value={pattern}
                 onChange={(event) =>
-                  handleSelectorChange(event.target.value as ConditionSelector)
+                  handlePatternChange(event.target.value)
                 }
-              >
-                {conditionSelectorOptions.map((option) => (
-                  <option key={option.value} value={option.value}>
-                    {option.label}
-                  </option>
-                ))}
-              </select>
+                placeholder="Laissez vide pour compter tous les fichiers"
+              />
+              <span style={workflowEditorStyles.helperText}>
+                Globs : *, **, ?, [0-9], {a,b}. Séparez plusieurs motifs par ;
+                et excluez avec ! (ex. *.pdf; !brouillon*).
+              </span>
             </div>
             <div style={workflowEditorStyles.field}>
-              <label style={workflowEditorStyles.label}>Opérateur</label>
+              <label style={workflowEditorStyles.label}>Portée</label>
               <select
                 style={workflowEditorStyles.select}
-                value={operator}
+                value={scope}
                 onChange={(event) =>
-                  handleOperatorChange(event.target.value as ConditionOperator)
+                  handleScopeChange(event.target.value as ConditionScope)
                 }
               >
-                {operatorChoices.map((option) => (
+                {conditionScopeOptions.map((option) => (
                   <option key={option.value} value={option.value}>
                     {option.label}
                   </option>
@@ -1547,122 +2208,275 @@ const SpecificFields: React.FC<SpecificFieldsProps> = ({
               </select>
             </div>
           </div>
+          <div style={workflowEditorStyles.field}>
+            <label style={workflowEditorStyles.label}>
+              Valeur de comparaison
+            </label>
+            <input
+              style={workflowEditorStyles.input}
+              type="number"
+              min={0}
+              value={value}
+              onChange={(event) =>
+                handleCountValueChange(event.target.value)
+              }
+            />
+            <span style={workflowEditorStyles.helperText}>
+              Nombre de fichiers correspondant au motif à comparer.
+            </span>
+          </div>
+        </>
+      )}
 
-          {selector === "current-folder-name" && (
+      {selector === "captured-value" && (
+        <div style={workflowEditorStyles.formGrid}>
+          <div style={workflowEditorStyles.field}>
+            <label style={workflowEditorStyles.label}>
+              Nom de la valeur capturée
+            </label>
+            <input
+              style={workflowEditorStyles.input}
+              value={normalizedTest.variable ?? ""}
+              onChange={(event) => handleVariableChange(event.target.value)}
+              placeholder="Ex. testsReussis"
+            />
+          </div>
+          {operator !== "exists" && operator !== "not-exists" && (
             <div style={workflowEditorStyles.field}>
-              <label style={workflowEditorStyles.label}>Valeur attendue</label>
+              <label style={workflowEditorStyles.label}>
+                Valeur de comparaison
+              </label>
               <input
                 style={workflowEditorStyles.input}
                 value={value}
                 onChange={(event) => handleValueChange(event.target.value)}
-                placeholder="Ex. projet-final"
               />
-              <span style={workflowEditorStyles.helperText}>
-                Comparaison appliquée au nom du dossier étudiant courant.
-              </span>
             </div>
           )}
+          <span style={workflowEditorStyles.helperText}>
+            Valeur capturée par une commande ou un script précédent pour
+            ce dossier.
+          </span>
+        </div>
+      )}
 
-          {selector === "file-search" && (
-            <div style={workflowEditorStyles.formGrid}>
-              <div style={workflowEditorStyles.field}>
-                <label style={workflowEditorStyles.label}>
-                  Motif de fichier
-                </label>
-                <input
-                  style={workflowEditorStyles.input}
-                  value={pattern}
-                  onChange={(event) => handlePatternChange(event.target.value)}
-                  placeholder="Ex. rapport.pdf ou *.yaml"
-                />
-                <span style={workflowEditorStyles.helperText}>
-                  Utilisez * comme caractère générique. Exemple : *.md.
-                </span>
-              </div>
+      {metricConditionSelectors.includes(selector) && (
+        <div style={workflowEditorStyles.formGrid}>
+          <div style={workflowEditorStyles.field}>
+            <label style={workflowEditorStyles.label}>Motif (optionnel)</label>
+            <input
+              style={workflowEditorStyles.input}
+              value={pattern}
+              onChange={(event) => handlePatternChange(event.target.value)}
+              placeholder="Laissez vide pour tous les fichiers"
+            />
+          </div>
+          <div style={workflowEditorStyles.field}>
+            <label style={workflowEditorStyles.label}>Portée</label>
+            <select
+              style={workflowEditorStyles.select}
+              value={scope}
+              onChange={(event) =>
+                handleScopeChange(event.target.value as ConditionScope)
+              }
+            >
+              {conditionScopeOptions.map((option) => (
+                <option key={option.value} value={option.value}>
+                  {option.label}
+                </option>
+              ))}
+            </select>
+          </div>
+          <div style={workflowEditorStyles.field}>
+            <label style={workflowEditorStyles.label}>
+              Valeur de comparaison
+            </label>
+            <input
+              style={workflowEditorStyles.input}
+              value={value}
+              onChange={(event) => handleValueChange(event.target.value)}
+              placeholder={metricValueHints[selector]}
+            />
+            <span style={workflowEditorStyles.helperText}>
+              Tailles en multiples de 1024 (Ko, Mo, Go). Les dates sont
+              comparées à la précision saisie, en heure locale.
+            </span>
+          </div>
+        </div>
+      )}
+
+      {selector === "command-exit-code" && (
+        <>
+          <div style={workflowEditorStyles.formGrid}>
+            <div style={workflowEditorStyles.field}>
+              <label style={workflowEditorStyles.label}>Lancer</label>
+              <select
+                style={workflowEditorStyles.select}
+                value={normalizedTest.runner ?? "command"}
+                onChange={(event) =>
+                  handleCommandChange({
+                    runner: event.target.value as ConditionRunner,
+                  })
+                }
+              >
+                <option value="command">Une commande</option>
+                <option value="python">Un extrait Python</option>
+              </select>
+            </div>
+            {normalizedTest.runner !== "python" && (
               <div style={workflowEditorStyles.field}>
-                <label style={workflowEditorStyles.label}>Portée</label>
+                <label style={workflowEditorStyles.label}>Shell</label>
                 <select
                   style={workflowEditorStyles.select}
-                  value={scope}
+                  value={normalizedTest.shell ?? "default"}
                   onChange={(event) =>
-                    handleScopeChange(event.target.value as ConditionScope)
+                    handleCommandChange({
+                      shell: event.target
+                        .value as CommandeurConditionTest["shell"],
+                    })
                   }
                 >
-                  {conditionScopeOptions.map((option) => (
-                    <option key={option.value} value={option.value}>
-                      {option.label}
-                    </option>
-                  ))}
+                  <option value="default">Système</option>
+                  <option value="bash">Bash</option>
+                  <option value="zsh">Zsh</option>
+                  <option value="powershell">PowerShell</option>
+                  <option value="fish">Fish</option>
                 </select>
               </div>
+            )}
+            <div style={workflowEditorStyles.field}>
+              <label style={workflowEditorStyles.label}>
+                Délai maximal en secondes (optionnel)
+              </label>
+              <input
+                style={workflowEditorStyles.input}
+                value={normalizedTest.timeoutSeconds?.toString() ?? ""}
+                onChange={(event) =>
+                  handleCommandChange({
+                    timeoutSeconds: parseTimeoutSeconds(event.target.value),
+                  })
+                }
+              />
             </div>
-          )}
+          </div>
+          <div style={workflowEditorStyles.field}>
+            <label style={workflowEditorStyles.label}>
+              {normalizedTest.runner === "python" ? "Code Python" : "Commande"}
+            </label>
+            <textarea
+              style={workflowEditorStyles.textarea}
+              value={normalizedTest.command ?? ""}
+              onChange={(event) =>
+                handleCommandChange({ command: event.target.value })
+              }
+              placeholder={
+                normalizedTest.runner === "python"
+                  ? "import sys; sys.exit(0)"
+                  : "Ex. javac *.java"
+              }
+            />
+          </div>
+          <div style={workflowEditorStyles.field}>
+            <label style={workflowEditorStyles.label}>
+              {operator === "equals" ||
+              operator === "greater-than" ||
+              operator === "less-than"
+                ? "Code de sortie attendu"
+                : "Texte ou regex recherché"}
+            </label>
+            <input
+              style={workflowEditorStyles.input}
+              value={value}
+              onChange={(event) => handleValueChange(event.target.value)}
+            />
+            <span style={workflowEditorStyles.helperText}>
+              La commande est lancée dans le dossier étudiant à chaque
+              exécution (pas en simulation). « Système » utilise sh (cmd sous
+              Windows) : jokers, tubes et redirections comme *.java sont
+              acceptés.
+            </span>
+          </div>
+        </>
+      )}
 
-          {selector === "file-count" && (
-            <>
-              <div style={workflowEditorStyles.formGrid}>
-                <div style={workflowEditorStyles.field}>
-                  <label style={workflowEditorStyles.label}>
-                    Motif (optionnel)
-                  </label>
-                  <input
-                    style={workflowEditorStyles.input}
-                    value={pattern}
-                    onChange={(event) =>
-                      handlePatternChange(event.target.value)
-                    }
-                    placeholder="Laissez vide pour compter tous les fichiers"
-                  />
-                  <span style={workflowEditorStyles.helperText}>
-                    Utilisez * comme caractère générique. Exemple : *.pdf.
-                  </span>
-                </div>
-                <div style={workflowEditorStyles.field}>
-                  <label style={workflowEditorStyles.label}>Portée</label>
-                  <select
-                    style={workflowEditorStyles.select}
-                    value={scope}
-                    onChange={(event) =>
-                      handleScopeChange(event.target.value as ConditionScope)
-                    }
-                  >
-                    {conditionScopeOptions.map((option) => (
-                      <option key={option.value} value={option.value}>
-                        {option.label}
-                      </option>
-                    ))}
-                  </select>
-                </div>
-              </div>
+      {selector === "file-content" && (
+        <>
+          <div style={workflowEditorStyles.formGrid}>
+            <div style={workflowEditorStyles.field}>
+              <label style={workflowEditorStyles.label}>Motif de fichier</label>
+              <input
+                style={workflowEditorStyles.input}
+                value={pattern}
+                onChange={(event) => handlePatternChange(event.target.value)}
+                placeholder="Ex. **/Main.java ou README.md"
+              />
+            </div>
+            <div style={workflowEditorStyles.field}>
+              <label style={workflowEditorStyles.label}>Portée</label>
+              <select
+                style={workflowEditorStyles.select}
+                value={scope}
+                onChange={(event) =>
+                  handleScopeChange(event.target.value as ConditionScope)
+                }
+              >
+                {conditionScopeOptions.map((option) => (
+                  <option key={option.value} value={option.value}>
+                    {option.label}
+                  </option>
+                ))}
+              </select>
+            </div>
+            <div style={workflowEditorStyles.field}>
+              <label style={workflowEditorStyles.label}>Fichiers concernés</label>
+              <select
+                style={workflowEditorStyles.select}
+                value={normalizedTest.quantifier ?? "any"}
+                onChange={(event) =>
+                  handleQuantifierChange(
+                    event.target.value as ConditionQuantifier
+                  )
+                }
+              >
+                {conditionQuantifierOptions.map((option) => (
+                  <option key={option.value} value={option.value}>
+                    {option.label}
+                  </option>
+                ))}
+              </select>
+            </div>
+          </div>
+          <div style={workflowEditorStyles.formGrid}>
+            <div style={workflowEditorStyles.field}>
+              <label style={workflowEditorStyles.label}>
+                {operator === "regex" ? "Expression régulière" : "Texte"}
+              </label>
+              <input
+                style={workflowEditorStyles.input}
+                value={value}
+                onChange={(event) => handleValueChange(event.target.value)}
+                placeholder="Ex. public static void main"
+              />
+            </div>
+            {operator === "regex" && (
               <div style={workflowEditorStyles.field}>
-                <label style={workflowEditorStyles.label}>
-                  Valeur de comparaison
-                </label>
+                <label style={workflowEditorStyles.label}>Options regex</label>
                 <input
                   style={workflowEditorStyles.input}
-                  type="number"
-                  min={0}
-                  value={value}
-                  onChange={(event) =>
-                    handleCountValueChange(event.target.value)
-                  }
+                  value={normalizedTest.flags ?? ""}
+                  onChange={(event) => handleFlagsChange(event.target.value)}
+                  placeholder="Ex. i, m, s"
                 />
-                <span style={workflowEditorStyles.helperText}>
-                  Nombre de fichiers correspondant au motif à comparer.
-                </span>
               </div>
-            </>
-          )}
-
-          <div style={workflowEditorStyles.helperText}>
-            Les sous-opérations sont gérées dans le panneau de gauche.
+            )}
           </div>
-        </div>
-      );
-    }
-    default:
-      return null;
-  }
+          <span style={workflowEditorStyles.helperText}>
+            Sans fichier correspondant au motif, la condition est fausse.
+          </span>
+        </>
+      )}
+    </div>
+  );
 };
 
 interface FieldProps {
@@ -1672,6 +2486,171 @@ interface FieldProps {
   multiline?: boolean;
 }
 
+const patternOperationKinds: CommandeurOperationKind[] = [
+  "delete-file",
+  "copy",
+  "move",
+  "replace-in-file",
+  "rename",
+];
+
+// Les lignes vides sont conservées pendant la saisie ; le moteur les ignore.
+function splitPatternLines(value: string): string[] | undefined {
+  return value.trim().length > 0 ? value.split("\n") : undefined;
+}
+
+function parseMatchBound(value: string): number | undefined {
+  const parsed = Number.parseInt(value, 10);
+  return Number.isNaN(parsed) || parsed < 0 ? undefined : parsed;
+}
+
+interface MatchingFieldsProps {
+  matching: CommandeurTargetMatching | undefined;
+  onChange: (matching: CommandeurTargetMatching | undefined) => void;
+}
+
+const MatchingFields: React.FC<MatchingFieldsProps> = ({
+  matching,
+  onChange,
+}) => {
+  const update = (patch: Partial<CommandeurTargetMatching>) => {
+    const next = { ...matching, ...patch };
+    const isEmpty =
+      !next.include &&
+      !next.exclude &&
+      next.minMatches === undefined &&
+      next.maxMatches === undefined &&
+      !next.caseInsensitive;
+    onChange(isEmpty ? undefined : next);
+  };
+
+  return (
+    <div style={workflowEditorStyles.formGrid}>
+      <div style={workflowEditorStyles.field}>
+        <label style={workflowEditorStyles.label}>
+          Motifs inclus en plus (un par ligne)
+        </label>
+        <textarea
+          style={workflowEditorStyles.textarea}
+          value={(matching?.include ?? []).join("\n")}
+          onChange={(event) =>
+            update({ include: splitPatternLines(event.target.value) })
+          }
+          placeholder="Ex. **/node_modules"
+        />
+      </div>
+      <div style={workflowEditorStyles.field}>
+        <label style={workflowEditorStyles.label}>
+          Motifs exclus (un par ligne)
+        </label>
+        <textarea
+          style={workflowEditorStyles.textarea}
+          value={(matching?.exclude ?? []).join("\n")}
+          onChange={(event) =>
+            update({ exclude: splitPatternLines(event.target.value) })
+          }
+          placeholder="Ex. ressources/**"
+        />
+        <span style={workflowEditorStyles.helperText}>
+          Le chemin principal accepte aussi *, **, ?, [0-9] et {"{a,b}"}.
+        </span>
+      </div>
+      <div style={workflowEditorStyles.field}>
+        <label style={workflowEditorStyles.label}>
+          Correspondances minimum
+        </label>
+        <input
+          style={workflowEditorStyles.input}
+          inputMode="numeric"
+          value={matching?.minMatches?.toString() ?? ""}
+          onChange={(event) =>
+            update({ minMatches: parseMatchBound(event.target.value) })
+          }
+          placeholder="Aucun minimum"
+        />
+      </div>
+      <div style={workflowEditorStyles.field}>
+        <label style={workflowEditorStyles.label}>
+          Correspondances maximum
+        </label>
+        <input
+          style={workflowEditorStyles.input}
+          inputMode="numeric"
+          value={matching?.maxMatches?.toString() ?? ""}
+          onChange={(event) =>
+            update({ maxMatches: parseMatchBound(event.target.value) })
+          }
+          placeholder="Aucun maximum"
+        />
+      </div>
+      <label style={workflowEditorStyles.checkboxRow}>
+        <input
+          type="checkbox"
+          checked={matching?.caseInsensitive === true}
+          onChange={(event) =>
+            update({ caseInsensitive: event.target.checked || undefined })
+          }
+          className="workflow-checkbox"
+        />
+        Ignorer la casse
+      </label>
+    </div>
+  );
+};
+
+interface CaptureFieldsProps {
+  capture: CommandeurOutputCapture | undefined;
+  onChange: (capture: CommandeurOutputCapture | undefined) => void;
+}
+
+const CaptureFields: React.FC<CaptureFieldsProps> = ({ capture, onChange }) => (
+  <>
+    <div style={workflowEditorStyles.field}>
+      <label style={workflowEditorStyles.label}>Capturer la sortie</label>
+      <select
+        style={workflowEditorStyles.input}
+        value={capture?.source ?? ""}
+        onChange={(event) => {
+          const source = event.target.value as
+            | CommandeurOutputCapture["source"]
+            | "";
+          onChange(
+            source
+              ? { name: capture?.name ?? "", ...capture, source }
+              : undefined
+          );
+        }}
+      >
+        <option value="">Ne rien capturer</option>
+        <option value="stdout">Sortie standard (nettoyée)</option>
+        <option value="exit-code">Code de sortie (n'échoue pas)</option>
+        <option value="regex">Extrait de la sortie (regex)</option>
+      </select>
+    </div>
+    {capture && (
+      <Field
+        label="Nom de la valeur capturée"
+        value={capture.name}
+        onChange={(name) => onChange({ ...capture, name })}
+      />
+    )}
+    {capture?.source === "regex" && (
+      <Field
+        label="Regex (premier groupe capturé)"
+        value={capture.pattern ?? ""}
+        onChange={(pattern) =>
+          onChange({ ...capture, pattern: pattern || undefined })
+        }
+      />
+    )}
+  </>
+);
+
+function parseTimeoutSeconds(value: string): number | undefined {
+  const parsed = Number.parseInt(value, 10);
+  return Number.isNaN(parsed) || parsed < 1 ? undefined : parsed;
+}
+
 const Field: React.FC<FieldProps> = ({ label, value, onChange, multiline }) => (
   <div style={workflowEditorStyles.field}>
     <label style={workflowEditorStyles.label}>{label}</label>
@@ -1,6 +1,5 @@
 import React, { useCallback, useEffect, useMemo, useState } from "react";
-import { open } from "@tauri-apps/api/dialog";
-import { readTextFile } from "@tauri-apps/api/fs";
+import { open, save } from "@tauri-apps/api/dialog";
 import { commandeurStyles } from "./Commandeur.styles";
 import type {
   CommandeurSavedWorkflowSummary,
@@ -9,13 +8,15 @@ import type {
   CommandeurWorkspaceSummary,
 } from "../../types";
 import {
-  parseWorkflowYaml,
   serializeWorkflowToYaml,
 } from "../../services/commandeur/workflowYaml";
 import WorkflowEditor from "./WorkflowEditor/WorkflowEditor";
 import {
   deleteSavedCommandeurWorkflow,
   duplicateSavedCommandeurWorkflow,
+  exportCommandeurWorkflow,
+  getCommandeurWorkflowSchemaVersion,
+  importCommandeurWorkflow,
   listSavedCommandeurWorkflows,
   loadSavedCommandeurWorkflow,
   saveCommandeurWorkflow,
@@ -103,6 +104,14 @@ export const WorkflowStep: React.FC<WorkflowStepProps> = ({
   }, [savedWorkflowId, workflow]);
 
   const isDesktop = typeof window !== "undefined" && "__TAURI__" in window;
+  const [schemaVersion, setSchemaVersion] = useState<number | null>(null);
+
+  useEffect(() => {
+    if (!isDesktop) return;
+    getCommandeurWorkflowSchemaVersion()
+      .then(setSchemaVersion)
+      .catch(() => setSchemaVersion(null));
+  }, [isDesktop]);
 
   const refreshSavedWorkflows = useCallback(async () => {
     if (!isDesktop) return;
@@ -128,16 +137,20 @@ export const WorkflowStep: React.FC<WorkflowStepProps> = ({
       const isDesktop = typeof window !== "undefined" && "__TAURI__" in window;
       if (!isDesktop) {
         throw new Error(
-          "L'import YAML est disponible uniquement dans l'application desktop."
+          "L'import de workflow est disponible uniquement dans l'application desktop."
         );
       }
       const selection = await open({
         multiple: false,
-        filters: [{ name: "Workflow YAML", extensions: ["yaml", "yml"] }],
+        filters: [
+          {
+            name: "Workflow YAML ou JSON",
+            extensions: ["yaml", "yml", "json"],
+          },
+        ],
       });
       if (!selection || Array.isArray(selection)) return;
-      const content = await readTextFile(selection);
-      const parsed = parseWorkflowYaml(content);
+      const parsed = await importCommandeurWorkflow(selection);
       setActiveSavedWorkflowId(null);
       onWorkflowLoaded({ workflow: parsed, path: selection, savedId: null });
       setSaveError(null);
@@ -309,15 +322,41 @@ export const WorkflowStep: React.FC<WorkflowStepProps> = ({
     [isDesktop, onNotify, onWorkflowLoaded, refreshSavedWorkflows]
   );
 
-  const handleExportWorkflow = () => {
+  const handleExportWorkflow = async () => {
     if (!workflow) return;
-    const yaml = serializeWorkflowToYaml(workflow);
     const fileStem = workflow.name
       ? workflow.name
           .trim()
           .toLowerCase()
           .replace(/[^a-z0-9-_]+/g, "-") || "workflow"
       : "workflow";
+    if (isDesktop) {
+      try {
+        const destination = await save({
+          defaultPath: `${fileStem}.yaml`,
+          filters: [
+            { name: "Workflow YAML", extensions: ["yaml", "yml"] },
+            { name: "Workflow JSON", extensions: ["json"] },
+          ],
+        });
+        if (!destination) return;
+        await exportCommandeurWorkflow(workflow, destination);
+        onNotify({
+          tone: "success",
+          message: `Workflow exporté : ${destination}`,
+        });
+      } catch (err) {
+        onNotify({
+          tone: "error",
+          message:
+            err instanceof Error
+              ? err.message
+              : "Impossible d'exporter le workflow",
+        });
+      }
+      return;
+    }
+    const yaml = serializeWorkflowToYaml(workflow, schemaVersion);
     const blob = new Blob([yaml], { type: "text/yaml" });
     const url = URL.createObjectURL(blob);
     const anchor = document.createElement("a");
@@ -330,8 +369,8 @@ export const WorkflowStep: React.FC<WorkflowStepProps> = ({
   };
 
   const currentYaml = useMemo(
-    () => (workflow ? serializeWorkflowToYaml(workflow) : null),
-    [workflow]
+    () => (workflow ? serializeWorkflowToYaml(workflow, schemaVersion) : null),
+    [workflow, schemaVersion]
   );
 
   const validationTone = computeValidationTone(
@@ -390,7 +429,7 @@ export const WorkflowStep: React.FC<WorkflowStepProps> = ({
         </button>
         <button
           className="btn"
-          onClick={handleExportWorkflow}
+          onClick={() => void handleExportWorkflow()}
           disabled={!workflow}
         >
           Exporter
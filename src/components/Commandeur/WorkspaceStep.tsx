@@ -2,7 +2,10 @@ import React, { useEffect, useState } from "react";
 import { open } from "@tauri-apps/api/dialog";
 import { FolderOpen } from "lucide-react";
 import { commandeurStyles } from "./Commandeur.styles";
-import type { CommandeurWorkspaceSummary } from "../../types";
+import type {
+  CommandeurNestedArchiveMode,
+  CommandeurWorkspaceSummary,
+} from "../../types";
 import { prepareCommandeurWorkspace } from "../../services/commandeur/api";
 import Modal from "../Modal/Modal";
 
@@ -24,12 +27,14 @@ export const WorkspaceStep: React.FC<WorkspaceStepProps> = ({
   const [isLoading, setIsLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [showSubfoldersDialog, setShowSubfoldersDialog] = useState(false);
+  const [nestedArchives, setNestedArchives] =
+    useState<CommandeurNestedArchiveMode>("ignore");
 
   useEffect(() => {
     setShowSubfoldersDialog(false);
   }, [workspace?.workspaceId]);
 
-  const selectWorkspace = async () => {
+  const selectWorkspace = async (source: "directory" | "zip" = "directory") => {
     try {
       setError(null);
       setIsLoading(true);
@@ -39,12 +44,19 @@ export const WorkspaceStep: React.FC<WorkspaceStepProps> = ({
           "La sélection de fichiers est disponible uniquement dans l'application desktop."
         );
       }
-      const selection = await open({
-        multiple: false,
-        directory: true,
-      });
+      const selection = await open(
+        source === "zip"
+          ? {
+              multiple: false,
+              directory: false,
+              filters: [{ name: "Archive ZIP", extensions: ["zip", "zipx"] }],
+            }
+          : { multiple: false, directory: true }
+      );
       if (!selection || Array.isArray(selection)) return;
-      const summary = await prepareCommandeurWorkspace(selection);
+      const summary = await prepareCommandeurWorkspace(selection, {
+        nestedArchives,
+      });
       onWorkspaceReady(summary);
     } catch (err) {
       setError(err instanceof Error ? err.message : String(err));
@@ -65,7 +77,8 @@ export const WorkspaceStep: React.FC<WorkspaceStepProps> = ({
               fontSize: ".85rem",
             }}
           >
-            Choisissez un dossier contenant les sous-dossiers étudiants.
+            Choisissez un dossier ou une archive ZIP contenant les
+            sous-dossiers étudiants.
           </p>
         </div>
       </div>
@@ -76,6 +89,22 @@ export const WorkspaceStep: React.FC<WorkspaceStepProps> = ({
           <p style={commandeurStyles.workspaceSelector.hint}>
             Sélectionnez le dossier racine qui contient les dossiers étudiants.
           </p>
+          <label style={commandeurStyles.workspaceSelector.hint}>
+            Archives imbriquées (ex. Intra.zip) :{" "}
+            <select
+              value={nestedArchives}
+              onChange={(event) =>
+                setNestedArchives(
+                  event.target.value as CommandeurNestedArchiveMode
+                )
+              }
+              disabled={isLoading}
+            >
+              <option value="ignore">Laisser telles quelles</option>
+              <option value="replace">Extraire et supprimer l'archive</option>
+              <option value="keep-both">Extraire et conserver l'archive</option>
+            </select>
+          </label>
           <div style={commandeurStyles.workspaceSelector.actions}>
             <button
               className="btn btn-primary"
@@ -84,6 +113,13 @@ export const WorkspaceStep: React.FC<WorkspaceStepProps> = ({
             >
               {isLoading ? "Préparation..." : "Choisir un dossier"}
             </button>
+            <button
+              className="btn"
+              onClick={() => selectWorkspace("zip")}
+              disabled={isLoading}
+            >
+              Choisir une archive ZIP
+            </button>
           </div>
           {isLoading && (
             <div style={commandeurStyles.workspaceSelector.status}>
@@ -109,6 +145,13 @@ export const WorkspaceStep: React.FC<WorkspaceStepProps> = ({
               >
                 Changer de dossier
               </button>
+              <button
+                className="btn"
+                onClick={() => selectWorkspace("zip")}
+                disabled={isLoading}
+              >
+                Choisir une archive ZIP
+              </button>
               <button className="btn" onClick={onReset} disabled={isLoading}>
                 Réinitialiser
               </button>
@@ -135,6 +178,16 @@ export const WorkspaceStep: React.FC<WorkspaceStepProps> = ({
               Archive extraite : {workspace.extractedPath}
             </p>
           )}
+          {workspace.expandedArchives.length > 0 && (
+            <p style={commandeurStyles.workspaceSummary.extracted}>
+              Archives imbriquées extraites :{" "}
+              {
+                workspace.expandedArchives.filter((item) => !item.error)
+                  .length
+              }
+              /{workspace.expandedArchives.length}
+            </p>
+          )}
           {isLoading && (
             <div style={commandeurStyles.workspaceSelector.status}>
               Préparation du workspace...
@@ -1,11 +1,18 @@
 import { invoke } from "@tauri-apps/api/tauri";
 import type {
+  CommandeurDryRunResult,
+  CommandeurExecutionOptions,
   CommandeurExecutionResult,
-  CommandeurExecutionStatus,
+  CommandeurExecutionStatusReport,
+  CommandeurFolderSelection,
+  CommandeurRunHistoryEntry,
+  CommandeurRunHistoryFilter,
   CommandeurValidationMessage,
   CommandeurWorkflow,
+  CommandeurWorkspaceOptions,
   CommandeurWorkspaceSummary,
   CommandeurSavedWorkflowSummary,
+  CommandeurSnapshotRestoreResult,
 } from "../../types";
 
 function ensureDesktop() {
@@ -16,10 +23,14 @@ function ensureDesktop() {
   }
 }
 
-export async function prepareCommandeurWorkspace(path: string) {
+export async function prepareCommandeurWorkspace(
+  path: string,
+  options?: CommandeurWorkspaceOptions
+) {
   ensureDesktop();
   return invoke<CommandeurWorkspaceSummary>("commandeur_prepare_workspace", {
     path,
+    options: options ?? null,
   });
 }
 
@@ -34,30 +45,82 @@ export async function validateCommandeurWorkflow(
   });
 }
 
-export async function executeCommandeurWorkflow(
+export async function dryRunCommandeurWorkflow(
   workspaceId: string,
   workflow: CommandeurWorkflow
+) {
+  ensureDesktop();
+  return invoke<CommandeurDryRunResult>("commandeur_dry_run_workflow", {
+    workspaceId,
+    workflow,
+  });
+}
+
+export async function executeCommandeurWorkflow(
+  workspaceId: string,
+  workflow: CommandeurWorkflow,
+  options?: CommandeurExecutionOptions
 ) {
   ensureDesktop();
   return invoke<CommandeurExecutionResult>("commandeur_execute_workflow", {
     workspaceId,
     workflow,
+    options: options ?? null,
+  });
+}
+
+/** Reprend une exécution interrompue à partir de son journal. */
+export async function continueCommandeurExecution(
+  workspaceId: string,
+  runId: string
+) {
+  ensureDesktop();
+  return invoke<CommandeurExecutionResult>("commandeur_resume_execution", {
+    workspaceId,
+    runId,
+  });
+}
+
+export async function previewCommandeurFolderSelection(
+  workspaceId: string,
+  selection: CommandeurFolderSelection
+) {
+  ensureDesktop();
+  return invoke<string[]>("commandeur_preview_folder_selection", {
+    workspaceId,
+    selection,
   });
 }
 
+export async function restoreCommandeurSnapshot(
+  workspaceId: string,
+  runId: string,
+  folders?: string[]
+) {
+  ensureDesktop();
+  return invoke<CommandeurSnapshotRestoreResult>(
+    "commandeur_restore_snapshot",
+    {
+      workspaceId,
+      runId,
+      folders: folders ?? null,
+    }
+  );
+}
+
 export async function pauseCommandeurExecution() {
   ensureDesktop();
-  return invoke<CommandeurExecutionStatus>("commandeur_execution_pause");
+  return invoke<CommandeurExecutionStatusReport>("commandeur_execution_pause");
 }
 
 export async function resumeCommandeurExecution() {
   ensureDesktop();
-  return invoke<CommandeurExecutionStatus>("commandeur_execution_resume");
+  return invoke<CommandeurExecutionStatusReport>("commandeur_execution_resume");
 }
 
 export async function stopCommandeurExecution() {
   ensureDesktop();
-  return invoke<CommandeurExecutionStatus>("commandeur_execution_stop");
+  return invoke<CommandeurExecutionStatusReport>("commandeur_execution_stop");
 }
 
 export async function saveCommandeurWorkflow(
@@ -98,7 +161,62 @@ export async function duplicateSavedCommandeurWorkflow(id: string) {
   );
 }
 
+export async function importCommandeurWorkflow(path: string) {
+  ensureDesktop();
+  return invoke<CommandeurWorkflow>("commandeur_import_workflow", { path });
+}
+
+export async function exportCommandeurWorkflow(
+  workflow: CommandeurWorkflow,
+  path: string
+) {
+  ensureDesktop();
+  return invoke<void>("commandeur_export_workflow", { workflow, path });
+}
+
+export async function getCommandeurWorkflowSchemaVersion() {
+  ensureDesktop();
+  return invoke<number>("commandeur_workflow_schema_version");
+}
+
 export async function listAvailableShells() {
   ensureDesktop();
   return invoke<string[]>("list_available_shells");
 }
+
+export async function listCommandeurRuns(filter?: CommandeurRunHistoryFilter) {
+  ensureDesktop();
+  return invoke<CommandeurRunHistoryEntry[]>("commandeur_list_runs", {
+    filter: filter ?? null,
+  });
+}
+
+export async function getCommandeurRunResult(runId: string) {
+  ensureDesktop();
+  return invoke<CommandeurExecutionResult>("commandeur_get_run_result", {
+    runId,
+  });
+}
+
+export async function exportCommandeurRunSummary(
+  runId: string,
+  destination: string
+) {
+  ensureDesktop();
+  return invoke<void>("commandeur_export_run_summary", { runId, destination });
+}
+
+export async function openCommandeurRunReport(runId: string) {
+  ensureDesktop();
+  return invoke<string>("commandeur_open_run_report", { runId });
+}
+
+export async function openCommandeurRun(runId: string) {
+  ensureDesktop();
+  return invoke<void>("commandeur_open_run", { runId });
+}
+
+export async function deleteCommandeurRun(runId: string) {
+  ensureDesktop();
+  return invoke<void>("commandeur_delete_run", { runId });
+}
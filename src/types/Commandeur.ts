@@ -15,6 +15,7 @@ export const CommandeurOperationKind = z.enum([
   "mkdir",
   "python",
   "if",
+  "for-each",
 ]);
 
 export type CommandeurOperationKind = z.infer<typeof CommandeurOperationKind>;
@@ -23,6 +24,13 @@ export const CommandeurConditionSelector = z.enum([
   "current-folder-name",
   "file-search",
   "file-count",
+  "captured-value",
+  "file-content",
+  "total-size",
+  "newest-modified",
+  "oldest-modified",
+  "line-count",
+  "command-exit-code",
 ]);
 
 export type CommandeurConditionSelector = z.infer<
@@ -32,6 +40,7 @@ export type CommandeurConditionSelector = z.infer<
 export const CommandeurConditionOperator = z.enum([
   "equals",
   "contains",
+  "not-contains",
   "regex",
   "exists",
   "not-exists",
@@ -47,6 +56,18 @@ export const CommandeurConditionScope = z.enum(["current-folder", "recursive"]);
 
 export type CommandeurConditionScope = z.infer<typeof CommandeurConditionScope>;
 
+export const CommandeurConditionRunner = z.enum(["command", "python"]);
+
+export type CommandeurConditionRunner = z.infer<
+  typeof CommandeurConditionRunner
+>;
+
+export const CommandeurConditionQuantifier = z.enum(["any", "all"]);
+
+export type CommandeurConditionQuantifier = z.infer<
+  typeof CommandeurConditionQuantifier
+>;
+
 export const PathFragmentSchema = z
   .string()
   .min(1, "Chemin requis")
@@ -55,6 +76,32 @@ export const PathFragmentSchema = z
       'Le chemin ne doit pas contenir de caractère invalide (\\, :, *, ?, " , <, >, |)',
   });
 
+export const PathPatternSchema = z
+  .string()
+  .min(1, "Chemin requis")
+  .regex(/^[^\\:"<>|]+$/u, {
+    message:
+      'Le motif ne doit pas contenir de caractère invalide (\\, :, " , <, >, |)',
+  });
+
+const TargetMatchingSchema = z
+  .object({
+    include: z.array(z.string()).optional(),
+    exclude: z.array(z.string()).optional(),
+    minMatches: z.number().int().min(0).optional(),
+    maxMatches: z.number().int().min(0).optional(),
+    caseInsensitive: z.boolean().optional(),
+  })
+  .refine(
+    (matching) =>
+      matching.minMatches === undefined ||
+      matching.maxMatches === undefined ||
+      matching.minMatches <= matching.maxMatches,
+    { message: "Le minimum de correspondances dépasse le maximum" }
+  );
+
+export type CommandeurTargetMatching = z.infer<typeof TargetMatchingSchema>;
+
 const BaseOperationSchema = z.object({
   id: z.string().uuid(),
   label: z.string().min(1, "Nom requis"),
@@ -62,6 +109,7 @@ const BaseOperationSchema = z.object({
   enabled: z.boolean().default(true),
   kind: CommandeurOperationKind,
   continueOnError: z.boolean().default(false),
+  matching: TargetMatchingSchema.optional(),
 });
 
 const CreateFileSchema = BaseOperationSchema.extend({
@@ -73,17 +121,26 @@ const CreateFileSchema = BaseOperationSchema.extend({
 
 const DeleteFileSchema = BaseOperationSchema.extend({
   kind: z.literal("delete-file"),
-  target: PathFragmentSchema,
+  target: PathPatternSchema,
   required: z.boolean().default(false),
 });
 
 const CopySchema = BaseOperationSchema.extend({
   kind: z.literal("copy"),
-  source: PathFragmentSchema,
+  source: PathPatternSchema,
   destination: PathFragmentSchema,
   overwrite: z.boolean().default(false),
 });
 
+const OutputCaptureSchema = z.object({
+  name: z.string().min(1, "Nom de capture requis"),
+  source: z.enum(["stdout", "exit-code", "regex"]).default("stdout"),
+  pattern: z.string().optional(),
+  flags: z.string().optional(),
+});
+
+export type CommandeurOutputCapture = z.infer<typeof OutputCaptureSchema>;
+
 const ExecSchema = BaseOperationSchema.extend({
   kind: z.literal("exec"),
   command: z.string().min(1),
@@ -93,13 +150,15 @@ const ExecSchema = BaseOperationSchema.extend({
     .default("default"),
   cwd: PathFragmentSchema.optional(),
   env: z.record(z.string(), z.string()).optional(),
+  timeoutSeconds: z.number().int().min(1).optional(),
+  capture: OutputCaptureSchema.optional(),
 });
 
 const ReplaceModeSchema = z.enum(["plain", "regex"]);
 
 const ReplaceInFileSchema = BaseOperationSchema.extend({
   kind: z.literal("replace-in-file"),
-  target: PathFragmentSchema,
+  target: PathPatternSchema,
   search: z.string().min(1),
   replace: z.string().default(""),
   mode: ReplaceModeSchema.default("plain"),
@@ -109,7 +168,7 @@ const ReplaceInFileSchema = BaseOperationSchema.extend({
 
 const RenameSchema = BaseOperationSchema.extend({
   kind: z.literal("rename"),
-  target: PathFragmentSchema,
+  target: PathPatternSchema,
   mode: z.enum(["suffix", "prefix", "change-extension", "replace"]),
   value: z.string().min(1),
   search: z.string().optional(),
@@ -118,7 +177,7 @@ const RenameSchema = BaseOperationSchema.extend({
 
 const MoveSchema = BaseOperationSchema.extend({
   kind: z.literal("move"),
-  source: PathFragmentSchema,
+  source: PathPatternSchema,
   destination: PathFragmentSchema,
   overwrite: z.boolean().default(false),
 });
@@ -136,21 +195,42 @@ const PythonSchema = BaseOperationSchema.extend({
   scriptPath: PathFragmentSchema.optional(),
   entry: z.enum(["inline", "file"]),
   detectExternalImports: z.boolean().default(true),
+  timeoutSeconds: z.number().int().min(1).optional(),
+  capture: OutputCaptureSchema.optional(),
 });
 
 type CommandeurBaseOperation = z.infer<typeof BaseOperationSchema>;
 
-const ConditionTestSchema = z.object({
+const ConditionLeafSchema = z.object({
   selector: CommandeurConditionSelector.optional(),
   operator: CommandeurConditionOperator.optional(),
   value: z.string().optional(),
   pattern: z.string().optional(),
   scope: CommandeurConditionScope.optional(),
   exists: PathFragmentSchema.optional(),
+  variable: z.string().optional(),
+  flags: z.string().optional(),
+  quantifier: CommandeurConditionQuantifier.optional(),
+  command: z.string().optional(),
+  runner: CommandeurConditionRunner.optional(),
+  shell: z.enum(["default", "powershell", "bash", "zsh", "fish"]).optional(),
+  timeoutSeconds: z.number().int().min(1).optional(),
+  ignoreCase: z.boolean().optional(),
   negate: z.boolean().default(false),
 });
 
-export type CommandeurConditionTest = z.infer<typeof ConditionTestSchema>;
+export type CommandeurConditionTest = z.infer<typeof ConditionLeafSchema> & {
+  all?: CommandeurConditionTest[];
+  any?: CommandeurConditionTest[];
+  not?: CommandeurConditionTest;
+};
+
+const ConditionTestSchema: z.ZodType<CommandeurConditionTest> =
+  ConditionLeafSchema.extend({
+    all: z.lazy(() => ConditionTestSchema.array()).optional(),
+    any: z.lazy(() => ConditionTestSchema.array()).optional(),
+    not: z.lazy(() => ConditionTestSchema).optional(),
+  });
 
 export interface CommandeurConditionalOperation
   extends CommandeurBaseOperation {
@@ -168,6 +248,21 @@ const ConditionalSchema: z.ZodType<CommandeurConditionalOperation> =
     else: z.lazy(() => CommandeurOperationSchema.array()).optional(),
   });
 
+export interface CommandeurForEachOperation extends CommandeurBaseOperation {
+  kind: "for-each";
+  pattern?: string;
+  items?: string[];
+  operations: CommandeurOperation[];
+}
+
+const ForEachSchema: z.ZodType<CommandeurForEachOperation> =
+  BaseOperationSchema.extend({
+    kind: z.literal("for-each"),
+    pattern: PathPatternSchema.optional(),
+    items: z.array(z.string()).optional(),
+    operations: z.lazy(() => CommandeurOperationSchema.array()),
+  });
+
 export const CommandeurOperationSchema: z.ZodType<CommandeurOperation> = z.lazy(
   () =>
     z.union([
@@ -181,6 +276,7 @@ export const CommandeurOperationSchema: z.ZodType<CommandeurOperation> = z.lazy(
       MkdirSchema,
       PythonSchema,
       ConditionalSchema,
+      ForEachSchema,
     ])
 );
 
@@ -203,11 +299,15 @@ export type CommandeurOperation =
   | CommandeurMoveOperation
   | CommandeurMkdirOperation
   | CommandeurPythonOperation
-  | CommandeurConditionalOperation;
+  | CommandeurConditionalOperation
+  | CommandeurForEachOperation;
 
 export const CommandeurWorkflowSchema = z.object({
   name: z.string().min(1, "Nom du workflow requis"),
   version: z.string().default("1.0"),
+  maxWorkers: z.number().int().min(1).optional(),
+  defaultTimeoutSeconds: z.number().int().min(1).optional(),
+  variables: z.record(z.string(), z.string()).optional(),
   operations: CommandeurOperationSchema.array(),
 });
 
@@ -235,11 +335,59 @@ export interface CommandeurExecutionLogEntry {
   timestamp: string; // ISO string
   operationId: string;
   operationLabel: string;
+  folder?: string;
   message: string;
   level: CommandeurValidationLevel;
+  artifacts?: string[];
+}
+
+export interface CommandeurExecutionOutputLine {
+  stream: "stdout" | "stderr";
+  text: string;
+}
+
+export interface CommandeurExecutionOutput {
+  operationId: string;
+  folder: string;
+  lines: CommandeurExecutionOutputLine[];
+  droppedLines: number;
+}
+
+export interface CommandeurLiveOutputLine extends CommandeurExecutionOutputLine {
+  folder: string;
+  operationId: string;
+}
+
+export type CommandeurNamePatternMode = "glob" | "regex";
+
+export interface CommandeurFolderSelection {
+  folders?: string[];
+  namePattern?: string;
+  namePatternMode?: CommandeurNamePatternMode;
+  caseInsensitive?: boolean;
+  condition?: CommandeurConditionTest;
+  failedInRun?: string;
+}
+
+export type CommandeurFolderRunStatus =
+  | "succeeded"
+  | "failed"
+  | "interrupted"
+  | "skipped";
+
+export interface CommandeurFolderStatus {
+  folder: string;
+  status: CommandeurFolderRunStatus;
+}
+
+export interface CommandeurExecutionOptions {
+  snapshot?: boolean;
+  selection?: CommandeurFolderSelection;
+  workflowId?: string;
 }
 
 export interface CommandeurExecutionResult {
+  runId: string;
   success: boolean;
   operationsRun: number;
   logFilePath: string;
@@ -247,6 +395,119 @@ export interface CommandeurExecutionResult {
   warnings: CommandeurValidationMessage[];
   errors: CommandeurValidationMessage[];
   outputArchivePath?: string;
+  snapshotPath?: string;
+  timeouts: CommandeurOperationTimeout[];
+  runDirectory: string;
+  artifacts: CommandeurProcessArtifact[];
+  folderStatuses: CommandeurFolderStatus[];
+  runLogPath?: string | null;
+  summary: CommandeurExecutionSummary;
+  summaryCsvPath?: string | null;
+  reportPath?: string | null;
+}
+
+export type CommandeurOperationRunStatus =
+  | "ok"
+  | "failed"
+  | "interrupted"
+  | "skipped"
+  | "disabled"
+  | "branch-not-taken";
+
+export interface CommandeurSummaryColumn {
+  operationId: string;
+  operationLabel: string;
+  depth: number;
+}
+
+export interface CommandeurSummaryCell {
+  operationId: string;
+  status: CommandeurOperationRunStatus;
+  durationMs?: number;
+  error?: string;
+}
+
+export interface CommandeurFolderSummary {
+  folder: string;
+  status: CommandeurFolderRunStatus;
+  durationMs?: number;
+  cells: CommandeurSummaryCell[];
+}
+
+export interface CommandeurExecutionSummary {
+  columns: CommandeurSummaryColumn[];
+  folders: CommandeurFolderSummary[];
+}
+
+export interface CommandeurProcessArtifact {
+  operationId: string;
+  operationLabel: string;
+  folder: string;
+  command: string;
+  exitCode?: number | null;
+  durationMs: number;
+  timedOut: boolean;
+  stdoutPath: string;
+  stderrPath: string;
+  resultPath: string;
+}
+
+export interface CommandeurOperationTimeout {
+  operationId: string;
+  operationLabel: string;
+  folder: string;
+  timeoutSeconds: number;
+}
+
+export interface CommandeurSnapshotRestoreResult {
+  runId: string;
+  restoredFolders: string[];
+  skippedFolders: string[];
+}
+
+export type CommandeurPlannedStepStatus =
+  | "planned"
+  | "no-change"
+  | "disabled"
+  | "would-fail";
+
+export type CommandeurPlannedChangeKind =
+  | "create-file"
+  | "overwrite-file"
+  | "modify-file"
+  | "delete"
+  | "copy"
+  | "move"
+  | "rename"
+  | "create-directory"
+  | "run-command";
+
+export interface CommandeurPlannedChange {
+  kind: CommandeurPlannedChangeKind;
+  path: string;
+  destination?: string;
+  details?: string;
+}
+
+export interface CommandeurPlannedStep {
+  operationId: string;
+  operationLabel: string;
+  parentOperationId?: string;
+  status: CommandeurPlannedStepStatus;
+  message: string;
+  changes: CommandeurPlannedChange[];
+}
+
+export interface CommandeurFolderPlan {
+  folder: string;
+  steps: CommandeurPlannedStep[];
+  haltsExecution: boolean;
+}
+
+export interface CommandeurDryRunResult {
+  folders: CommandeurFolderPlan[];
+  plannedChanges: number;
+  failingFolders: number;
 }
 
 export type CommandeurExecutionStatus =
@@ -255,9 +516,53 @@ export type CommandeurExecutionStatus =
   | "paused"
   | "stopping";
 
+export interface CommandeurExecutionActivity {
+  folder: string;
+  operationId: string;
+  operationLabel: string;
+}
+
+export interface CommandeurExecutionStatusReport {
+  status: CommandeurExecutionStatus;
+  activities: CommandeurExecutionActivity[];
+}
+
 export interface CommandeurExecutionProgress {
   operationsProcessed: number;
   operationsTotal: number;
+  folder?: string;
+}
+
+export interface CommandeurRunHistoryEntry {
+  runId: string;
+  resumedFrom?: string;
+  workflowId?: string;
+  workflowName: string;
+  workflowVersion?: string;
+  workflowHash: string;
+  sourcePath: string;
+  startedAt: string;
+  finishedAt: string;
+  status: CommandeurFolderRunStatus;
+  operationsRun: number;
+  folderCount: number;
+  failedFolderCount: number;
+  warningCount: number;
+  errorCount: number;
+  logFilePath: string;
+  runLogPath?: string;
+  runDirectory: string;
+  outputArchivePath?: string;
+  snapshotPath?: string;
+}
+
+export interface CommandeurRunHistoryFilter {
+  query?: string;
+  workflowId?: string;
+  sourcePath?: string;
+  status?: CommandeurFolderRunStatus;
+  since?: string;
+  until?: string;
 }
 
 export interface CommandeurSavedWorkflowSummary {
@@ -266,12 +571,26 @@ export interface CommandeurSavedWorkflowSummary {
   savedAt: string;
 }
 
+export type CommandeurNestedArchiveMode = "ignore" | "replace" | "keep-both";
+
+export interface CommandeurWorkspaceOptions {
+  nestedArchives?: CommandeurNestedArchiveMode;
+}
+
+export interface CommandeurNestedArchiveExpansion {
+  archive: string;
+  directory?: string;
+  archiveKept: boolean;
+  error?: string;
+}
+
 export interface CommandeurWorkspaceSummary {
   workspaceId: string;
   mode: "zip" | "directory";
   sourcePath: string;
   extractedPath?: string;
   subFolders: string[];
+  expandedArchives: CommandeurNestedArchiveExpansion[];
 }
 
 export interface CommandeurValidationContext {
@@ -10,18 +10,49 @@ export type {
   CommandeurOperation,
   CommandeurWorkflow,
   CommandeurExecutionLogEntry,
+  CommandeurExecutionOutput,
+  CommandeurExecutionOutputLine,
+  CommandeurLiveOutputLine,
   CommandeurExecutionResult,
+  CommandeurExecutionOptions,
+  CommandeurOperationTimeout,
+  CommandeurProcessArtifact,
+  CommandeurSnapshotRestoreResult,
+  CommandeurDryRunResult,
+  CommandeurFolderPlan,
+  CommandeurPlannedStep,
+  CommandeurPlannedChange,
   CommandeurExecutionStatus,
+  CommandeurExecutionActivity,
+  CommandeurExecutionStatusReport,
   CommandeurExecutionProgress,
+  CommandeurRunHistoryEntry,
+  CommandeurRunHistoryFilter,
   CommandeurValidationContext,
   CommandeurValidationMessage,
   CommandeurOperationKind,
   CommandeurWorkspaceSummary,
+  CommandeurWorkspaceOptions,
+  CommandeurNestedArchiveMode,
+  CommandeurNestedArchiveExpansion,
   CommandeurSavedWorkflowSummary,
   CommandeurConditionSelector,
   CommandeurConditionOperator,
   CommandeurConditionScope,
+  CommandeurConditionQuantifier,
+  CommandeurConditionRunner,
   CommandeurConditionTest,
+  CommandeurOutputCapture,
+  CommandeurTargetMatching,
+  CommandeurFolderSelection,
+  CommandeurFolderStatus,
+  CommandeurOperationRunStatus,
+  CommandeurSummaryColumn,
+  CommandeurSummaryCell,
+  CommandeurFolderSummary,
+  CommandeurExecutionSummary,
+  CommandeurFolderRunStatus,
+  CommandeurNamePatternMode,
 } from "./Commandeur";
 export {
   CommandeurOperationSchema,